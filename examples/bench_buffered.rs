@@ -0,0 +1,80 @@
+//! Compares vlogging directly to a vlogger which takes a lock per record
+//! with vlogging through a `Buffered` of it, from several threads at once.
+//!
+//! Run with `cargo run --release --example bench_buffered`.
+
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use v_log::{point, Buffered, Metadata, Record, RecordOwned, VLog};
+
+const THREADS: usize = 8;
+const RECORDS: usize = 100_000;
+
+/// Collects the positions behind a single lock, like a typical backend.
+#[derive(Default)]
+struct LockingVLogger {
+    positions: Mutex<Vec<[f64; 3]>>,
+}
+
+impl LockingVLogger {
+    fn len(&self) -> usize {
+        self.positions.lock().unwrap().len()
+    }
+}
+
+impl VLog for LockingVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        if let Some(pos) = record.visual().position() {
+            self.positions.lock().unwrap().push(pos);
+        }
+    }
+
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        let mut positions = self.positions.lock().unwrap();
+        for record in records {
+            if let Some(pos) = record.visual().position() {
+                positions.push(pos);
+            }
+        }
+    }
+
+    fn clear(&self, _: &str) {
+        self.positions.lock().unwrap().clear();
+    }
+
+    fn flush(&self) {}
+}
+
+fn run(vlogger: &(impl VLog + Sync)) -> Duration {
+    let start = Instant::now();
+    std::thread::scope(|s| {
+        for t in 0..THREADS {
+            s.spawn(move || {
+                for i in 0..RECORDS {
+                    point!(vlogger: vlogger, "main_surface", [i as f64, t as f64], 5.0, Base);
+                }
+                vlogger.flush();
+            });
+        }
+    });
+    start.elapsed()
+}
+
+fn main() {
+    let direct = LockingVLogger::default();
+    let direct_time = run(&direct);
+    assert_eq!(direct.len(), THREADS * RECORDS);
+
+    println!("{} threads, {} records each", THREADS, RECORDS);
+    println!("direct:             {:>10.1?}", direct_time);
+    for capacity in [16, 256, 4096] {
+        let buffered = Buffered::new(LockingVLogger::default(), capacity);
+        let buffered_time = run(&buffered);
+        assert_eq!(buffered.inner().len(), THREADS * RECORDS);
+        println!("buffered ({:>4}):    {:>10.1?}", capacity, buffered_time);
+    }
+}
@@ -0,0 +1,51 @@
+//! Measures the cost of the vlogging macros for a disabled vlogger,
+//! compared to an enabled one which drops the records.
+//!
+//! Run with `cargo run --release --example bench_disabled`.
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+use v_log::{point, point_cloud, Metadata, Record, VLog};
+
+struct NopVLogger {
+    enabled: bool,
+}
+
+impl VLog for NopVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        self.enabled
+    }
+
+    fn vlog(&self, record: &Record) {
+        black_box(record);
+    }
+
+    fn clear(&self, _: &str) {}
+
+    fn flush(&self) {}
+}
+
+fn time(iterations: u32, mut f: impl FnMut(u32)) -> Duration {
+    let start = Instant::now();
+    for i in 0..iterations {
+        f(black_box(i));
+    }
+    start.elapsed() / iterations
+}
+
+fn main() {
+    let points: Vec<[f64; 3]> = (0..1000).map(|i| [i as f64, 0.0, 0.0]).collect();
+    for enabled in [false, true] {
+        let vlogger = NopVLogger { enabled };
+        let point = time(10_000_000, |i| {
+            point!(vlogger: vlogger, "main_surface", [i as f64, 0.0], 5.0, Base, "o", "{}", i);
+        });
+        let cloud = time(10_000, |_| {
+            point_cloud!(vlogger: vlogger, "main_surface", points.iter().copied(), 2.0, Base);
+        });
+        println!(
+            "enabled: {:5}  point!: {:>8.1?}  point_cloud! (1000 points): {:>8.1?}",
+            enabled, point, cloud
+        );
+    }
+}
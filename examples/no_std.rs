@@ -0,0 +1,43 @@
+//! Checks that the vlogging macros build in a `no_std` crate.
+//!
+//! `cargo test --no-default-features` builds it, or build it alone with
+//! `cargo build --no-default-features --example no_std`.
+
+#![no_std]
+
+use core::sync::atomic::{AtomicUsize, Ordering};
+use v_log::{clear, label, message, point, polyline, Metadata, Record, VLog};
+
+struct CountingVLogger(AtomicUsize);
+
+impl VLog for CountingVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, _: &Record) {
+        self.0.fetch_add(1, Ordering::Relaxed);
+    }
+
+    fn clear(&self, _: &str) {}
+
+    fn flush(&self) {}
+}
+
+static VLOGGER: CountingVLogger = CountingVLogger(AtomicUsize::new(0));
+
+/// Installs the vlogger and draws a few records.
+///
+/// # Safety
+///
+/// Must not be called while other threads use the vlogging functions.
+pub unsafe fn run() -> usize {
+    v_log::set_vlogger_racy(&VLOGGER).unwrap();
+
+    clear!("main_surface");
+    point!("main_surface", [1.0, 2.0], 5.0, Base, "o", "p = {}", 1);
+    polyline!("main_surface", ([0.0, 0.0], [1.0, 1.0]), 1.0, X, "--");
+    label!("main_surface", [0.0, 0.0], (12.0, Base, "<"), "origin");
+    message!("main_surface", "done");
+    VLOGGER.0.load(Ordering::Relaxed)
+}
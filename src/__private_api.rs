@@ -1,20 +1,39 @@
 //! WARNING: this is not part of the crate's public API and is subject to change at any time
 
+#[cfg(feature = "alloc")]
+use crate::ImageFormat;
+#[cfg(feature = "alloc")]
+use crate::RecordOwned;
 use crate::{
-    vlogger, Color, LineStyle, Metadata, MetadataBuilder, PointStyle, Record, TextAlignment, VLog,
-    Visual,
+    vlogger, Color, DrawMode, GridPlane, LabelStyle, LineStyle, Metadata, MetadataBuilder,
+    PointStyle, Record, TextAlignment, VLog, VLogError, View, Visual,
 };
-use std::fmt::Arguments;
-use std::panic::Location;
-pub use std::{format_args, module_path};
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use core::fmt::{self, Arguments};
+use core::panic::Location;
+pub use core::{compile_error, format_args, module_path, stringify};
+#[cfg(feature = "std")]
+pub use std::time::Instant;
 
 // VLog implementation.
 
+// The target of the macros without a `target:`, which is the module path of the call site.
+// All macros default to this, so vloggers can rely on `target == module_path` for them.
+#[macro_export]
+#[doc(hidden)]
+macro_rules! __default_target {
+    () => {
+        $crate::__private_api::module_path!()
+    };
+}
+
 #[macro_export]
 #[doc(hidden)]
 macro_rules! __abs_module_path {
     () => {
-        $crate::__abs_module_path!($crate::__private_api::module_path!())
+        $crate::__abs_module_path!($crate::__default_target!())
     };
     ($target:expr) => {
         (
@@ -38,28 +57,163 @@ macro_rules! __abs_module_path {
 }
 
 /// The global vlogger proxy.
+///
+/// Records are buffered in a `LocalBatch` if there is one,
+/// which the other calls submit first to keep the order.
 #[derive(Debug)]
 pub struct GlobalVLogger;
 
+impl GlobalVLogger {
+    /// Clear a surface, even if repeated clears are coalesced.
+    pub(crate) fn clear_always(&self, surface: &str) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().clear(surface)
+    }
+}
+
 impl VLog for GlobalVLogger {
     fn enabled(&self, metadata: &Metadata) -> bool {
         vlogger().enabled(metadata)
     }
 
     fn vlog(&self, record: &Record) {
+        #[cfg(feature = "std")]
+        if crate::batch::push(record) {
+            return;
+        }
         vlogger().vlog(record)
     }
 
     fn clear(&self, surface: &str) {
-        vlogger().clear(surface)
+        #[cfg(feature = "std")]
+        if !crate::surfaces::take_clear(surface) {
+            return;
+        }
+        self.clear_always(surface)
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().remove_surface(surface)
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().transaction(surface, f)
+    }
+
+    fn begin_frame(&self, surface: &str) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().begin_frame(surface)
+    }
+
+    fn end_frame(&self, surface: &str) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().end_frame(surface)
+    }
+
+    fn push_group(&self, name: &str) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().push_group(name)
+    }
+
+    fn pop_group(&self) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().pop_group()
     }
 
     fn flush(&self) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
         vlogger().flush()
     }
+
+    fn set_draw_mode(&self, surface: &str, mode: DrawMode) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().set_draw_mode(surface, mode)
+    }
+
+    fn set_view(&self, surface: &str, view: &View) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().set_view(surface, view)
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().snapshot(surface, path)
+    }
+
+    fn quantize_color(&self, color: Color) -> Color {
+        vlogger().quantize_color(color)
+    }
+
+    fn max_records(&self, surface: &str) -> Option<usize> {
+        vlogger().max_records(surface)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        vlogger().enabled_record(record)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        vlogger().vlog_batch(records)
+    }
+
+    fn on_shutdown(&self) {
+        #[cfg(feature = "std")]
+        crate::batch::flush();
+        vlogger().on_shutdown()
+    }
+
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        vlogger().as_any()
+    }
+}
+
+// Clears are housekeeping, so they are forwarded even if the target is disabled,
+// otherwise a filtering vlogger would keep stale drawings.
+pub fn clear<L>(vlogger: &L, _target: &str, surface: &str)
+where
+    L: VLog,
+{
+    vlogger.clear(surface);
+}
+
+// Removing a surface is housekeeping like clearing it.
+pub fn remove_surface<L>(vlogger: &L, _target: &str, surface: &str)
+where
+    L: VLog,
+{
+    vlogger.remove_surface(surface);
+}
+
+pub fn transaction<L, F>(vlogger: &L, target: &str, surface: &str, f: F)
+where
+    L: VLog,
+    F: Fn(&dyn VLog),
+{
+    if vlogger.enabled(
+        &MetadataBuilder::new()
+            .target(target)
+            .surface(surface)
+            .build(),
+    ) {
+        vlogger.transaction(surface, &f);
+    }
 }
 
-pub fn clear<L>(vlogger: &L, target: &str, surface: &str)
+pub fn begin_frame<L>(vlogger: &L, target: &str, surface: &str)
 where
     L: VLog,
 {
@@ -69,7 +223,226 @@ where
             .surface(surface)
             .build(),
     ) {
-        vlogger.clear(&surface);
+        vlogger.begin_frame(surface);
+    }
+}
+
+pub fn end_frame<L>(vlogger: &L, target: &str, surface: &str)
+where
+    L: VLog,
+{
+    if vlogger.enabled(
+        &MetadataBuilder::new()
+            .target(target)
+            .surface(surface)
+            .build(),
+    ) {
+        vlogger.end_frame(surface);
+    }
+}
+
+pub fn set_view<L>(vlogger: &L, target: &str, surface: &str, view: &View)
+where
+    L: VLog,
+{
+    if vlogger.enabled(
+        &MetadataBuilder::new()
+            .target(target)
+            .surface(surface)
+            .build(),
+    ) {
+        vlogger.set_view(surface, view);
+    }
+}
+
+pub fn snapshot<L>(vlogger: &L, target: &str, surface: &str, path: &str) -> Result<(), VLogError>
+where
+    L: VLog,
+{
+    if vlogger.enabled(
+        &MetadataBuilder::new()
+            .target(target)
+            .surface(surface)
+            .build(),
+    ) {
+        vlogger.snapshot(surface, path)
+    } else {
+        Err(VLogError::Disabled)
+    }
+}
+
+/// Pops the group pushed by [`group`] when dropped.
+pub struct GroupGuard<'a, L: VLog> {
+    vlogger: &'a L,
+}
+
+impl<L: VLog> fmt::Debug for GroupGuard<'_, L> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("GroupGuard").finish_non_exhaustive()
+    }
+}
+
+impl<L: VLog> Drop for GroupGuard<'_, L> {
+    fn drop(&mut self) {
+        self.vlogger.pop_group();
+    }
+}
+
+pub fn group<'a, L>(vlogger: &'a L, name: &str) -> GroupGuard<'a, L>
+where
+    L: VLog,
+{
+    vlogger.push_group(name);
+    GroupGuard { vlogger }
+}
+
+pub fn clear_surfaces<'a, L>(vlogger: &L, target: &str, surfaces: impl IntoIterator<Item = &'a str>)
+where
+    L: VLog,
+{
+    for surface in surfaces {
+        clear(vlogger, target, surface);
+    }
+}
+
+/// Optional keyword arguments of the macros, e.g. `world_width: 2.0`.
+#[derive(Clone, Copy, Debug)]
+pub struct Extra<'a> {
+    world_width: Option<f64>,
+    opacity: f32,
+    rotation: f64,
+    tooltip: Option<&'a str>,
+    path_id: u64,
+    show_pos: bool,
+    z_order: i32,
+    #[cfg(feature = "std")]
+    ttl: Option<std::time::Duration>,
+    #[cfg(feature = "kv")]
+    key_values: &'a [(&'a str, crate::kv::Value<'a>)],
+}
+
+impl Default for Extra<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<'a> Extra<'a> {
+    pub const fn new() -> Self {
+        Extra {
+            world_width: None,
+            opacity: 1.0,
+            rotation: 0.0,
+            tooltip: None,
+            path_id: 0,
+            show_pos: false,
+            z_order: 0,
+            #[cfg(feature = "std")]
+            ttl: None,
+            #[cfg(feature = "kv")]
+            key_values: &[],
+        }
+    }
+
+    pub const fn world_width(mut self, width: f64) -> Self {
+        self.world_width = Some(width);
+        self
+    }
+
+    pub const fn opacity(mut self, opacity: f32) -> Self {
+        self.opacity = opacity;
+        self
+    }
+
+    pub const fn rot(mut self, rotation: f64) -> Self {
+        self.rotation = rotation;
+        self
+    }
+
+    pub const fn tip(mut self, tooltip: &'a str) -> Self {
+        self.tooltip = Some(tooltip);
+        self
+    }
+
+    pub const fn path_id(mut self, id: u64) -> Self {
+        self.path_id = id;
+        self
+    }
+
+    pub const fn show_pos(mut self) -> Self {
+        self.show_pos = true;
+        self
+    }
+
+    pub const fn z_order(mut self, z_order: i32) -> Self {
+        self.z_order = z_order;
+        self
+    }
+
+    #[cfg(feature = "std")]
+    pub const fn ttl(mut self, ttl: std::time::Duration) -> Self {
+        self.ttl = Some(ttl);
+        self
+    }
+
+    #[cfg(feature = "kv")]
+    pub const fn key_values(mut self, key_values: &'a [(&'a str, crate::kv::Value<'a>)]) -> Self {
+        self.key_values = key_values;
+        self
+    }
+}
+
+/// A position argument of the macros, which is either `IntoIterator<Item = f64>` or a
+/// [`Position`](crate::Position). The method call picks the first one which applies,
+/// as `ViaIntoIter` is implemented for `PosArg<T>` and `ViaPosition` for `&PosArg<T>`.
+#[derive(Debug)]
+pub struct PosArg<T>(pub T);
+
+pub trait ViaIntoIter {
+    type Coords: IntoIterator<Item = f64>;
+    fn into_pos(self) -> Self::Coords;
+}
+
+impl<T: IntoIterator<Item = f64>> ViaIntoIter for PosArg<T> {
+    type Coords = T;
+
+    #[inline]
+    fn into_pos(self) -> T {
+        self.0
+    }
+}
+
+pub trait ViaPosition {
+    type Coords: IntoIterator<Item = f64>;
+    fn into_pos(self) -> Self::Coords;
+}
+
+impl<T: crate::Position> ViaPosition for &PosArg<T> {
+    type Coords = T::Coords;
+
+    #[inline]
+    fn into_pos(self) -> T::Coords {
+        self.0.coords()
+    }
+}
+
+/// The message of a record followed by its position, for the `show_pos` keyword.
+struct WithPos<'a> {
+    args: Arguments<'a>,
+    pos: [f64; 3],
+}
+
+impl fmt::Display for WithPos<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.args.as_str() != Some("") {
+            write!(f, "{} ", self.args)?;
+        }
+        let [x, y, z] = self.pos;
+        if z == 0.0 {
+            write!(f, "({:.2}, {:.2})", x, y)
+        } else {
+            write!(f, "({:.2}, {:.2}, {:.2})", x, y, z)
+        }
     }
 }
 
@@ -81,24 +454,106 @@ fn vlog<'a, L>(
     color: Color,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
-    let mut builder = Record::builder();
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    vlog_enabled(
+        vlogger,
+        args,
+        visual,
+        size,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+
+// `vlog` after the target and surface were found to be enabled,
+// e.g. by a helper which checks that before collecting the points.
+fn vlog_enabled<L>(
+    vlogger: &L,
+    args: Arguments,
+    visual: Visual,
+    size: f64,
+    color: Color,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let (true, Some(pos)) = (extra.show_pos, visual.position()) {
+        let extra = Extra {
+            show_pos: false,
+            ..*extra
+        };
+        return vlog_enabled(
+            vlogger,
+            format_args!("{}", WithPos { args, pos }),
+            visual,
+            size,
+            color,
+            surface,
+            target_module_path_and_loc,
+            &extra,
+        );
+    }
+
     let (target, file_path, module_path, loc) = target_module_path_and_loc;
+    #[cfg(feature = "std")]
+    let visual = match crate::surfaces::scale(surface) {
+        Some(scale) => visual.scale(scale),
+        None => visual,
+    };
+
+    let mut builder = Record::builder();
 
     builder
         .args(args)
         .visual(visual)
         .size(size)
         .color(color)
+        .opacity(extra.opacity)
+        .tooltip(extra.tooltip)
+        .path_id(extra.path_id)
+        .z_order(extra.z_order)
         .surface(surface)
         .target(target)
-        .module_path_static(Some(module_path))
-        .file(Some(file_path))
-        .line(Some(loc.line()));
+        .call_site_id(call_site_id(loc));
+    #[cfg(feature = "std")]
+    builder.ttl(extra.ttl);
+    #[cfg(feature = "kv")]
+    builder.key_values(extra.key_values);
+    if crate::capture_location() {
+        builder
+            .module_path_static(Some(module_path))
+            .file(Some(file_path))
+            .line(Some(loc.line()));
+    }
 
-    vlogger.vlog(&builder.build());
+    let record = builder.build();
+    #[cfg(feature = "std")]
+    crate::validate::check(&record);
+    if vlogger.enabled_record(&record) {
+        #[cfg(feature = "std")]
+        if crate::surfaces::take_auto_clear(surface, crate::FRAME.load(crate::Ordering::Relaxed)) {
+            // the global vlogger submits the records of a `LocalBatch` first,
+            // so the clear doesn't remove the records of this frame instead.
+            vlogger.clear(surface);
+        }
+        vlogger.vlog(&record);
+        #[cfg(feature = "std")]
+        crate::surfaces::note_draw(surface);
+        #[cfg(feature = "std")]
+        crate::tap::notify(&record);
+        #[cfg(feature = "std")]
+        crate::auto_flush::note_draw();
+    }
 }
 
 pub fn vlog_point<'a, P: IntoIterator<Item = f64>, L>(
@@ -107,59 +562,412 @@ pub fn vlog_point<'a, P: IntoIterator<Item = f64>, L>(
     pos: P,
     diameter: f64,
     color: Color,
-    style: PointStyle,
+    style: PointStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let PointStyle::None = style {
+        return;
+    }
+    let mut pos = pos.into_iter();
+    vlog(
+        vlogger,
+        args,
+        Visual::Point {
+            x: pos.next().unwrap_or(0.0),
+            y: pos.next().unwrap_or(0.0),
+            z: pos.next().unwrap_or(0.0),
+            style,
+        },
+        diameter,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+pub fn vlog_aabb<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    corner1: P,
+    corner2: P,
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let LineStyle::None = style {
+        return;
+    }
+    let mut corner1 = corner1.into_iter();
+    let mut corner2 = corner2.into_iter();
+    let mut min = [0.0; 3];
+    let mut max = [0.0; 3];
+    for i in 0..3 {
+        let (a, b) = (corner1.next().unwrap_or(0.0), corner2.next().unwrap_or(0.0));
+        (min[i], max[i]) = if b < a { (b, a) } else { (a, b) };
+    }
+    vlog(
+        vlogger,
+        args,
+        Visual::Aabb { min, max, style },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+/// Normalizes a rotation quaternion `[x, y, z, w]`, see [`obb!`](crate::obb).
+fn unit_quaternion(rotation: impl IntoIterator<Item = f64>) -> [f64; 4] {
+    let mut iter = rotation.into_iter();
+    let rotation = [
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(1.0),
+    ];
+    let norm_sqr: f64 = rotation.iter().map(|c| c * c).sum();
+    if norm_sqr == 0.0 || !norm_sqr.is_finite() {
+        return [0.0, 0.0, 0.0, 1.0];
+    }
+    let norm = sqrt(norm_sqr);
+    if norm - 1.0 > 1e-6 || 1.0 - norm > 1e-6 {
+        return rotation.map(|c| c / norm);
+    }
+    rotation
+}
+/// `f64::sqrt` is not available in `core`, so this uses Newton's method for a positive finite `x`.
+fn sqrt(x: f64) -> f64 {
+    // halving the exponent gives an estimate within 6%, and every step about squares the error.
+    let mut y = f64::from_bits((x.to_bits() >> 1) + (1023 << 51));
+    for _ in 0..5 {
+        y = 0.5 * (y + x / y);
+    }
+    y
+}
+pub fn vlog_axes<P: IntoIterator<Item = f64>, R: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    origin: P,
+    rotation: R,
+    length: f64,
+    thickness: f64,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    let mut iter = origin.into_iter();
+    let origin = [
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+    ];
+    vlog(
+        vlogger,
+        args,
+        Visual::Axes {
+            origin,
+            rotation: unit_quaternion(rotation),
+            length,
+        },
+        thickness,
+        Color::Base,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+pub fn vlog_obb<P: IntoIterator<Item = f64>, R: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    center: P,
+    half_extents: P,
+    rotation: R,
+    thickness: f64,
+    color: Color,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    let mut iter = center.into_iter();
+    let center = [
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+    ];
+    let mut iter = half_extents.into_iter();
+    let half_extents = [
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+    ];
+    vlog(
+        vlogger,
+        args,
+        Visual::Obb {
+            center,
+            half_extents,
+            rotation: unit_quaternion(rotation),
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+pub fn vlog_ellipse<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    pos: P,
+    (rx, ry): (f64, f64),
+    rotation: f64,
+    filled: bool,
+    thickness: f64,
+    color: Color,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    // `f64::abs` is not available in `core`.
+    let abs = |r: f64| if r < 0.0 { -r } else { r };
+    let mut pos = pos.into_iter();
+    vlog(
+        vlogger,
+        args,
+        Visual::Ellipse {
+            cx: pos.next().unwrap_or(0.0),
+            cy: pos.next().unwrap_or(0.0),
+            cz: pos.next().unwrap_or(0.0),
+            rx: abs(rx),
+            ry: abs(ry),
+            rotation,
+            filled,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+pub fn vlog_line<'a, P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    pos1: P,
+    pos2: P,
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let LineStyle::None = style {
+        return;
+    }
+    let mut pos1 = pos1.into_iter();
+    let mut pos2 = pos2.into_iter();
+    vlog(
+        vlogger,
+        args,
+        Visual::Line {
+            x1: pos1.next().unwrap_or(0.0),
+            y1: pos1.next().unwrap_or(0.0),
+            z1: pos1.next().unwrap_or(0.0),
+            x2: pos2.next().unwrap_or(0.0),
+            y2: pos2.next().unwrap_or(0.0),
+            z2: pos2.next().unwrap_or(0.0),
+            style,
+            world_thickness: extra.world_width,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+pub fn vlog_ray<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    origin: P,
+    direction: P,
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let LineStyle::None = style {
+        return;
+    }
+    let mut iter = origin.into_iter();
+    let origin = [
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+    ];
+    let mut iter = direction.into_iter();
+    let direction = [
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+    ];
+    let norm_sqr: f64 = direction.iter().map(|c| c * c).sum();
+    // a zero direction has no ray to draw.
+    if norm_sqr == 0.0 {
+        return;
+    }
+    // normalizing requires `sqrt`, which is not available in `core`.
+    #[cfg(feature = "std")]
+    let direction = if norm_sqr.is_finite() {
+        let norm = norm_sqr.sqrt();
+        direction.map(|c| c / norm)
+    } else {
+        direction
+    };
+    vlog(
+        vlogger,
+        args,
+        Visual::Ray {
+            origin,
+            direction,
+            style,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+pub fn vlog_arc<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    center: P,
+    radius: f64,
+    (start_angle, end_angle): (f64, f64),
+    sector: bool,
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let LineStyle::None = style {
+        return;
+    }
+    let mut center = center.into_iter();
+    vlog(
+        vlogger,
+        args,
+        Visual::Arc {
+            cx: center.next().unwrap_or(0.0),
+            cy: center.next().unwrap_or(0.0),
+            cz: center.next().unwrap_or(0.0),
+            radius,
+            start_angle,
+            end_angle,
+            style,
+            sector,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+pub fn vlog_bezier<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    points: [P; 4],
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
-    let mut pos = pos.into_iter();
+    if let LineStyle::None = style {
+        return;
+    }
+    let [p0, p1, p2, p3] = points.map(|p| {
+        let mut iter = p.into_iter();
+        [
+            iter.next().unwrap_or(0.0),
+            iter.next().unwrap_or(0.0),
+            iter.next().unwrap_or(0.0),
+        ]
+    });
     vlog(
         vlogger,
         args,
-        Visual::Point {
-            x: pos.next().unwrap_or(0.0),
-            y: pos.next().unwrap_or(0.0),
-            z: pos.next().unwrap_or(0.0),
+        Visual::Bezier {
+            p0,
+            p1,
+            p2,
+            p3,
             style,
         },
-        diameter,
+        thickness,
         color,
         surface,
         target_module_path_and_loc,
+        extra,
     );
 }
-pub fn vlog_line<'a, P: IntoIterator<Item = f64>, L>(
+pub fn vlog_grid<L>(
     vlogger: &L,
     args: Arguments,
-    pos1: P,
-    pos2: P,
-    thickness: f64,
+    spacing: f64,
+    extent: f64,
+    plane: GridPlane,
     color: Color,
-    style: LineStyle,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
-    let mut pos1 = pos1.into_iter();
-    let mut pos2 = pos2.into_iter();
     vlog(
         vlogger,
         args,
-        Visual::Line {
-            x1: pos1.next().unwrap_or(0.0),
-            y1: pos1.next().unwrap_or(0.0),
-            z1: pos1.next().unwrap_or(0.0),
-            x2: pos2.next().unwrap_or(0.0),
-            y2: pos2.next().unwrap_or(0.0),
-            z2: pos2.next().unwrap_or(0.0),
-            style,
+        Visual::Grid {
+            spacing,
+            extent,
+            plane,
         },
-        thickness,
+        0.0,
         color,
         surface,
         target_module_path_and_loc,
+        extra,
     );
 }
 pub fn vlog_arrow<'a, P: IntoIterator<Item = f64>, L>(
@@ -172,6 +980,7 @@ pub fn vlog_arrow<'a, P: IntoIterator<Item = f64>, L>(
     color: Color,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
@@ -182,6 +991,7 @@ pub fn vlog_arrow<'a, P: IntoIterator<Item = f64>, L>(
         iter.next().unwrap_or(0.0),
     ];
     let mut iter = dir.into_iter();
+    #[cfg_attr(not(feature = "std"), allow(unused_mut))]
     let mut dir = [
         iter.next().unwrap_or(0.0),
         iter.next().unwrap_or(0.0),
@@ -204,6 +1014,10 @@ pub fn vlog_arrow<'a, P: IntoIterator<Item = f64>, L>(
             }
         }
     }
+    // normalizing requires `sqrt`, which is not available in `core`.
+    // The macro makes sure `len` is always `None` without `std`.
+    #[cfg(not(feature = "std"))]
+    let _ = len;
     vlog(
         vlogger,
         args,
@@ -215,11 +1029,13 @@ pub fn vlog_arrow<'a, P: IntoIterator<Item = f64>, L>(
             y2: pos[1] + dir[1],
             z2: pos[2] + dir[2],
             style: LineStyle::Arrow,
+            world_thickness: extra.world_width,
         },
         thickness,
         color,
         surface,
         target_module_path_and_loc,
+        extra,
     );
 }
 pub fn vlog_closed_line<'a, P: IntoIterator<Item = f64> + Clone, L>(
@@ -232,9 +1048,14 @@ pub fn vlog_closed_line<'a, P: IntoIterator<Item = f64> + Clone, L>(
     style: LineStyle,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
+    if let LineStyle::None = style {
+        return;
+    }
+    let extra = &extra.path_id(next_path_id());
     let mut first = None;
     let mut last = None;
     let mut count = 0;
@@ -251,6 +1072,7 @@ pub fn vlog_closed_line<'a, P: IntoIterator<Item = f64> + Clone, L>(
                 style,
                 surface,
                 target_module_path_and_loc,
+                extra,
             );
         } else {
             first = Some(p.clone()); // TODO is this ok?!?
@@ -272,6 +1094,7 @@ pub fn vlog_closed_line<'a, P: IntoIterator<Item = f64> + Clone, L>(
         style,
         surface,
         target_module_path_and_loc,
+        extra,
     );
     let count = count as f64;
     sum[0] /= count;
@@ -284,8 +1107,11 @@ pub fn vlog_closed_line<'a, P: IntoIterator<Item = f64> + Clone, L>(
         textsize,
         color,
         TextAlignment::Center,
+        None,
+        LabelStyle::WordWrap,
         surface,
         target_module_path_and_loc,
+        extra,
     );
 }
 pub fn vlog_axis<'a, P: IntoIterator<Item = f64> + Clone, L>(
@@ -296,6 +1122,7 @@ pub fn vlog_axis<'a, P: IntoIterator<Item = f64> + Clone, L>(
     thickness: f64,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
@@ -328,9 +1155,457 @@ pub fn vlog_axis<'a, P: IntoIterator<Item = f64> + Clone, L>(
             LineStyle::Arrow,
             surface,
             target_module_path_and_loc,
+            extra,
+        );
+    }
+}
+pub fn vlog_errorbar<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    pos: P,
+    extents: impl IntoIterator<Item = f64>,
+    size: f64,
+    color: Color,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    let mut iter = pos.into_iter();
+    let pos = [
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+        iter.next().unwrap_or(0.0),
+    ];
+    let extra = &extra.path_id(next_path_id());
+    vlog_point(
+        vlogger,
+        args,
+        pos,
+        size,
+        color,
+        crate::default_point_style(),
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+    for (axis, extent) in extents.into_iter().enumerate().take(3) {
+        for sign in [1.0, -1.0] {
+            let mut end = pos;
+            end[axis] += sign * extent;
+            vlog_line(
+                vlogger,
+                format_args!(""),
+                pos,
+                end,
+                size,
+                color,
+                LineStyle::Simple,
+                surface,
+                target_module_path_and_loc,
+                extra,
+            );
+        }
+    }
+}
+pub fn vlog_path<P: IntoIterator<Item = F>, F: Borrow<f64>, L>(
+    vlogger: &L,
+    points: impl IntoIterator<Item = P>,
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+) where
+    L: VLog,
+{
+    if let LineStyle::None = style {
+        return;
+    }
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    let extra = Extra::new().path_id(next_path_id());
+    let mut points = points
+        .into_iter()
+        .map(|p| {
+            let mut iter = p.into_iter();
+            [
+                iter.next().map_or(0.0, |v| *v.borrow()),
+                iter.next().map_or(0.0, |v| *v.borrow()),
+                iter.next().map_or(0.0, |v| *v.borrow()),
+            ]
+        })
+        .peekable();
+    let Some(mut last) = points.next() else {
+        return;
+    };
+    while let Some(p) = points.next() {
+        // the last segment is always an arrow to show the direction.
+        let style = if points.peek().is_some() {
+            style
+        } else {
+            LineStyle::Arrow
+        };
+        vlog_line(
+            vlogger,
+            format_args!(""),
+            last,
+            p,
+            thickness,
+            color,
+            style,
+            surface,
+            target_module_path_and_loc,
+            &extra,
+        );
+        last = p;
+    }
+}
+#[cfg(feature = "std")]
+pub fn vlog_gradient_line<P: IntoIterator<Item = F>, F: Borrow<f64>, L>(
+    vlogger: &L,
+    points: impl IntoIterator<Item = P>,
+    thickness: f64,
+    colors: (Color, Color),
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+) where
+    L: VLog,
+{
+    if let LineStyle::None = style {
+        return;
+    }
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    let extra = Extra::new().path_id(next_path_id());
+    let points: Vec<[f64; 3]> = points
+        .into_iter()
+        .map(|p| {
+            let mut iter = p.into_iter();
+            [
+                iter.next().map_or(0.0, |v| *v.borrow()),
+                iter.next().map_or(0.0, |v| *v.borrow()),
+                iter.next().map_or(0.0, |v| *v.borrow()),
+            ]
+        })
+        .collect();
+    if points.len() < 2 {
+        return;
+    }
+    let lengths: Vec<f64> = points
+        .windows(2)
+        .map(|w| {
+            let d = [w[1][0] - w[0][0], w[1][1] - w[0][1], w[1][2] - w[0][2]];
+            (d[0] * d[0] + d[1] * d[1] + d[2] * d[2]).sqrt()
+        })
+        .collect();
+    let total: f64 = lengths.iter().sum();
+    let mut start = 0.0;
+    for (w, len) in points.windows(2).zip(&lengths) {
+        // color each segment by the arc length at its center.
+        let t = if total > 0.0 {
+            (start + len * 0.5) / total
+        } else {
+            0.0
+        };
+        start += len;
+        vlog_line(
+            vlogger,
+            format_args!(""),
+            w[0],
+            w[1],
+            thickness,
+            colors.0.lerp(&colors.1, t),
+            style,
+            surface,
+            target_module_path_and_loc,
+            &extra,
         );
     }
 }
+#[cfg(feature = "alloc")]
+pub fn vlog_image<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    pos: P,
+    (width, height): (u32, u32),
+    format: ImageFormat,
+    data: &[u8],
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    let mut pos = pos.into_iter();
+    vlog_enabled(
+        vlogger,
+        args,
+        Visual::Image {
+            x: pos.next().unwrap_or(0.0),
+            y: pos.next().unwrap_or(0.0),
+            z: pos.next().unwrap_or(0.0),
+            width,
+            height,
+            format,
+            data: data.to_vec(),
+        },
+        0.0,
+        Color::Base,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+#[cfg(feature = "alloc")]
+pub fn vlog_point_cloud<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    points: impl IntoIterator<Item = P>,
+    diameter: f64,
+    color: Color,
+    style: PointStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let PointStyle::None = style {
+        return;
+    }
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    let points = points
+        .into_iter()
+        .map(|p| {
+            let mut iter = p.into_iter();
+            [
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+            ]
+        })
+        .collect();
+    vlog_enabled(
+        vlogger,
+        args,
+        Visual::PointCloud { points, style },
+        diameter,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+#[cfg(feature = "alloc")]
+pub fn vlog_mesh<P: IntoIterator<Item = f64>, I: Borrow<u32>, L>(
+    vlogger: &L,
+    args: Arguments,
+    vertices: impl IntoIterator<Item = P>,
+    indices: impl IntoIterator<Item = I>,
+    shaded: bool,
+    thickness: f64,
+    color: Color,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    let vertices: Vec<[f64; 3]> = vertices
+        .into_iter()
+        .map(|p| {
+            let mut iter = p.into_iter();
+            [
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+            ]
+        })
+        .collect();
+    let indices: Vec<u32> = indices.into_iter().map(|i| *i.borrow()).collect();
+    // invalid meshes are skipped instead of panicking in debug code.
+    if indices.len() % 3 != 0 || indices.iter().any(|&i| i as usize >= vertices.len()) {
+        return;
+    }
+    vlog_enabled(
+        vlogger,
+        args,
+        Visual::Mesh {
+            vertices,
+            indices,
+            shaded,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+#[cfg(feature = "alloc")]
+pub fn vlog_vector_field<P: IntoIterator<Item = f64>, V: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    origins: impl IntoIterator<Item = P>,
+    vectors: impl IntoIterator<Item = V>,
+    scale: f64,
+    thickness: f64,
+    color: Color,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    fn to_array(p: impl IntoIterator<Item = f64>) -> [f64; 3] {
+        let mut iter = p.into_iter();
+        [
+            iter.next().unwrap_or(0.0),
+            iter.next().unwrap_or(0.0),
+            iter.next().unwrap_or(0.0),
+        ]
+    }
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    // zip truncates to the shorter of the two lists.
+    let (origins, vectors) = origins
+        .into_iter()
+        .zip(vectors)
+        .map(|(o, v)| (to_array(o), to_array(v)))
+        .unzip();
+    vlog_enabled(
+        vlogger,
+        args,
+        Visual::VectorField {
+            origins,
+            vectors,
+            scale,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+#[cfg(feature = "alloc")]
+pub fn vlog_polygon<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    points: impl IntoIterator<Item = P>,
+    filled: bool,
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if !filled {
+        if let LineStyle::None = style {
+            return;
+        }
+    }
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    let points: Vec<[f64; 3]> = points
+        .into_iter()
+        .map(|p| {
+            let mut iter = p.into_iter();
+            [
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+            ]
+        })
+        .collect();
+    if points.len() < 3 {
+        return;
+    }
+    vlog_enabled(
+        vlogger,
+        args,
+        Visual::Polygon {
+            points,
+            filled,
+            style,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
+#[cfg(feature = "alloc")]
+pub fn vlog_spline<P: IntoIterator<Item = f64>, L>(
+    vlogger: &L,
+    args: Arguments,
+    points: impl IntoIterator<Item = P>,
+    tension: f64,
+    closed: bool,
+    thickness: f64,
+    color: Color,
+    style: LineStyle,
+    surface: &str,
+    target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
+) where
+    L: VLog,
+{
+    if let LineStyle::None = style {
+        return;
+    }
+    if !enabled(vlogger, surface, target_module_path_and_loc.0) {
+        return;
+    }
+    let points: Vec<[f64; 3]> = points
+        .into_iter()
+        .map(|p| {
+            let mut iter = p.into_iter();
+            [
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+                iter.next().unwrap_or(0.0),
+            ]
+        })
+        .collect();
+    if points.len() < 2 {
+        return;
+    }
+    vlog_enabled(
+        vlogger,
+        args,
+        Visual::Spline {
+            points,
+            tension,
+            closed,
+            style,
+        },
+        thickness,
+        color,
+        surface,
+        target_module_path_and_loc,
+        extra,
+    );
+}
 pub fn vlog_label<'a, P: IntoIterator<Item = f64>, L>(
     vlogger: &L,
     args: Arguments,
@@ -338,8 +1613,11 @@ pub fn vlog_label<'a, P: IntoIterator<Item = f64>, L>(
     size: f64,
     color: Color,
     alignment: TextAlignment,
+    max_width: Option<f64>,
+    style: LabelStyle,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
@@ -352,11 +1630,15 @@ pub fn vlog_label<'a, P: IntoIterator<Item = f64>, L>(
             y: pos.next().unwrap_or(0.0),
             z: pos.next().unwrap_or(0.0),
             alignment,
+            rotation: extra.rotation,
+            max_width,
+            style,
         },
         size,
         color,
         surface,
         target_module_path_and_loc,
+        extra,
     );
 }
 #[inline(always)]
@@ -366,6 +1648,7 @@ pub fn vlog_message<'a, L>(
     color: Color,
     surface: &str,
     target_module_path_and_loc: &(&str, &'static str, &'static str, &'static Location),
+    extra: &Extra,
 ) where
     L: VLog,
 {
@@ -377,9 +1660,58 @@ pub fn vlog_message<'a, L>(
         color,
         surface,
         target_module_path_and_loc,
+        extra,
     );
 }
 
+/// A new id for the segments of a path, see [`Record::path_id`].
+pub fn next_path_id() -> u64 {
+    static NEXT_PATH_ID: crate::AtomicUsize = crate::AtomicUsize::new(1);
+    NEXT_PATH_ID.fetch_add(1, crate::Ordering::Relaxed) as u64
+}
+
+/// The per call site counter of `vlog_every!`.
+pub struct Every(crate::AtomicUsize);
+
+impl Default for Every {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl fmt::Debug for Every {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Every")
+            .field(&self.0.load(crate::Ordering::Relaxed))
+            .finish()
+    }
+}
+
+impl Every {
+    pub const fn new() -> Self {
+        Every(crate::AtomicUsize::new(0))
+    }
+
+    /// Count an invocation and check if it is the first of a period of `n`.
+    pub fn tick(&self, n: usize) -> bool {
+        self.0.fetch_add(1, crate::Ordering::Relaxed) % n.max(1) == 0
+    }
+}
+
+/// Stable FNV-1a hash of the file, line and column of a call site.
+fn call_site_id(loc: &Location) -> u64 {
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    let bytes = loc.file().bytes();
+    let line = loc.line().to_le_bytes();
+    let column = loc.column().to_le_bytes();
+    for b in bytes.chain(line).chain(column) {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(PRIME);
+    }
+    hash
+}
+
 pub fn enabled<L: VLog>(vlogger: L, surface: &str, target: &str) -> bool {
     vlogger.enabled(&Metadata::builder().surface(surface).target(target).build())
 }
@@ -0,0 +1,388 @@
+//! A crude text rendering vlogger for 2D surfaces.
+
+use crate::{GridPlane, Metadata, Record, TextAlignment, VLog, VLogError, Visual};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// A vlogger which rasterizes 2D surfaces into a grid of characters.
+///
+/// This is useful for quick debugging in a terminal or in CI logs without
+/// image support. Points are drawn as `o`, lines with one of `-`, `|`, `/`, `\`
+/// depending on their direction and zero length lines as `.`. Curves and the outlines
+/// of shapes are drawn like lines. Labels are written as text. Images, grids
+/// outside of the xy-plane, the z-coordinate and messages are ignored.
+/// Everything outside of the world bounds is cut off.
+/// [`snapshot`](VLog::snapshot) writes the [`render`](AsciiVLogger::render)ed text
+/// of a surface to `.txt` files.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{point, polyline, AsciiVLogger};
+///
+/// let vlogger = AsciiVLogger::new(10, 5).with_bounds([0.0, 0.0], [10.0, 5.0]);
+///
+/// point!(vlogger: vlogger, "main_surface", [2.5, 1.5], 1.0, Base);
+/// polyline!(vlogger: vlogger, "main_surface", ([5.5, 0.5], [9.5, 0.5]), 1.0, Base);
+///
+/// let text = vlogger.render("main_surface");
+/// let rows: Vec<&str> = text.lines().collect();
+/// assert_eq!(rows.len(), 5);
+/// // the y-axis points upwards, so y = 1.5 is in the second row from the bottom.
+/// assert_eq!(rows[3], "  o       ");
+/// assert_eq!(rows[4], "     -----");
+/// ```
+///
+/// Lines are clipped to the grid first, so far away points don't slow it down:
+///
+/// ```
+/// use v_log::{polyline, AsciiVLogger};
+///
+/// let vlogger = AsciiVLogger::new(20, 10);
+/// polyline!(vlogger: vlogger, "main_surface", ([0.0, 0.5], [1e10, 0.5]), 1.0, Base);
+/// assert_eq!(vlogger.render("main_surface").lines().last(), Some("-".repeat(20).as_str()));
+/// ```
+///
+/// Malformed records, e.g. deserialized ones, are drawn as far as possible:
+///
+/// ```
+/// use v_log::{AsciiVLogger, LineStyle, Record, VLog, Visual};
+///
+/// let vlogger = AsciiVLogger::new(4, 4);
+/// let spline = Visual::Spline {
+///     points: Vec::new(),
+///     tension: 0.5,
+///     closed: true,
+///     style: LineStyle::Simple,
+/// };
+/// // the second triangle refers to a vertex which doesn't exist.
+/// let mesh = Visual::Mesh {
+///     vertices: vec![[0.5, 0.5, 0.0], [3.5, 0.5, 0.0], [0.5, 3.5, 0.0]],
+///     indices: vec![0, 1, 2, 0, 1, 3],
+///     shaded: false,
+/// };
+/// for visual in [spline, mesh] {
+///     vlogger.vlog(&Record::builder().surface("main_surface").visual(visual).build());
+/// }
+/// assert_eq!(vlogger.render("main_surface"), "|   \n|\\  \n| \\ \n|--\\");
+/// ```
+#[derive(Debug)]
+pub struct AsciiVLogger {
+    width: usize,
+    height: usize,
+    min: [f64; 2],
+    max: [f64; 2],
+    surfaces: Mutex<BTreeMap<String, Vec<char>>>,
+}
+
+impl AsciiVLogger {
+    /// Create a new `AsciiVLogger` with a grid of `width` times `height` characters.
+    ///
+    /// The world bounds default to one unit per character, starting at the origin.
+    pub fn new(width: usize, height: usize) -> Self {
+        AsciiVLogger {
+            width,
+            height,
+            min: [0.0, 0.0],
+            max: [width as f64, height as f64],
+            surfaces: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Set the world bounds, which are mapped onto the grid.
+    pub fn with_bounds(mut self, min: [f64; 2], max: [f64; 2]) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Render a surface into lines of text, separated by `'\n'`.
+    ///
+    /// Surfaces which haven't been drawn to render as empty grids.
+    pub fn render(&self, surface: &str) -> String {
+        let surfaces = self.surfaces.lock().unwrap_or_else(|e| e.into_inner());
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in 0..self.height {
+            if row > 0 {
+                text.push('\n');
+            }
+            match surfaces.get(surface) {
+                Some(grid) => text.extend(&grid[row * self.width..(row + 1) * self.width]),
+                None => text.extend((0..self.width).map(|_| ' ')),
+            }
+        }
+        text
+    }
+
+    /// Map world coordinates to (fractional) grid coordinates with the row pointing down.
+    fn to_grid(&self, x: f64, y: f64) -> (f64, f64) {
+        (
+            (x - self.min[0]) / (self.max[0] - self.min[0]) * self.width as f64,
+            (self.max[1] - y) / (self.max[1] - self.min[1]) * self.height as f64,
+        )
+    }
+
+    fn put(&self, grid: &mut [char], col: f64, row: f64, c: char) {
+        if col >= 0.0 && row >= 0.0 {
+            let (col, row) = (col as usize, row as usize);
+            if col < self.width && row < self.height {
+                grid[row * self.width + col] = c;
+            }
+        }
+    }
+
+    /// Clip the segment from `a` to `b` to the grid with the Liang-Barsky algorithm.
+    fn clip(&self, a: (f64, f64), b: (f64, f64)) -> Option<((f64, f64), (f64, f64))> {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let (mut t0, mut t1) = (0.0f64, 1.0f64);
+        for (p, q) in [
+            (-dx, a.0),
+            (dx, self.width as f64 - a.0),
+            (-dy, a.1),
+            (dy, self.height as f64 - a.1),
+        ] {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else if p < 0.0 {
+                t0 = t0.max(q / p);
+            } else {
+                t1 = t1.min(q / p);
+            }
+        }
+        if t0 > t1 {
+            return None;
+        }
+        Some((
+            (a.0 + t0 * dx, a.1 + t0 * dy),
+            (a.0 + t1 * dx, a.1 + t1 * dy),
+        ))
+    }
+
+    fn draw_line(&self, grid: &mut [char], a: (f64, f64), b: (f64, f64)) {
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        if !dx.is_finite() || !dy.is_finite() {
+            return;
+        }
+        let c = if dx == 0.0 && dy == 0.0 {
+            '.'
+        } else if dx.abs() > 2.0 * dy.abs() {
+            '-'
+        } else if dy.abs() > 2.0 * dx.abs() {
+            '|'
+        } else if (dx > 0.0) == (dy > 0.0) {
+            // rows point down, so this is a falling line
+            '\\'
+        } else {
+            '/'
+        };
+        // only the part on the grid is stepped through, so huge coordinates stay cheap.
+        let Some((a, b)) = self.clip(a, b) else {
+            return;
+        };
+        let (dx, dy) = (b.0 - a.0, b.1 - a.1);
+        let steps = dx
+            .abs()
+            .max(dy.abs())
+            .ceil()
+            .min((self.width + self.height) as f64) as usize;
+        for i in 0..=steps {
+            let t = if steps == 0 {
+                0.0
+            } else {
+                i as f64 / steps as f64
+            };
+            self.put(grid, a.0 + dx * t, a.1 + dy * t, c);
+        }
+    }
+}
+
+impl VLog for AsciiVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        let mut surfaces = self.surfaces.lock().unwrap_or_else(|e| e.into_inner());
+        let grid = surfaces
+            .entry(record.surface().into())
+            .or_insert_with(|| vec![' '; self.width * self.height]);
+        match record.visual() {
+            Visual::Point { x, y, .. } => {
+                let (col, row) = self.to_grid(*x, *y);
+                self.put(grid, col, row, 'o');
+            }
+            Visual::PointCloud { points, .. } => {
+                for p in points {
+                    let (col, row) = self.to_grid(p[0], p[1]);
+                    self.put(grid, col, row, 'o');
+                }
+            }
+            Visual::Line { x1, y1, x2, y2, .. } => {
+                self.draw_line(grid, self.to_grid(*x1, *y1), self.to_grid(*x2, *y2));
+            }
+            Visual::Ray {
+                origin, direction, ..
+            } => {
+                // far enough along the ray to leave the bounds from any origin.
+                let far = [0, 1]
+                    .map(|k| (origin[k] - self.min[k]).abs() + (origin[k] - self.max[k]).abs())
+                    .iter()
+                    .sum::<f64>()
+                    / direction[0].hypot(direction[1]);
+                if far.is_finite() {
+                    let end = (
+                        origin[0] + far * direction[0],
+                        origin[1] + far * direction[1],
+                    );
+                    self.draw_line(
+                        grid,
+                        self.to_grid(origin[0], origin[1]),
+                        self.to_grid(end.0, end.1),
+                    );
+                }
+            }
+            Visual::Aabb { min, max, .. } => {
+                let corners = [
+                    (min[0], min[1]),
+                    (max[0], min[1]),
+                    (max[0], max[1]),
+                    (min[0], max[1]),
+                ];
+                for (i, &(x, y)) in corners.iter().enumerate() {
+                    let (x2, y2) = corners[(i + 1) % corners.len()];
+                    self.draw_line(grid, self.to_grid(x, y), self.to_grid(x2, y2));
+                }
+            }
+            Visual::Obb { .. } => {
+                let corners = record.visual().obb_corners().unwrap_or_default();
+                for (i, a) in corners.iter().enumerate() {
+                    for bit in [1, 2, 4] {
+                        if i & bit == 0 {
+                            let b = corners[i | bit];
+                            self.draw_line(
+                                grid,
+                                self.to_grid(a[0], a[1]),
+                                self.to_grid(b[0], b[1]),
+                            );
+                        }
+                    }
+                }
+            }
+            Visual::Axes { origin, .. } => {
+                for tip in record.visual().axes_tips().unwrap_or_default() {
+                    self.draw_line(
+                        grid,
+                        self.to_grid(origin[0], origin[1]),
+                        self.to_grid(tip[0], tip[1]),
+                    );
+                }
+            }
+            Visual::Arc { .. } | Visual::Ellipse { .. } | Visual::Bezier { .. } => {
+                let visual = record.visual();
+                let points = visual
+                    .arc_points(32)
+                    .or_else(|| visual.ellipse_points(32))
+                    .or_else(|| visual.bezier_points(32))
+                    .unwrap_or_default();
+                for segment in points.windows(2) {
+                    let (a, b) = (segment[0], segment[1]);
+                    self.draw_line(grid, self.to_grid(a[0], a[1]), self.to_grid(b[0], b[1]));
+                }
+            }
+            Visual::Grid {
+                plane: GridPlane::XY,
+                ..
+            } => {
+                for [a, b] in record.visual().grid_lines().unwrap_or_default() {
+                    self.draw_line(grid, self.to_grid(a[0], a[1]), self.to_grid(b[0], b[1]));
+                }
+            }
+            Visual::Spline { points, closed, .. } => {
+                let closing = points.last().zip(points.first()).filter(|_| *closed);
+                let segments = points.iter().zip(points.iter().skip(1));
+                for (a, b) in segments.chain(closing) {
+                    self.draw_line(grid, self.to_grid(a[0], a[1]), self.to_grid(b[0], b[1]));
+                }
+            }
+            Visual::Mesh {
+                vertices, indices, ..
+            } => {
+                for triangle in indices.chunks_exact(3) {
+                    // triangles with indices out of range are skipped.
+                    let corners = (
+                        vertices.get(triangle[0] as usize),
+                        vertices.get(triangle[1] as usize),
+                        vertices.get(triangle[2] as usize),
+                    );
+                    let (Some(a), Some(b), Some(c)) = corners else {
+                        continue;
+                    };
+                    for (p, q) in [(a, b), (b, c), (c, a)] {
+                        self.draw_line(grid, self.to_grid(p[0], p[1]), self.to_grid(q[0], q[1]));
+                    }
+                }
+            }
+            Visual::VectorField {
+                origins,
+                vectors,
+                scale,
+            } => {
+                for (o, v) in origins.iter().zip(vectors) {
+                    let (x2, y2) = (o[0] + scale * v[0], o[1] + scale * v[1]);
+                    self.draw_line(grid, self.to_grid(o[0], o[1]), self.to_grid(x2, y2));
+                }
+            }
+            Visual::Polygon { points, .. } => {
+                for (i, a) in points.iter().enumerate() {
+                    let b = points[(i + 1) % points.len()];
+                    self.draw_line(grid, self.to_grid(a[0], a[1]), self.to_grid(b[0], b[1]));
+                }
+            }
+            Visual::Label {
+                x,
+                y,
+                alignment,
+                max_width,
+                style,
+                ..
+            } => {
+                let max_chars = max_width
+                    .map(|w| (w / (self.max[0] - self.min[0]) * self.width as f64) as usize);
+                let lines = style.wrap(&record.args().to_string(), max_chars);
+                let (col, row) = self.to_grid(*x, *y);
+                // the lines are centered vertically around the position.
+                let top = row - (lines.len() / 2) as f64;
+                for (i, line) in lines.iter().enumerate() {
+                    let len = line.chars().count() as f64;
+                    let start = match alignment {
+                        TextAlignment::Left => col,
+                        TextAlignment::Right => col - len,
+                        TextAlignment::Center | TextAlignment::Flexible => col - len * 0.5,
+                    };
+                    for (j, c) in line.chars().enumerate() {
+                        self.put(grid, start + j as f64, top + i as f64, c);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        let mut surfaces = self.surfaces.lock().unwrap_or_else(|e| e.into_inner());
+        surfaces.remove(surface);
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        if !path.ends_with(".txt") {
+            return Err(VLogError::UnsupportedFormat);
+        }
+        std::fs::write(path, self.render(surface)).map_err(|_| VLogError::Failed)
+    }
+
+    fn flush(&self) {}
+}
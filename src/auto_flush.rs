@@ -0,0 +1,100 @@
+//! Automatic flushing of the global vlogger after a burst of draws.
+
+use crate::vlogger;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+
+// The idle time in microseconds, or 0 if auto flushing is disabled.
+static IDLE_MICROS: AtomicUsize = AtomicUsize::new(0);
+// Counts the draws, so the flush thread can tell when the last draw happened.
+// This is much cheaper than taking the time on every draw.
+static DRAWS: AtomicUsize = AtomicUsize::new(0);
+static THREAD_STARTED: AtomicBool = AtomicBool::new(false);
+
+/// Called by the private API on every draw.
+#[inline]
+pub(crate) fn note_draw() {
+    if IDLE_MICROS.load(Ordering::Relaxed) != 0 {
+        DRAWS.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Flush the global vlogger automatically when no draw has occurred for the given idle duration.
+///
+/// This avoids calling [`VLog::flush`](crate::VLog::flush) manually in event driven code.
+/// The first call spawns a background thread, which polls for draws a few times per
+/// idle duration, so the flush happens between `idle` and about `1.25 * idle` after the
+/// last draw. There is at most one flush per burst of draws.
+/// Calling this again changes the idle duration and [`Duration::ZERO`] disables auto flushing.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct FlushCounter(AtomicUsize);
+/// # impl VLog for FlushCounter {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) { self.0.fetch_add(1, Ordering::Relaxed); }
+/// # }
+/// # static VLOGGER: FlushCounter = FlushCounter(AtomicUsize::new(0));
+/// # v_log::set_vlogger(&VLOGGER).unwrap();
+/// use std::time::Duration;
+///
+/// v_log::set_auto_flush(Duration::from_millis(20));
+///
+/// v_log::point!("main_surface", [1.0, 2.0], 5.0, Base);
+/// v_log::point!("main_surface", [2.0, 3.0], 5.0, Base);
+/// std::thread::sleep(Duration::from_millis(200));
+/// assert_eq!(VLOGGER.0.load(Ordering::Relaxed), 1);
+/// ```
+pub fn set_auto_flush(idle: Duration) {
+    let micros = usize::try_from(idle.as_micros()).unwrap_or(usize::MAX);
+    // round up, so tiny durations don't disable it.
+    let micros = if idle.is_zero() { 0 } else { micros.max(1) };
+    IDLE_MICROS.store(micros, Ordering::Relaxed);
+    if micros != 0 && !THREAD_STARTED.swap(true, Ordering::Relaxed) {
+        thread::Builder::new()
+            .name("v-log auto flush".into())
+            .spawn(flush_loop)
+            .expect("failed to spawn the auto flush thread");
+    }
+}
+
+/// The idle duration set with [`set_auto_flush`], if auto flushing is enabled.
+pub(crate) fn idle() -> Option<Duration> {
+    match IDLE_MICROS.load(Ordering::Relaxed) {
+        0 => None,
+        micros => Some(Duration::from_micros(micros as u64)),
+    }
+}
+
+fn flush_loop() {
+    // draws are only counted while auto flushing is enabled, so they all count.
+    let mut draws = 0;
+    let mut last_draw = Instant::now();
+    let mut pending = false;
+    loop {
+        let idle = Duration::from_micros(IDLE_MICROS.load(Ordering::Relaxed) as u64);
+        if idle.is_zero() {
+            pending = false;
+            thread::sleep(Duration::from_millis(50));
+            continue;
+        }
+        thread::sleep(idle / 4);
+        let current = DRAWS.load(Ordering::Relaxed);
+        if current != draws {
+            draws = current;
+            last_draw = Instant::now();
+            pending = true;
+        } else if pending && last_draw.elapsed() >= idle {
+            pending = false;
+            vlogger().flush();
+        }
+    }
+}
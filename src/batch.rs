@@ -0,0 +1,182 @@
+//! Thread local batching of records sent to the global vlogger.
+
+use crate::{vlogger, Record, RecordOwned};
+use std::cell::RefCell;
+use std::marker::PhantomData;
+
+thread_local! {
+    static BATCH: RefCell<Option<Vec<RecordOwned>>> = const { RefCell::new(None) };
+}
+
+/// A guard which buffers all records sent to the global vlogger on the current thread.
+///
+/// While the guard is alive, the macros using the global vlogger don't call
+/// [`VLog::vlog`](crate::VLog::vlog), but store the records locally instead.
+/// When the guard is dropped, they are submitted together with
+/// [`VLog::vlog_batch`](crate::VLog::vlog_batch). This reduces the lock traffic
+/// in vloggers which are used from many threads at once.
+///
+/// Records sent to an explicit vlogger (`vlogger: ...`) are not buffered.
+/// The other calls to the global vlogger, like [`clear!`](crate::clear), aren't
+/// buffered either, but they submit the buffered records first, so the vlogger
+/// receives everything in order. Guards can be nested, in which case the records
+/// are submitted together when the outermost guard is dropped.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use v_log::{point, LocalBatch, Metadata, Record, RecordOwned, VLog};
+///
+/// struct CountingVLogger {
+///     records: AtomicUsize,
+///     batches: AtomicUsize,
+/// }
+///
+/// impl VLog for CountingVLogger {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, _: &Record) {
+///         self.records.fetch_add(1, Ordering::SeqCst);
+///     }
+///     fn vlog_batch(&self, records: &[RecordOwned]) {
+///         self.batches.fetch_add(1, Ordering::SeqCst);
+///         self.records.fetch_add(records.len(), Ordering::SeqCst);
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// static VLOGGER: CountingVLogger = CountingVLogger {
+///     records: AtomicUsize::new(0),
+///     batches: AtomicUsize::new(0),
+/// };
+/// v_log::set_vlogger(&VLOGGER).unwrap();
+///
+/// {
+///     let _batch = LocalBatch::new();
+///     point!("main_surface", [0.0, 0.0], 5.0, Base);
+///     point!("main_surface", [1.0, 0.0], 5.0, Base);
+///     assert_eq!(VLOGGER.records.load(Ordering::SeqCst), 0);
+/// }
+/// assert_eq!(VLOGGER.records.load(Ordering::SeqCst), 2);
+/// assert_eq!(VLOGGER.batches.load(Ordering::SeqCst), 1);
+/// ```
+///
+/// A clear in a batch doesn't overtake the records before it:
+///
+/// ```
+/// use std::sync::Mutex;
+/// use v_log::{clear, message, LocalBatch, Metadata, Record, RecordOwned, VLog};
+///
+/// struct Capture(Mutex<Vec<String>>);
+///
+/// impl VLog for Capture {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         self.0.lock().unwrap().push(format!("draw {}", record.args()));
+///     }
+///     fn vlog_batch(&self, records: &[RecordOwned]) {
+///         let mut calls = self.0.lock().unwrap();
+///         calls.extend(records.iter().map(|r| format!("draw {}", r.args())));
+///     }
+///     fn clear(&self, surface: &str) {
+///         self.0.lock().unwrap().push(format!("clear {}", surface));
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+/// v_log::set_vlogger(&CAPTURE).unwrap();
+///
+/// {
+///     let _batch = LocalBatch::new();
+///     message!("s", "A");
+///     clear!("s");
+///     {
+///         // the records of nested guards go into the outer batch.
+///         let _inner = LocalBatch::new();
+///         message!("s", "B");
+///     }
+///     message!("s", "C");
+///     assert_eq!(*CAPTURE.0.lock().unwrap(), ["draw A", "clear s"]);
+/// }
+/// assert_eq!(
+///     *CAPTURE.0.lock().unwrap(),
+///     ["draw A", "clear s", "draw B", "draw C"]
+/// );
+/// ```
+#[derive(Debug)]
+#[must_use = "records are only buffered while the guard is alive"]
+pub struct LocalBatch {
+    // nested guards leave the submission to the outermost guard.
+    nested: bool,
+    // the guard must be dropped on the thread it was created on.
+    _not_send: PhantomData<*const ()>,
+}
+
+impl LocalBatch {
+    /// Start buffering the records sent to the global vlogger on the current thread.
+    pub fn new() -> Self {
+        let nested = BATCH.with(|batch| {
+            let mut batch = batch.borrow_mut();
+            let nested = batch.is_some();
+            batch.get_or_insert_with(Vec::new);
+            nested
+        });
+        LocalBatch {
+            nested,
+            _not_send: PhantomData,
+        }
+    }
+}
+
+impl Default for LocalBatch {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Drop for LocalBatch {
+    fn drop(&mut self) {
+        if self.nested {
+            return;
+        }
+        let records = BATCH.with(|batch| batch.borrow_mut().take());
+        if let Some(records) = records {
+            if !records.is_empty() {
+                vlogger().vlog_batch(&records);
+            }
+        }
+    }
+}
+
+/// Submit the records buffered by a [`LocalBatch`] on the current thread, if any,
+/// so a following call to the global vlogger doesn't overtake them.
+pub(crate) fn flush() {
+    let records = BATCH
+        .try_with(|batch| batch.borrow_mut().as_mut().map(core::mem::take))
+        .ok()
+        .flatten();
+    if let Some(records) = records {
+        if !records.is_empty() {
+            vlogger().vlog_batch(&records);
+        }
+    }
+}
+
+/// Buffer the record if a [`LocalBatch`] is active on the current thread.
+///
+/// Returns `false` if the record needs to be sent to the vlogger directly.
+pub(crate) fn push(record: &Record) -> bool {
+    BATCH
+        .try_with(|batch| match &mut *batch.borrow_mut() {
+            Some(records) => {
+                records.push(record.into());
+                true
+            }
+            None => false,
+        })
+        .unwrap_or(false)
+}
@@ -0,0 +1,271 @@
+//! A vlogger adapter which buffers records per thread.
+
+use crate::{Metadata, Record, RecordOwned, VLog};
+use std::cell::{Cell, RefCell};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Mutex, MutexGuard};
+
+thread_local! {
+    // the buffers of the `Buffered` vloggers on this thread, by their id.
+    static BUFFERS: RefCell<Vec<(usize, Vec<RecordOwned>)>> = const { RefCell::new(Vec::new()) };
+    // the value of `DROPPED` when the buffers of this thread were last pruned.
+    static PRUNED: Cell<usize> = const { Cell::new(0) };
+}
+
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+// the ids of the `Buffered` vloggers which haven't been dropped yet.
+static LIVE: Mutex<Vec<usize>> = Mutex::new(Vec::new());
+// the number of dropped `Buffered` vloggers, so threads only check `LIVE` after a drop.
+static DROPPED: AtomicUsize = AtomicUsize::new(0);
+
+fn lock_live() -> MutexGuard<'static, Vec<usize>> {
+    // the ids are always in a valid state, so poisoning can be ignored.
+    LIVE.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Free the buffers of the dropped vloggers on this thread, which can't be flushed anymore.
+fn prune(buffers: &mut Vec<(usize, Vec<RecordOwned>)>) {
+    let dropped = DROPPED.load(Ordering::Acquire);
+    if PRUNED.try_with(|pruned| pruned.replace(dropped)) == Ok(dropped) {
+        return;
+    }
+    let live = lock_live();
+    buffers.retain(|(id, _)| live.contains(id));
+}
+
+/// Buffers the records in a thread local buffer and forwards them to the inner vlogger in batches.
+///
+/// This is useful for loops which vlog thousands of records per frame from several threads,
+/// where a vlogger which takes a lock per record becomes a bottleneck. The records are sent
+/// with [`VLog::vlog_batch`] when the buffer of the current thread reaches the capacity, and
+/// when [`flush`](VLog::flush) is called on that thread.
+///
+/// Buffering isn't free, as every record is converted into a [`RecordOwned`], so it only
+/// pays off if the lock of the inner vlogger is contended. The example `bench_buffered`
+/// of the repository compares both for a given machine.
+///
+/// In contrast to [`LocalBatch`](crate::LocalBatch), which buffers the records sent to the
+/// global vlogger while a guard is alive, this wraps a vlogger, so it can e.g. be installed
+/// as the global vlogger itself.
+///
+/// # Ordering
+///
+/// The records of each thread reach the inner vlogger in the order they were sent (FIFO),
+/// but there is no order between the records of different threads. A [`clear`](VLog::clear)
+/// and the other surface requests, like [`begin_frame`](VLog::begin_frame), first flush the buffer
+/// of the current thread, so they stay in order with the records of that thread.
+///
+/// Each thread can only flush its own buffer. Records which are still buffered when
+/// a thread exits are lost, so threads should call [`flush`](VLog::flush) when they are
+/// done, e.g. at the end of every frame.
+///
+/// # Dropping
+///
+/// Dropping the `Buffered` only flushes the buffer of the current thread. **The records
+/// which are still buffered on other threads are discarded**, as the inner vlogger is
+/// dropped as well. Their buffers are freed the next time these threads start a new buffer
+/// of any `Buffered`, or when they exit. So flush on every thread before dropping it.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use v_log::{point, Buffered, Metadata, Record, RecordOwned, VLog};
+///
+/// #[derive(Default)]
+/// struct CountingVLogger {
+///     records: AtomicUsize,
+///     batches: AtomicUsize,
+/// }
+///
+/// impl VLog for CountingVLogger {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, _: &Record) {
+///         self.records.fetch_add(1, Ordering::SeqCst);
+///     }
+///     fn vlog_batch(&self, records: &[RecordOwned]) {
+///         self.batches.fetch_add(1, Ordering::SeqCst);
+///         self.records.fetch_add(records.len(), Ordering::SeqCst);
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// let vlogger = Buffered::new(CountingVLogger::default(), 100);
+/// std::thread::scope(|s| {
+///     for _ in 0..4 {
+///         s.spawn(|| {
+///             for i in 0..250 {
+///                 point!(vlogger: vlogger, "main_surface", [i as f64, 0.0], 5.0, Base);
+///             }
+///             vlogger.flush();
+///         });
+///     }
+/// });
+/// // every thread sent two full buffers and flushed the rest.
+/// assert_eq!(vlogger.inner().records.load(Ordering::SeqCst), 1000);
+/// assert_eq!(vlogger.inner().batches.load(Ordering::SeqCst), 12);
+/// ```
+///
+/// A clear flushes the buffered records first:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{clear, point, Buffered, Metadata, Record, VLog};
+///
+/// struct Capture(RefCell<Vec<String>>);
+///
+/// impl VLog for Capture {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         self.0.borrow_mut().push(record.args().to_string());
+///     }
+///     fn clear(&self, surface: &str) {
+///         self.0.borrow_mut().push(format!("clear {}", surface));
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// let vlogger = Buffered::new(Capture(RefCell::new(Vec::new())), 16);
+/// point!(vlogger: vlogger, "main_surface", [0.0, 0.0], 5.0, Base, "o", "a");
+/// point!(vlogger: vlogger, "main_surface", [1.0, 0.0], 5.0, Base, "o", "b");
+/// assert!(vlogger.inner().0.borrow().is_empty());
+/// clear!(vlogger: vlogger, "main_surface");
+/// point!(vlogger: vlogger, "main_surface", [2.0, 0.0], 5.0, Base, "o", "c");
+/// vlogger.flush();
+/// assert_eq!(*vlogger.inner().0.borrow(), ["a", "b", "clear main_surface", "c"]);
+/// ```
+#[derive(Debug)]
+pub struct Buffered<V: VLog> {
+    inner: V,
+    capacity: usize,
+    id: usize,
+}
+
+impl<V: VLog> Buffered<V> {
+    /// Buffer up to `capacity` records per thread before sending them to `inner`.
+    ///
+    /// A capacity of 0 or 1 sends every record at once.
+    pub fn new(inner: V, capacity: usize) -> Self {
+        let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+        lock_live().push(id);
+        Buffered {
+            inner,
+            capacity,
+            id,
+        }
+    }
+
+    /// The inner vlogger.
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// The number of records which are buffered per thread.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// The number of records buffered on the current thread.
+    pub fn buffered(&self) -> usize {
+        BUFFERS
+            .try_with(|buffers| {
+                buffers
+                    .borrow()
+                    .iter()
+                    .find(|(id, _)| *id == self.id)
+                    .map_or(0, |(_, records)| records.len())
+            })
+            .unwrap_or(0)
+    }
+
+    /// Send the records buffered on the current thread to the inner vlogger.
+    fn flush_buffer(&self) {
+        let records = BUFFERS
+            .try_with(|buffers| {
+                let mut buffers = buffers.borrow_mut();
+                let index = buffers.iter().position(|(id, _)| *id == self.id)?;
+                Some(buffers.swap_remove(index).1)
+            })
+            .ok()
+            .flatten();
+        if let Some(records) = records {
+            if !records.is_empty() {
+                self.inner.vlog_batch(&records);
+            }
+        }
+    }
+}
+
+impl<V: VLog> Drop for Buffered<V> {
+    fn drop(&mut self) {
+        self.flush_buffer();
+        lock_live().retain(|id| *id != self.id);
+        DROPPED.fetch_add(1, Ordering::Release);
+    }
+}
+
+impl<V: VLog> VLog for Buffered<V> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.inner.enabled_record(record)
+    }
+
+    fn vlog(&self, record: &Record) {
+        // the buffer is taken out before calling the inner vlogger,
+        // which may be another `Buffered` using the same thread local.
+        let full = BUFFERS.try_with(|buffers| {
+            let mut buffers = buffers.borrow_mut();
+            let index = match buffers.iter().position(|(id, _)| *id == self.id) {
+                Some(index) => index,
+                None => {
+                    prune(&mut buffers);
+                    buffers.push((self.id, Vec::with_capacity(self.capacity)));
+                    buffers.len() - 1
+                }
+            };
+            let records = &mut buffers[index].1;
+            records.push(record.into());
+            if records.len() >= self.capacity {
+                Some(buffers.swap_remove(index).1)
+            } else {
+                None
+            }
+        });
+        match full {
+            Ok(Some(records)) => self.inner.vlog_batch(&records),
+            Ok(None) => {}
+            // the thread is shutting down.
+            Err(_) => self.inner.vlog(record),
+        }
+    }
+
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        self.flush_buffer();
+        self.inner.vlog_batch(records);
+    }
+
+    fn clear(&self, surface: &str) {
+        self.flush_buffer();
+        self.inner.clear(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        self.flush_buffer();
+        self.inner.transaction(surface, f);
+    }
+
+    forward_vlog! {
+        inner; before flush_buffer => remove_surface, begin_frame, end_frame, push_group,
+        pop_group, flush, on_shutdown, set_draw_mode, set_view, snapshot
+    }
+
+    forward_vlog! {
+        inner => quantize_color, max_records
+    }
+}
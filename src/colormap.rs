@@ -0,0 +1,159 @@
+//! Colormaps to color scalar data.
+//!
+//! The colormaps map a value `t` in `[0, 1]` to a [`Color::Rgba`]. Values out of range
+//! are clamped and `NaN` is treated as `0.0`.
+//!
+//! # Examples
+//!
+//! ```
+//! use v_log::colormap::viridis;
+//! use v_log::point;
+//!
+//! let speeds = [0.0, 2.5, 10.0];
+//! let max = 10.0;
+//! for (i, speed) in speeds.iter().enumerate() {
+//!     point!("main_surface", [i as f64, 0.0], 5.0, (viridis(speed / max)));
+//! }
+//! ```
+
+use crate::Color;
+
+/// A colormap, to select one at runtime.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::colormap::{self, Colormap};
+///
+/// let map = Colormap::Turbo;
+/// assert_eq!(map.sample(0.3).to_rgba(), colormap::turbo(0.3).to_rgba());
+/// ```
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub enum Colormap {
+    /// See [`viridis`].
+    #[default]
+    Viridis,
+    /// See [`turbo`].
+    Turbo,
+}
+
+impl Colormap {
+    /// Map `t` in `[0, 1]` to a color.
+    pub fn sample(self, t: f64) -> Color {
+        match self {
+            Colormap::Viridis => viridis(t),
+            Colormap::Turbo => turbo(t),
+        }
+    }
+}
+
+/// Clamp to `[0, 1]`, where `NaN` becomes `0.0`.
+fn clamp01(t: f64) -> f64 {
+    if t > 1.0 {
+        1.0
+    } else if t > 0.0 {
+        t
+    } else {
+        0.0
+    }
+}
+
+/// The perceptually uniform "viridis" colormap of matplotlib, from dark purple over
+/// blue and green to yellow.
+///
+/// The lightness increases monotonically, so it stays readable in grayscale.
+/// This interpolates linearly between 11 samples of the colormap.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::colormap::viridis;
+/// use v_log::Color;
+///
+/// assert!(viridis(0.0).approx_eq(&Color::Hex(0x440154ff), 0));
+/// assert!(viridis(1.0).approx_eq(&Color::Hex(0xfde725ff), 0));
+/// assert!(viridis(0.5).approx_eq(&Color::Hex(0x21918cff), 0));
+/// // values out of range are clamped.
+/// assert!(viridis(-1.0).approx_eq(&viridis(0.0), 0));
+/// assert!(viridis(2.0).approx_eq(&viridis(1.0), 0));
+///
+/// // the lightness increases monotonically.
+/// let luma = |c: Color| {
+///     let [r, g, b, _] = c.to_rgba();
+///     0.2126 * r + 0.7152 * g + 0.0722 * b
+/// };
+/// for i in 0..100 {
+///     assert!(luma(viridis(i as f64 / 100.0)) < luma(viridis((i + 1) as f64 / 100.0)));
+/// }
+/// ```
+pub fn viridis(t: f64) -> Color {
+    const SAMPLES: [u32; 11] = [
+        0x440154, 0x482475, 0x414487, 0x355f8d, 0x2a788e, 0x21918c, 0x22a884, 0x44bf70, 0x7ad151,
+        0xbddf26, 0xfde725,
+    ];
+    let x = clamp01(t) * (SAMPLES.len() - 1) as f64;
+    let i = (x as usize).min(SAMPLES.len() - 2);
+    let f = x - i as f64;
+    let (a, b) = (SAMPLES[i].to_be_bytes(), SAMPLES[i + 1].to_be_bytes());
+    let c = |k: usize| ((a[k] as f64 + (b[k] as f64 - a[k] as f64) * f) / 255.0) as f32;
+    Color::rgba(c(1), c(2), c(3), 1.0)
+}
+
+/// The "turbo" rainbow colormap of Google, from dark blue over cyan, green
+/// and yellow to dark red.
+///
+/// It has a high contrast, but its lightness is not monotonic.
+/// This uses the polynomial approximation published with the colormap,
+/// which deviates slightly from the original lookup table at the ends.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::colormap::turbo;
+///
+/// // dark at the low end, then blue.
+/// let [r, g, b, a] = turbo(0.0).to_rgba();
+/// assert!(r < 0.2 && g < 0.2 && b < 0.2 && a == 1.0);
+/// let [r, g, b, _] = turbo(0.1).to_rgba();
+/// assert!(b > 0.8 && r < 0.5 && g < 0.5);
+/// let [r, g, b, _] = turbo(1.0).to_rgba();
+/// assert!(r > 0.5 && g < 0.1 && b == 0.0);
+/// let [r, g, b, _] = turbo(0.5).to_rgba();
+/// assert!(g > 0.9 && r < g && b < g);
+/// ```
+pub fn turbo(t: f64) -> Color {
+    let t = clamp01(t);
+    let poly = |c: [f64; 6]| {
+        let v = c[0] + t * (c[1] + t * (c[2] + t * (c[3] + t * (c[4] + t * c[5]))));
+        clamp01(v) as f32
+    };
+    Color::rgba(
+        poly([
+            0.13572138,
+            4.61539260,
+            -42.66032258,
+            132.13108234,
+            -152.94239396,
+            59.28637943,
+        ]),
+        poly([
+            0.09140261,
+            2.19418839,
+            4.84296658,
+            -14.18503333,
+            4.27729857,
+            2.82956604,
+        ]),
+        poly([
+            0.10667330,
+            12.64194608,
+            -60.58204836,
+            110.36276771,
+            -89.90310912,
+            27.34824973,
+        ]),
+        1.0,
+    )
+}
@@ -0,0 +1,58 @@
+//! Selection of the global vlogger with an environment variable.
+
+use crate::{set_boxed_vlogger, SetVLoggerError, VLog};
+
+/// The environment variable read by [`init_from_env`].
+const BACKEND_VAR: &str = "V_LOG_BACKEND";
+
+/// Installs the global vlogger selected by the `V_LOG_BACKEND` environment variable.
+///
+/// The value of the variable is passed to `factory`, which builds the matching
+/// vlogger, e.g. by comparing it to the names of the backends compiled into the
+/// application. The vlogger is then installed with [`set_boxed_vlogger`].
+/// If the variable is unset or not unicode, or the factory returns `None`, no
+/// vlogger is installed, so all draws go to the default no-op vlogger.
+///
+/// Requires the `std` feature.
+///
+/// # Errors
+///
+/// An error is returned if a vlogger has already been set.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::atomic::{AtomicUsize, Ordering};
+/// # use v_log::{Metadata, Record};
+/// # static DRAWN: AtomicUsize = AtomicUsize::new(0);
+/// # struct Counting;
+/// # impl VLog for Counting {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) { DRAWN.fetch_add(1, Ordering::Relaxed); }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::VLog;
+///
+/// std::env::set_var("V_LOG_BACKEND", "counting");
+/// v_log::init_from_env(|name| match name {
+///     "counting" => Some(Box::new(Counting) as Box<dyn VLog>),
+///     _ => None,
+/// })
+/// .unwrap();
+///
+/// v_log::point!("main_surface", [1.0, 2.0], 5.0, Base);
+/// assert_eq!(DRAWN.load(Ordering::Relaxed), 1);
+/// ```
+pub fn init_from_env<F>(factory: F) -> Result<(), SetVLoggerError>
+where
+    F: Fn(&str) -> Option<Box<dyn VLog>>,
+{
+    match std::env::var(BACKEND_VAR)
+        .ok()
+        .and_then(|name| factory(&name))
+    {
+        Some(vlogger) => set_boxed_vlogger(vlogger),
+        None => Ok(()),
+    }
+}
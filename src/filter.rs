@@ -0,0 +1,201 @@
+//! A vlogger adapter which filters by metadata.
+
+use crate::{Metadata, Record, VLog};
+use alloc::boxed::Box;
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+use core::fmt;
+
+type Predicate = dyn Fn(&Metadata) -> bool + Send + Sync;
+
+/// Forwards only the records whose [`Metadata`] match a predicate to the inner vlogger.
+///
+/// This allows filtering by target or surface without changing the vlogger,
+/// e.g. for the single global vlogger. A directive is [enabled](VLog::enabled)
+/// if it is enabled for the inner vlogger and matches the predicate.
+///
+/// Only records are filtered. The predicate doesn't affect [`clear`](VLog::clear)
+/// and the other surface requests like [`set_draw_mode`](VLog::set_draw_mode),
+/// which are always forwarded to the inner vlogger. The same holds for the vlogger
+/// of a [`transaction!`], which filters the records with the same predicate.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{clear, point, remove_surface, transaction, Filter, Metadata, Record, VLog};
+///
+/// struct Capture(RefCell<Vec<String>>);
+///
+/// impl VLog for Capture {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         self.0.borrow_mut().push(record.surface().to_string());
+///     }
+///     fn clear(&self, surface: &str) {
+///         self.0.borrow_mut().push(format!("clear {}", surface));
+///     }
+///     fn flush(&self) {}
+///     fn remove_surface(&self, surface: &str) {
+///         self.0.borrow_mut().push(format!("remove {}", surface));
+///     }
+/// }
+///
+/// let vlogger = Filter::by_surface(Capture(RefCell::new(Vec::new())), &["main", "debug"]);
+/// point!(vlogger: vlogger, "main", [0.0, 0.0], 5.0, Base);
+/// point!(vlogger: vlogger, "overlay", [0.0, 0.0], 5.0, Base);
+/// point!(vlogger: vlogger, "debug", [0.0, 0.0], 5.0, Base);
+/// clear!(vlogger: vlogger, "main");
+/// clear!(vlogger: vlogger, "overlay");
+/// remove_surface!(vlogger: vlogger, "overlay");
+/// assert_eq!(
+///     *vlogger.inner().0.borrow(),
+///     ["main", "debug", "clear main", "clear overlay", "remove overlay"]
+/// );
+///
+/// vlogger.inner().0.borrow_mut().clear();
+/// transaction!(vlogger: vlogger, "main", |v| {
+///     point!(vlogger: v, "main", [0.0, 0.0], 5.0, Base);
+///     point!(vlogger: v, "overlay", [0.0, 0.0], 5.0, Base);
+///     remove_surface!(vlogger: v, "debug");
+/// });
+/// assert_eq!(*vlogger.inner().0.borrow(), ["clear main", "main", "remove debug"]);
+/// ```
+pub struct Filter<V> {
+    inner: V,
+    predicate: Box<Predicate>,
+}
+
+impl<V: VLog> Filter<V> {
+    /// Forward the records matching `predicate` to `inner`.
+    pub fn new(inner: V, predicate: impl Fn(&Metadata) -> bool + Send + Sync + 'static) -> Self {
+        Filter {
+            inner,
+            predicate: Box::new(predicate),
+        }
+    }
+
+    /// Forward only the records of the given surfaces to `inner`.
+    pub fn by_surface(inner: V, surfaces: &[&str]) -> Self {
+        let surfaces: Vec<String> = surfaces.iter().map(|s| s.to_string()).collect();
+        Self::new(inner, move |metadata| {
+            surfaces.iter().any(|s| s == metadata.surface())
+        })
+    }
+
+    /// Forward only the records whose target starts with `prefix` to `inner`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{Filter, Metadata, VLog};
+    ///
+    /// let vlogger = Filter::by_target_prefix(AllVLogger, "physics::");
+    /// let metadata = |target| Metadata::builder().target(target).build();
+    /// assert!(vlogger.enabled(&metadata("physics::broadphase")));
+    /// assert!(!vlogger.enabled(&metadata("render")));
+    /// # struct AllVLogger;
+    /// # impl VLog for AllVLogger {
+    /// #     fn enabled(&self, _: &Metadata) -> bool { true }
+    /// #     fn vlog(&self, _: &v_log::Record) {}
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// # }
+    /// ```
+    pub fn by_target_prefix(inner: V, prefix: &str) -> Self {
+        let prefix = prefix.to_string();
+        Self::new(inner, move |metadata| {
+            metadata.target().starts_with(&prefix)
+        })
+    }
+
+    /// The inner vlogger.
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// Returns the inner vlogger.
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+}
+
+impl<V: fmt::Debug> fmt::Debug for Filter<V> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Filter")
+            .field("inner", &self.inner)
+            .finish_non_exhaustive()
+    }
+}
+
+/// The vlogger of a transaction of a [`Filter`], which applies the same predicate.
+struct Filtered<'a> {
+    inner: &'a dyn VLog,
+    predicate: &'a Predicate,
+}
+
+impl VLog for Filtered<'_> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata) && (self.predicate)(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.inner.enabled_record(record) && (self.predicate)(record.metadata())
+    }
+
+    fn vlog(&self, record: &Record) {
+        if (self.predicate)(record.metadata()) {
+            self.inner.vlog(record);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.inner.clear(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        let predicate = self.predicate;
+        self.inner.transaction(surface, &|inner| {
+            f(&Filtered { inner, predicate });
+        });
+    }
+
+    forward_vlog! {
+        inner => remove_surface, begin_frame, end_frame, push_group, pop_group, flush,
+        on_shutdown, set_draw_mode, set_view, snapshot, quantize_color, max_records
+    }
+}
+
+impl<V: VLog> VLog for Filter<V> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata) && (self.predicate)(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.inner.enabled_record(record) && (self.predicate)(record.metadata())
+    }
+
+    fn vlog(&self, record: &Record) {
+        if (self.predicate)(record.metadata()) {
+            self.inner.vlog(record);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.inner.clear(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        let predicate = &*self.predicate;
+        self.inner.transaction(surface, &|inner| {
+            f(&Filtered { inner, predicate });
+        });
+    }
+
+    forward_vlog! {
+        inner => remove_surface, begin_frame, end_frame, push_group, pop_group, flush,
+        on_shutdown, set_draw_mode, set_view, snapshot, quantize_color, max_records
+    }
+}
@@ -0,0 +1,199 @@
+//! A vlogger adapter which draws all surfaces onto a single surface.
+
+use crate::{Color, DrawMode, Metadata, Record, VLog, VLogError, View};
+use core::fmt;
+
+/// Forwards all records to a single surface of the inner vlogger.
+///
+/// The original surface of a record is prepended to its message as `[surface]`,
+/// so the records stay distinguishable. This allows using vloggers which only
+/// support a single surface, e.g. a simple single window backend.
+///
+/// Clearing any surface clears the single surface of the inner vlogger,
+/// as the records of the different surfaces can't be told apart there.
+///
+/// # Examples
+///
+/// ```
+/// use std::cell::RefCell;
+/// use v_log::{label, point, FlattenVLogger, Metadata, Record, VLog};
+///
+/// struct Capture(RefCell<Vec<String>>);
+///
+/// impl VLog for Capture {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         self.0.borrow_mut().push(format!("{} {}", record.surface(), record.args()));
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// let vlogger = FlattenVLogger::new(Capture(RefCell::new(Vec::new())), "window");
+/// point!(vlogger: vlogger, "physics", [0.0, 0.0], 5.0, Base);
+/// label!(vlogger: vlogger, "ui", [1.0, 0.0], "button");
+/// assert_eq!(*vlogger.inner().0.borrow(), ["window [physics]", "window [ui] button"]);
+/// ```
+///
+/// The other properties of the records, like the key-value pairs, are kept:
+///
+/// ```
+/// # #[cfg(all(feature = "kv", feature = "test-util"))]
+/// # fn main() {
+/// use v_log::kv::Value;
+/// use v_log::test::CaptureVLogger;
+/// use v_log::{point, FlattenVLogger};
+///
+/// let vlogger = FlattenVLogger::new(CaptureVLogger::new(), "window");
+/// point!(vlogger: vlogger, "physics", [0.0, 0.0], 5.0, Base, "o", "body"; entity_id = 42);
+///
+/// let records = vlogger.inner().records();
+/// assert_eq!(records[0].args(), "[physics] body");
+/// records[0].with_record(|record| {
+///     assert_eq!(record.key_values(), [("entity_id", Value::I64(42))]);
+/// });
+/// # }
+/// # #[cfg(not(all(feature = "kv", feature = "test-util")))]
+/// # fn main() {}
+/// ```
+#[derive(Debug)]
+pub struct FlattenVLogger<L> {
+    inner: L,
+    surface: &'static str,
+}
+
+impl<L: VLog> FlattenVLogger<L> {
+    /// Forward all records to the given surface of `inner`.
+    pub const fn new(inner: L, surface: &'static str) -> Self {
+        FlattenVLogger { inner, surface }
+    }
+
+    /// The inner vlogger.
+    pub fn inner(&self) -> &L {
+        &self.inner
+    }
+
+    /// The surface all records are forwarded to.
+    pub fn surface(&self) -> &'static str {
+        self.surface
+    }
+
+    /// Returns the inner vlogger.
+    pub fn into_inner(self) -> L {
+        self.inner
+    }
+}
+
+/// The message of a record with its original surface in front.
+struct Prefixed<'a> {
+    surface: &'a str,
+    args: &'a fmt::Arguments<'a>,
+}
+
+impl fmt::Display for Prefixed<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "[{}]", self.surface)?;
+        if self.args.as_str() != Some("") {
+            write!(f, " {}", self.args)?;
+        }
+        Ok(())
+    }
+}
+
+impl<L: VLog> VLog for FlattenVLogger<L> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(
+            &Metadata::builder()
+                .surface(self.surface)
+                .target(metadata.target())
+                .build(),
+        )
+    }
+
+    fn vlog(&self, record: &Record) {
+        let prefixed = Prefixed {
+            surface: record.surface(),
+            args: record.args(),
+        };
+        let mut builder = Record::builder();
+        builder
+            .surface(self.surface)
+            .target(record.target())
+            .visual(record.visual().clone())
+            .color(*record.color())
+            .opacity(record.opacity())
+            .size(record.size())
+            .module_path(record.module_path())
+            .file(record.file())
+            .line(record.line())
+            .call_site_id(record.call_site_id())
+            .path_id(record.path_id())
+            .tooltip(record.tooltip())
+            .z_order(record.z_order());
+        #[cfg(feature = "std")]
+        builder.ttl(record.ttl());
+        #[cfg(feature = "kv")]
+        builder.key_values(record.key_values());
+        self.inner
+            .vlog(&builder.args(format_args!("{}", prefixed)).build());
+    }
+
+    fn clear(&self, _: &str) {
+        self.inner.clear(self.surface);
+    }
+
+    fn remove_surface(&self, _: &str) {
+        self.inner.remove_surface(self.surface);
+    }
+
+    fn transaction(&self, _: &str, f: &dyn Fn(&dyn VLog)) {
+        let surface = self.surface;
+        self.inner.transaction(surface, &|inner| {
+            f(&FlattenVLogger { inner, surface });
+        });
+    }
+
+    fn begin_frame(&self, _: &str) {
+        self.inner.begin_frame(self.surface);
+    }
+
+    fn end_frame(&self, _: &str) {
+        self.inner.end_frame(self.surface);
+    }
+
+    fn push_group(&self, name: &str) {
+        self.inner.push_group(name);
+    }
+
+    fn pop_group(&self) {
+        self.inner.pop_group();
+    }
+
+    fn flush(&self) {
+        self.inner.flush();
+    }
+
+    fn on_shutdown(&self) {
+        self.inner.on_shutdown();
+    }
+
+    fn set_draw_mode(&self, _: &str, mode: DrawMode) {
+        self.inner.set_draw_mode(self.surface, mode);
+    }
+
+    fn set_view(&self, _: &str, view: &View) {
+        self.inner.set_view(self.surface, view);
+    }
+
+    fn snapshot(&self, _: &str, path: &str) -> Result<(), VLogError> {
+        self.inner.snapshot(self.surface, path)
+    }
+
+    fn quantize_color(&self, color: Color) -> Color {
+        self.inner.quantize_color(color)
+    }
+
+    fn max_records(&self, _: &str) -> Option<usize> {
+        self.inner.max_records(self.surface)
+    }
+}
@@ -0,0 +1,90 @@
+//! Forwarding of the [`VLog`](crate::VLog) methods, shared by the vlogger adapters.
+
+// Implements the listed `VLog` methods by forwarding them unchanged to the given fields,
+// in order, calling `self.$before()` first if `before` is given.
+//
+// The methods which return a value only take a single field.
+//
+// forward_vlog! {
+//     inner; before flush_buffer => begin_frame, end_frame
+// }
+macro_rules! forward_vlog {
+    ($($inner:ident),+ $(; before $before:ident)? => $($method:ident),* $(,)?) => {
+        forward_vlog!(@all ($($inner),+) ($($before)?) $($method)*);
+    };
+    (@all $inner:tt $before:tt $($method:ident)*) => {
+        $(forward_vlog!(@one $method $inner $before);)*
+    };
+    (@one remove_surface ($($inner:ident),+) ($($before:ident)?)) => {
+        fn remove_surface(&self, surface: &str) {
+            $(self.$before();)?
+            $(self.$inner.remove_surface(surface);)+
+        }
+    };
+    (@one begin_frame ($($inner:ident),+) ($($before:ident)?)) => {
+        fn begin_frame(&self, surface: &str) {
+            $(self.$before();)?
+            $(self.$inner.begin_frame(surface);)+
+        }
+    };
+    (@one end_frame ($($inner:ident),+) ($($before:ident)?)) => {
+        fn end_frame(&self, surface: &str) {
+            $(self.$before();)?
+            $(self.$inner.end_frame(surface);)+
+        }
+    };
+    (@one push_group ($($inner:ident),+) ($($before:ident)?)) => {
+        fn push_group(&self, name: &str) {
+            $(self.$before();)?
+            $(self.$inner.push_group(name);)+
+        }
+    };
+    (@one pop_group ($($inner:ident),+) ($($before:ident)?)) => {
+        fn pop_group(&self) {
+            $(self.$before();)?
+            $(self.$inner.pop_group();)+
+        }
+    };
+    (@one flush ($($inner:ident),+) ($($before:ident)?)) => {
+        fn flush(&self) {
+            $(self.$before();)?
+            $(self.$inner.flush();)+
+        }
+    };
+    (@one on_shutdown ($($inner:ident),+) ($($before:ident)?)) => {
+        fn on_shutdown(&self) {
+            $(self.$before();)?
+            $(self.$inner.on_shutdown();)+
+        }
+    };
+    (@one set_draw_mode ($($inner:ident),+) ($($before:ident)?)) => {
+        fn set_draw_mode(&self, surface: &str, mode: $crate::DrawMode) {
+            $(self.$before();)?
+            $(self.$inner.set_draw_mode(surface, mode);)+
+        }
+    };
+    (@one set_view ($($inner:ident),+) ($($before:ident)?)) => {
+        fn set_view(&self, surface: &str, view: &$crate::View) {
+            $(self.$before();)?
+            $(self.$inner.set_view(surface, view);)+
+        }
+    };
+    (@one snapshot ($inner:ident) ($($before:ident)?)) => {
+        fn snapshot(&self, surface: &str, path: &str) -> Result<(), $crate::VLogError> {
+            $(self.$before();)?
+            self.$inner.snapshot(surface, path)
+        }
+    };
+    (@one quantize_color ($inner:ident) ($($before:ident)?)) => {
+        fn quantize_color(&self, color: $crate::Color) -> $crate::Color {
+            $(self.$before();)?
+            self.$inner.quantize_color(color)
+        }
+    };
+    (@one max_records ($inner:ident) ($($before:ident)?)) => {
+        fn max_records(&self, surface: &str) -> Option<usize> {
+            $(self.$before();)?
+            self.$inner.max_records(surface)
+        }
+    };
+}
@@ -0,0 +1,668 @@
+//! Recording vlog commands as JSON lines and replaying them.
+
+use crate::{Metadata, Record, RecordOwned, VLog};
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeSeed, IntoDeserializer, Visitor};
+use serde::ser::{SerializeMap, SerializeSeq, SerializeStruct, SerializeStructVariant};
+use serde::ser::{SerializeTuple, SerializeTupleStruct, SerializeTupleVariant};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use serde_json::Value;
+use std::error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufWriter, Write};
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// A single line of the JSON output.
+#[derive(Serialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum Command<'a> {
+    Vlog {
+        #[serde(flatten)]
+        record: Box<RecordOwned>,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        time: Option<f64>,
+    },
+    Clear {
+        surface: &'a str,
+        #[serde(skip_serializing_if = "Option::is_none")]
+        time: Option<f64>,
+    },
+}
+
+/// A single line of the JSON input.
+///
+/// The record is decoded from the [`Value`] afterwards, as the strings of
+/// non-finite floats can't be told apart from other strings while buffering.
+/// Unknown fields, like the time, are ignored.
+#[derive(Deserialize)]
+#[serde(tag = "command", rename_all = "snake_case")]
+enum Line {
+    Vlog(Value),
+    Clear { surface: String },
+}
+
+/// A vlogger which writes every command as a line of JSON.
+///
+/// Every call to [`vlog`](VLog::vlog) and [`clear`](VLog::clear) is written as one
+/// JSON object per line, which can be read back with [`replay_json`].
+/// Every line has the time of the call, unless disabled with
+/// [`without_timestamps`](JsonlVLogger::without_timestamps).
+/// Write errors are ignored, as vloggers can't report them.
+///
+/// JSON has no representation for non-finite floats, so they are written as
+/// the strings `"NaN"`, `"inf"` and `"-inf"` and restored by [`replay_json`].
+///
+/// The writer is behind a [`Mutex`], so the vlogger can be shared between threads
+/// and the lines of concurrent calls don't interleave. Buffered writers are flushed
+/// by [`flush`](VLog::flush).
+///
+/// Requires the `serde` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::point;
+/// use v_log::sinks::JsonlVLogger;
+///
+/// let vlogger = JsonlVLogger::new(Vec::new());
+/// point!(vlogger: vlogger, "main_surface", [1.0, 2.0], 5.0, Base);
+///
+/// let json = String::from_utf8(vlogger.into_inner()).unwrap();
+/// assert_eq!(json.lines().count(), 1);
+/// assert!(json.starts_with(r#"{"command":"vlog","#));
+/// assert!(json.contains(r#""time":"#));
+/// ```
+#[derive(Debug)]
+pub struct JsonlVLogger<W> {
+    writer: Mutex<W>,
+    timestamps: bool,
+}
+
+impl JsonlVLogger<BufWriter<File>> {
+    /// Create a new `JsonlVLogger` writing to a new file at `path`, through a [`BufWriter`].
+    ///
+    /// An existing file is truncated. Call [`flush`](VLog::flush), e.g. with
+    /// [`shutdown`](crate::shutdown), to make sure all lines are written.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the file can't be created.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::sinks::JsonlVLogger;
+    /// use v_log::{clear, point, VLog};
+    ///
+    /// let path = std::env::temp_dir().join("v_log_create_example.jsonl");
+    /// let vlogger = JsonlVLogger::create(&path).unwrap();
+    /// point!(vlogger: vlogger, "main_surface", [1.0, 2.0], 5.0, Base);
+    /// clear!(vlogger: vlogger, "main_surface");
+    /// vlogger.flush();
+    ///
+    /// let json = std::fs::read_to_string(&path).unwrap();
+    /// # std::fs::remove_file(&path).unwrap();
+    /// assert_eq!(json.lines().count(), 2);
+    /// assert!(json.lines().all(|line| line.contains(r#""time":"#)));
+    /// // the timestamps are ignored when replaying.
+    /// assert_eq!(v_log::sinks::replay_json(json.as_bytes(), &JsonlVLogger::new(Vec::new())).unwrap(), 2);
+    /// ```
+    pub fn create(path: impl AsRef<Path>) -> io::Result<Self> {
+        Ok(JsonlVLogger::new(BufWriter::new(File::create(path)?)))
+    }
+}
+
+impl<W: Write> JsonlVLogger<W> {
+    /// Create a new `JsonlVLogger` writing to `writer`.
+    pub fn new(writer: W) -> Self {
+        JsonlVLogger {
+            writer: Mutex::new(writer),
+            timestamps: true,
+        }
+    }
+
+    /// Don't add the time of the call to every line.
+    ///
+    /// By default, every line has a `time` in seconds since the Unix epoch,
+    /// which is ignored by [`replay_json`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::point;
+    /// use v_log::sinks::JsonlVLogger;
+    ///
+    /// let vlogger = JsonlVLogger::new(Vec::new()).without_timestamps();
+    /// point!(vlogger: vlogger, "main_surface", [1.0, 2.0], 5.0, Base);
+    ///
+    /// let json = String::from_utf8(vlogger.into_inner()).unwrap();
+    /// assert!(!json.contains(r#""time":"#));
+    /// ```
+    pub fn without_timestamps(mut self) -> Self {
+        self.timestamps = false;
+        self
+    }
+
+    /// Take the writer back out of the vlogger.
+    pub fn into_inner(self) -> W {
+        self.writer.into_inner().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn time(&self) -> Option<f64> {
+        if !self.timestamps {
+            return None;
+        }
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .ok()
+            .map(|time| time.as_secs_f64())
+    }
+
+    fn write(&self, command: &Command) {
+        // a failed serialization must not leave half a line in the writer.
+        let Ok(mut line) = serde_json::to_vec(&NonFinite(command)) else {
+            return;
+        };
+        line.push(b'\n');
+        let mut writer = self.writer.lock().unwrap_or_else(|e| e.into_inner());
+        let _ = writer.write_all(&line);
+    }
+}
+
+impl<W: Write + Send> VLog for JsonlVLogger<W> {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        self.write(&Command::Vlog {
+            record: Box::new(record.into()),
+            time: self.time(),
+        });
+    }
+
+    fn clear(&self, surface: &str) {
+        self.write(&Command::Clear {
+            surface,
+            time: self.time(),
+        });
+    }
+
+    fn flush(&self) {
+        let _ = self
+            .writer
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .flush();
+    }
+}
+
+/// Replays the commands written by a [`JsonlVLogger`] to another vlogger.
+///
+/// The records are passed to [`vlog`](VLog::vlog) directly, without checking
+/// if they are enabled. Empty lines are skipped.
+/// Returns the number of replayed commands.
+///
+/// Requires the `serde` feature.
+///
+/// # Errors
+///
+/// Returns an error if reading fails or a line isn't a valid command.
+/// The commands before the invalid line have been replayed at that point.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Mutex;
+/// use v_log::sinks::JsonlVLogger;
+/// use v_log::{clear, point, polyline, Metadata, Record, RecordOwned, VLog};
+///
+/// #[derive(Default)]
+/// struct Capture(Mutex<Vec<String>>);
+///
+/// impl VLog for Capture {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         self.0.lock().unwrap().push(format!("{:?}", RecordOwned::from(record)));
+///     }
+///     fn clear(&self, surface: &str) {
+///         self.0.lock().unwrap().push(format!("clear {}", surface));
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// fn draw(vlogger: &dyn VLog) {
+///     point!(vlogger: vlogger, "main_surface", [1.0, 2.0], 5.0, Healthy, "o", "start");
+///     point!(vlogger: vlogger, "main_surface", [f64::NAN, f64::INFINITY], 5.0, Base);
+///     polyline!(vlogger: vlogger, "main_surface", ([0.0, 0.0], [1.0, 2.0]), 2.0, 0xff8000ff, "--");
+///     clear!(vlogger: vlogger, "main_surface");
+/// }
+///
+/// let json = JsonlVLogger::new(Vec::new());
+/// draw(&json);
+/// let json = json.into_inner();
+///
+/// let direct = Capture::default();
+/// draw(&direct);
+/// let replayed = Capture::default();
+/// assert_eq!(v_log::sinks::replay_json(&json[..], &replayed).unwrap(), 4);
+/// // the non-finite floats survive the round-trip.
+/// assert!(replayed.0.lock().unwrap()[1].contains("x: NaN, y: inf"));
+/// assert_eq!(*replayed.0.lock().unwrap(), *direct.0.lock().unwrap());
+///
+/// assert!(v_log::sinks::replay_json(&b"not json\n"[..], &replayed).is_err());
+/// ```
+pub fn replay_json(reader: impl BufRead, target: &dyn VLog) -> Result<usize, ReplayError> {
+    let mut count = 0;
+    for (index, line) in reader.lines().enumerate() {
+        let line = line.map_err(ReplayError::Io)?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        let parse = |error| ReplayError::Parse {
+            line: index + 1,
+            error,
+        };
+        match serde_json::from_str(&line).map_err(parse)? {
+            Line::Vlog(record) => RecordOwned::deserialize(NonFinite(record))
+                .map_err(parse)?
+                .with_record(|record| target.vlog(record)),
+            Line::Clear { surface } => target.clear(&surface),
+        }
+        count += 1;
+    }
+    Ok(count)
+}
+
+/// The error returned by [`replay_json`].
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum ReplayError {
+    /// Reading the input failed.
+    Io(io::Error),
+    /// A line couldn't be parsed as a command.
+    Parse {
+        /// The line number, starting at 1.
+        line: usize,
+        /// The underlying JSON error.
+        error: serde_json::Error,
+    },
+}
+
+impl fmt::Display for ReplayError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ReplayError::Io(error) => write!(fmt, "failed to read vlog commands: {}", error),
+            ReplayError::Parse { line, error } => {
+                write!(fmt, "invalid vlog command in line {}: {}", line, error)
+            }
+        }
+    }
+}
+
+impl error::Error for ReplayError {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            ReplayError::Io(error) => Some(error),
+            ReplayError::Parse { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Encodes non-finite floats as strings when serializing, and decodes them again
+/// when deserializing a [`Value`].
+///
+/// Everything else is passed through unchanged.
+struct NonFinite<T>(T);
+
+fn encode(v: f64) -> &'static str {
+    if v.is_nan() {
+        "NaN"
+    } else if v > 0.0 {
+        "inf"
+    } else {
+        "-inf"
+    }
+}
+
+fn decode(s: &str) -> Option<f64> {
+    match s {
+        "NaN" => Some(f64::NAN),
+        "inf" => Some(f64::INFINITY),
+        "-inf" => Some(f64::NEG_INFINITY),
+        _ => None,
+    }
+}
+
+impl<T: Serialize + ?Sized> Serialize for NonFinite<&T> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        self.0.serialize(NonFinite(serializer))
+    }
+}
+
+macro_rules! forward_serialize {
+    ($($method:ident($($arg:ident: $ty:ty),*);)*) => {$(
+        fn $method(self, $($arg: $ty),*) -> Result<S::Ok, S::Error> {
+            self.0.$method($($arg),*)
+        }
+    )*};
+}
+
+impl<S: Serializer> Serializer for NonFinite<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+    type SerializeSeq = NonFinite<S::SerializeSeq>;
+    type SerializeTuple = NonFinite<S::SerializeTuple>;
+    type SerializeTupleStruct = NonFinite<S::SerializeTupleStruct>;
+    type SerializeTupleVariant = NonFinite<S::SerializeTupleVariant>;
+    type SerializeMap = NonFinite<S::SerializeMap>;
+    type SerializeStruct = NonFinite<S::SerializeStruct>;
+    type SerializeStructVariant = NonFinite<S::SerializeStructVariant>;
+
+    forward_serialize! {
+        serialize_bool(v: bool);
+        serialize_i8(v: i8);
+        serialize_i16(v: i16);
+        serialize_i32(v: i32);
+        serialize_i64(v: i64);
+        serialize_u8(v: u8);
+        serialize_u16(v: u16);
+        serialize_u32(v: u32);
+        serialize_u64(v: u64);
+        serialize_char(v: char);
+        serialize_str(v: &str);
+        serialize_bytes(v: &[u8]);
+        serialize_none();
+        serialize_unit();
+        serialize_unit_struct(name: &'static str);
+        serialize_unit_variant(name: &'static str, index: u32, variant: &'static str);
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<S::Ok, S::Error> {
+        if v.is_finite() {
+            self.0.serialize_f32(v)
+        } else {
+            self.0.serialize_str(encode(v.into()))
+        }
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<S::Ok, S::Error> {
+        if v.is_finite() {
+            self.0.serialize_f64(v)
+        } else {
+            self.0.serialize_str(encode(v))
+        }
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(self, value: &T) -> Result<S::Ok, S::Error> {
+        self.0.serialize_some(&NonFinite(value))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.0.serialize_newtype_struct(name, &NonFinite(value))
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        value: &T,
+    ) -> Result<S::Ok, S::Error> {
+        self.0
+            .serialize_newtype_variant(name, index, variant, &NonFinite(value))
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<Self::SerializeSeq, S::Error> {
+        self.0.serialize_seq(len).map(NonFinite)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<Self::SerializeTuple, S::Error> {
+        self.0.serialize_tuple(len).map(NonFinite)
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleStruct, S::Error> {
+        self.0.serialize_tuple_struct(name, len).map(NonFinite)
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeTupleVariant, S::Error> {
+        self.0
+            .serialize_tuple_variant(name, index, variant, len)
+            .map(NonFinite)
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, S::Error> {
+        self.0.serialize_map(len).map(NonFinite)
+    }
+
+    fn serialize_struct(
+        self,
+        name: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStruct, S::Error> {
+        self.0.serialize_struct(name, len).map(NonFinite)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        name: &'static str,
+        index: u32,
+        variant: &'static str,
+        len: usize,
+    ) -> Result<Self::SerializeStructVariant, S::Error> {
+        self.0
+            .serialize_struct_variant(name, index, variant, len)
+            .map(NonFinite)
+    }
+}
+
+macro_rules! forward_compound {
+    ($($trait:ident::$method:ident),*) => {$(
+        impl<S: $trait> $trait for NonFinite<S> {
+            type Ok = S::Ok;
+            type Error = S::Error;
+
+            fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+                self.0.$method(&NonFinite(value))
+            }
+
+            fn end(self) -> Result<S::Ok, S::Error> {
+                self.0.end()
+            }
+        }
+    )*};
+}
+
+forward_compound!(
+    SerializeSeq::serialize_element,
+    SerializeTuple::serialize_element,
+    SerializeTupleStruct::serialize_field,
+    SerializeTupleVariant::serialize_field
+);
+
+macro_rules! forward_struct {
+    ($($trait:ident),*) => {$(
+        impl<S: $trait> $trait for NonFinite<S> {
+            type Ok = S::Ok;
+            type Error = S::Error;
+
+            fn serialize_field<T: Serialize + ?Sized>(
+                &mut self,
+                key: &'static str,
+                value: &T,
+            ) -> Result<(), S::Error> {
+                self.0.serialize_field(key, &NonFinite(value))
+            }
+
+            fn skip_field(&mut self, key: &'static str) -> Result<(), S::Error> {
+                self.0.skip_field(key)
+            }
+
+            fn end(self) -> Result<S::Ok, S::Error> {
+                self.0.end()
+            }
+        }
+    )*};
+}
+
+forward_struct!(SerializeStruct, SerializeStructVariant);
+
+impl<S: SerializeMap> SerializeMap for NonFinite<S> {
+    type Ok = S::Ok;
+    type Error = S::Error;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), S::Error> {
+        self.0.serialize_key(&NonFinite(key))
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), S::Error> {
+        self.0.serialize_value(&NonFinite(value))
+    }
+
+    fn end(self) -> Result<S::Ok, S::Error> {
+        self.0.end()
+    }
+}
+
+impl<'de> Deserializer<'de> for NonFinite<Value> {
+    type Error = serde_json::Error;
+
+    fn deserialize_any<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Array(values) => {
+                let mut seq = SeqDeserializer::new(values.into_iter().map(NonFinite));
+                let value = visitor.visit_seq(&mut seq)?;
+                seq.end()?;
+                Ok(value)
+            }
+            Value::Object(map) => {
+                let mut map = MapDeserializer::new(map.into_iter().map(|(k, v)| (k, NonFinite(v))));
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            value => value.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_f32<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        self.deserialize_f64(visitor)
+    }
+
+    fn deserialize_f64<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(s) => match decode(&s) {
+                Some(v) => visitor.visit_f64(v),
+                None => Value::String(s).deserialize_f64(visitor),
+            },
+            value => value.deserialize_f64(visitor),
+        }
+    }
+
+    fn deserialize_option<V: Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::Null => visitor.visit_none(),
+            value => visitor.visit_some(NonFinite(value)),
+        }
+    }
+
+    fn deserialize_newtype_struct<V: Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    fn deserialize_enum<V: Visitor<'de>>(
+        self,
+        name: &'static str,
+        variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self.0 {
+            Value::String(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Object(map) if map.len() == 1 => {
+                let (variant, value) = map.into_iter().next().unwrap();
+                visitor.visit_enum(NonFinite((variant, value)))
+            }
+            value => value.deserialize_enum(name, variants, visitor),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 i128 u8 u16 u32 u64 u128 char str string bytes byte_buf
+        unit unit_struct seq tuple tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, serde_json::Error> for NonFinite<Value> {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+// an enum variant with its content.
+impl<'de> de::EnumAccess<'de> for NonFinite<(String, Value)> {
+    type Error = serde_json::Error;
+    type Variant = NonFinite<Value>;
+
+    fn variant_seed<V: DeserializeSeed<'de>>(
+        self,
+        seed: V,
+    ) -> Result<(V::Value, Self::Variant), Self::Error> {
+        let (variant, value) = self.0;
+        let variant = seed.deserialize(variant.into_deserializer())?;
+        Ok((variant, NonFinite(value)))
+    }
+}
+
+impl<'de> de::VariantAccess<'de> for NonFinite<Value> {
+    type Error = serde_json::Error;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Deserialize::deserialize(self)
+    }
+
+    fn newtype_variant_seed<T: DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self)
+    }
+
+    fn tuple_variant<V: Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_any(visitor)
+    }
+}
@@ -0,0 +1,125 @@
+//! Structured key-value pairs attached to records.
+//!
+//! The macros which take a message accept a trailing `; key = value, ...` section
+//! after it. The pairs are available to the vlogger with [`Record::key_values`](crate::Record::key_values),
+//! e.g. to show them on hover. The keys are identifiers and the values are
+//! converted with [`Value::from`]. Without the section, the records have no pairs
+//! and nothing is allocated.
+//!
+//! Requires the `kv` feature.
+//!
+//! # Examples
+//!
+//! ```
+//! use v_log::{message, point};
+//!
+//! let entity_id = 42;
+//! point!("main_surface", [1.0, 2.0], 5.0, Base, "o", "entity"; entity_id = entity_id, iteration = 7);
+//! message!("main_surface", "step {}", 3; converged = false, residual = 0.25, solver = "cg");
+//! ```
+
+use core::fmt;
+
+/// The value of a key-value pair.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Value<'a> {
+    /// An integer.
+    I64(i64),
+    /// A floating point number.
+    F64(f64),
+    /// A boolean.
+    Bool(bool),
+    /// A string.
+    Str(&'a str),
+}
+
+impl fmt::Display for Value<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::I64(v) => fmt::Display::fmt(v, f),
+            Value::F64(v) => fmt::Display::fmt(v, f),
+            Value::Bool(v) => fmt::Display::fmt(v, f),
+            Value::Str(v) => fmt::Display::fmt(v, f),
+        }
+    }
+}
+
+macro_rules! impl_from {
+    ($($ty:ty => $variant:ident),* $(,)?) => {
+        $(
+            impl From<$ty> for Value<'_> {
+                fn from(v: $ty) -> Self {
+                    Value::$variant(v.into())
+                }
+            }
+        )*
+    };
+}
+
+impl_from!(
+    i8 => I64,
+    i16 => I64,
+    i32 => I64,
+    i64 => I64,
+    u8 => I64,
+    u16 => I64,
+    u32 => I64,
+    f32 => F64,
+    f64 => F64,
+    bool => Bool,
+);
+
+impl<'a> From<&'a str> for Value<'a> {
+    fn from(v: &'a str) -> Self {
+        Value::Str(v)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a> From<&'a alloc::string::String> for Value<'a> {
+    fn from(v: &'a alloc::string::String) -> Self {
+        Value::Str(v)
+    }
+}
+
+/// An owned version of a [`Value`], as stored in a [`RecordOwned`](crate::RecordOwned).
+///
+/// Requires the `kv` and `alloc` features.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ValueOwned {
+    /// An integer.
+    I64(i64),
+    /// A floating point number.
+    F64(f64),
+    /// A boolean.
+    Bool(bool),
+    /// A string.
+    Str(alloc::string::String),
+}
+
+#[cfg(feature = "alloc")]
+impl ValueOwned {
+    /// The borrowed [`Value`].
+    pub fn as_value(&self) -> Value<'_> {
+        match self {
+            ValueOwned::I64(v) => Value::I64(*v),
+            ValueOwned::F64(v) => Value::F64(*v),
+            ValueOwned::Bool(v) => Value::Bool(*v),
+            ValueOwned::Str(v) => Value::Str(v),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl From<Value<'_>> for ValueOwned {
+    fn from(v: Value<'_>) -> Self {
+        match v {
+            Value::I64(v) => ValueOwned::I64(v),
+            Value::F64(v) => ValueOwned::F64(v),
+            Value::Bool(v) => ValueOwned::Bool(v),
+            Value::Str(v) => ValueOwned::Str(v.into()),
+        }
+    }
+}
@@ -29,7 +29,9 @@
 //! # Usage
 //!
 //! The basic use of the vlog crate is through the vlogging macros:
-//! [`point!`], [`polyline!`], [`arrow!`], [`message!`], [`label!`], [`clear!`].
+//! [`point!`], [`polyline!`], [`ray!`], [`path!`], [`aabb!`], [`obb!`], [`axes!`], [`arc!`], [`bezier!`], [`arrow!`], [`ellipse!`], [`errorbar!`], [`grid!`], [`message!`], [`label!`], [`clear!`], [`clear_many!`], [`remove_surface!`], [`transaction!`], [`begin_frame!`], [`end_frame!`], [`group!`], [`set_view!`], [`snapshot!`].
+//! With the `alloc` feature, there is also [`point_cloud!`], [`spline!`], [`polygon!`], [`mesh!`], [`quiver!`] and [`image!`]
+//! and with the `std` feature [`polyline_gradient!`] and [`timed_label!`].
 //! They form the building blocks of drawing.
 //!
 //! The following example draws a square with text inside in 3 different ways
@@ -54,6 +56,15 @@
 //! can be used directly as arguments, however it is recommended to use the shorthands instead.
 //! The shorthands are documented on the enum items. E.g. [`LineStyle::Simple`] would be `"-"`.
 //!
+//! With the `test-util` feature, the `v_log::test` module provides a `CaptureVLogger`,
+//! which records all vlog calls for assertions in tests.
+//!
+//! With the `kv` feature, structured key-value pairs can be attached to the messages
+//! with a trailing `; key = value, ...` section, see the `v_log::kv` module.
+//!
+//! Positions are anything which yields the coordinates as `f64`, e.g. `[x, y]` or [`pos3`]`(x, y, z)`,
+//! or implements [`Position`]. With the `glam` feature, the vectors of `glam` can be used directly.
+//!
 //! # Implementing a Vlogger
 //!
 //! Visual loggers implement the [`VLog`] trait. Here is a very basic example, that
@@ -85,7 +96,7 @@
 //!             Visual::Message => {
 //!                 println!("{surface}: {label}");
 //!             }
-//!             Visual::Label { x, y, z, alignment } => {
+//!             Visual::Label { x, y, z, alignment, .. } => {
 //!                 draw_text(surface, [*x, *y, *z], size, &label);
 //!             }
 //!             Visual::Point { x, y, z, style } => {
@@ -94,12 +105,13 @@
 //!                     draw_text(surface, [*x, *y, *z], size, &label);
 //!                 }
 //!             }
-//!             Visual::Line { x1, y1, z1, x2, y2, z2, style } => {
+//!             Visual::Line { x1, y1, z1, x2, y2, z2, .. } => {
 //!                 draw_line(surface, [*x1, *y1, *z1], [*x2, *y2, *z2], size);
 //!                 if !label.is_empty() {
 //!                     draw_text(surface, [(x1 + x2) * 0.5, (y1 + y2) * 0.5, (z1 + z2) * 0.5], 16.0, &label);
 //!                 }
 //!             }
+//!             _ => {}
 //!         }
 //!     }
 //!     fn clear(&self, surface: &str) {
@@ -118,6 +130,11 @@
 #[cfg(all(not(feature = "std"), not(test)))]
 extern crate core as std;
 
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+#[cfg(feature = "alloc")]
+use alloc::vec::Vec;
 #[cfg(feature = "std")]
 use std::error;
 use std::fmt;
@@ -132,8 +149,101 @@ use std::sync::atomic::Ordering;
 
 #[macro_use]
 pub mod macros;
+#[cfg(feature = "alloc")]
+#[macro_use]
+mod forward;
 #[doc(hidden)]
 pub mod __private_api;
+#[cfg(feature = "std")]
+mod ascii;
+#[cfg(feature = "std")]
+mod auto_flush;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod buffered;
+pub mod colormap;
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+mod env;
+#[cfg(feature = "alloc")]
+mod filter;
+mod flatten;
+#[cfg(feature = "serde")]
+mod json;
+#[cfg(feature = "kv")]
+pub mod kv;
+#[cfg(feature = "log-bridge")]
+mod log_bridge;
+#[cfg(feature = "alloc")]
+mod owned;
+#[cfg(feature = "plotters")]
+mod plotters;
+mod position;
+#[cfg(feature = "std")]
+mod rate_limit;
+#[cfg(feature = "rerun")]
+mod rerun;
+#[cfg(feature = "std")]
+mod sample;
+#[cfg(feature = "std")]
+mod setup;
+#[cfg(feature = "std")]
+pub mod sinks;
+#[cfg(feature = "alloc")]
+mod spatial;
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+mod stderr;
+#[cfg(feature = "std")]
+mod surfaces;
+#[cfg(feature = "std")]
+mod svg;
+#[cfg(feature = "std")]
+mod tap;
+#[cfg(feature = "std")]
+mod tee;
+#[cfg(feature = "test-util")]
+pub mod test;
+#[cfg(feature = "std")]
+mod validate;
+
+#[cfg(feature = "std")]
+pub use ascii::AsciiVLogger;
+#[cfg(feature = "std")]
+pub use auto_flush::set_auto_flush;
+#[cfg(feature = "std")]
+pub use batch::LocalBatch;
+#[cfg(feature = "std")]
+pub use buffered::Buffered;
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+pub use env::init_from_env;
+#[cfg(feature = "alloc")]
+pub use filter::Filter;
+pub use flatten::FlattenVLogger;
+#[cfg(feature = "log-bridge")]
+pub use log_bridge::LogBridge;
+#[cfg(feature = "alloc")]
+pub use owned::{group_by_surface_and_kind, RecordOwned};
+#[cfg(feature = "plotters")]
+pub use plotters::PlottersVLogger;
+pub use position::{pos2, pos3, Point, Position};
+#[cfg(feature = "std")]
+pub use rate_limit::RateLimit;
+#[cfg(feature = "rerun")]
+pub use rerun::RerunVLogger;
+#[cfg(feature = "std")]
+pub use sample::Sample;
+#[cfg(feature = "std")]
+pub use setup::{describe, VLogSetup};
+#[cfg(feature = "alloc")]
+pub use spatial::SpatialIndex;
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+pub use stderr::init_stdout;
+#[cfg(feature = "std")]
+pub use tap::{add_tap, remove_tap, TapId};
+#[cfg(feature = "std")]
+pub use tee::{tee, Tee};
+#[cfg(feature = "std")]
+pub use validate::{enable_validation, validation_warnings};
 
 #[cfg(not(target_has_atomic = "ptr"))]
 struct AtomicUsize {
@@ -153,6 +263,12 @@ impl AtomicUsize {
     fn store(&self, val: usize, _order: Ordering) {
         self.v.set(val)
     }
+
+    fn fetch_add(&self, val: usize, _order: Ordering) -> usize {
+        let prev = self.v.get();
+        self.v.set(prev.wrapping_add(val));
+        prev
+    }
 }
 
 // Any platform without atomics is unlikely to have multiple cores, so
@@ -164,8 +280,22 @@ unsafe impl Sync for AtomicUsize {}
 // the STATE static which determines whether VLOGGER has been initialized yet.
 static mut VLOGGER: &dyn VLog = &NopVLogger;
 
+// A vlogger which replaced the one in VLOGGER after initialization, see `replace_vlogger`.
+// VLOGGER can't be swapped atomically, so this points to a leaked reference instead.
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+static REPLACED: std::sync::atomic::AtomicPtr<&'static dyn VLog> =
+    std::sync::atomic::AtomicPtr::new(core::ptr::null_mut());
+
 static STATE: AtomicUsize = AtomicUsize::new(0);
 
+static CAPTURE_LOCATION: AtomicUsize = AtomicUsize::new(1);
+
+static FRAME: AtomicUsize = AtomicUsize::new(0);
+
+static DEFAULT_POINT_STYLE: AtomicUsize = AtomicUsize::new(PointStyle::Point as usize);
+
+static DEFAULT_LINE_STYLE: AtomicUsize = AtomicUsize::new(LineStyle::Simple as usize);
+
 // There are three different states that we care about: the vlogger's
 // uninitialized, the vlogger's initializing (set_vlogger's been called but
 // VLOGGER hasn't actually been set yet), or the vlogger's active.
@@ -200,16 +330,34 @@ impl<'a> MaybeStaticStr<'a> {
 /// method of the [`VLog`] trait. Vlogger implementors manipulate these
 /// structures in order to display vlog commands. `Record`s are automatically
 /// created by the macros and so are not seen by vlog users.
+///
+/// # Serialization
+///
+/// A `Record` borrows its message as [`fmt::Arguments`], which can't be serialized.
+/// With the `serde` feature, convert it to a [`RecordOwned`] instead, which owns
+/// the formatted message and is the serialization boundary of this crate.
 #[derive(Clone, Debug)]
 pub struct Record<'a> {
     metadata: Metadata<'a>,
     visual: Visual,
     color: Color,
+    opacity: f32,
     size: f64,
     args: fmt::Arguments<'a>,
     module_path: Option<MaybeStaticStr<'a>>,
     file: Option<MaybeStaticStr<'a>>,
     line: Option<u32>,
+    call_site_id: u64,
+    path_id: u64,
+    tooltip: Option<&'a str>,
+    z_order: i32,
+    #[cfg(feature = "std")]
+    ttl: Option<std::time::Duration>,
+    #[cfg(feature = "kv")]
+    key_values: &'a [(&'a str, kv::Value<'a>)],
+    // the formatted `args`, if they have been rendered already.
+    #[cfg(feature = "alloc")]
+    message: Option<&'a str>,
 }
 
 impl<'a> Record<'a> {
@@ -237,12 +385,105 @@ impl<'a> Record<'a> {
         &self.color
     }
 
+    /// The opacity of the visual element in `[0, 1]`, which multiplies the alpha of the color.
+    ///
+    /// This fades out the whole visual element without changing its color, e.g. to draw
+    /// a shape at 30% with the `opacity: 0.3` argument of the macros. The default is `1.0`.
+    ///
+    /// The opacity is applied on top of the alpha of the color, so a [`Color::Hex`] with
+    /// an alpha byte of `0x80` or a [`Color::Rgba`] with an `a` of `0.5` drawn with an
+    /// opacity of `0.5` ends up at 25%. The color itself is left as it was sent, the
+    /// vlogger multiplies the two when rendering, see [`resolved_color`](Record::resolved_color).
+    /// Values out of range are clamped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// use v_log::{label, message, point, polyline, Metadata, Record, VLog};
+    ///
+    /// struct Capture(RefCell<Vec<f32>>);
+    ///
+    /// impl VLog for Capture {
+    ///     fn enabled(&self, _: &Metadata) -> bool { true }
+    ///     fn vlog(&self, record: &Record) {
+    ///         self.0.borrow_mut().push(record.opacity());
+    ///     }
+    ///     fn clear(&self, _: &str) {}
+    ///     fn flush(&self) {}
+    /// }
+    ///
+    /// assert_eq!(Record::builder().build().opacity(), 1.0);
+    ///
+    /// let capture = Capture(RefCell::new(Vec::new()));
+    /// point!(vlogger: capture, "main_surface", [0.0, 0.0], 5.0, Base);
+    /// point!(vlogger: capture, "main_surface", [0.0, 0.0], 5.0, Base, opacity: 0.3);
+    /// polyline!(vlogger: capture, "main_surface", ([0.0, 0.0], [1.0, 1.0]), 5.0, Base, opacity: 0.3);
+    /// label!(vlogger: capture, "main_surface", [0.0, 0.0], opacity: 0.3, "faded");
+    /// message!(vlogger: capture, "main_surface", opacity: 0.3, "faded");
+    /// assert_eq!(*capture.0.borrow(), [1.0, 0.3, 0.3, 0.3, 0.3]);
+    /// ```
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// The color of the visual element as RGBA bytes, with the opacity applied.
+    ///
+    /// Themed colors are resolved to their default shades with [`Color::to_rgba8`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{Color, Record};
+    ///
+    /// let record = Record::builder()
+    ///     .color(Color::Hex(0xff0000ff))
+    ///     .opacity(0.5)
+    ///     .build();
+    /// let [r, g, b, a] = record.resolved_color();
+    /// assert_eq!([r, g, b], [0xff, 0, 0]);
+    /// assert!((127..=128).contains(&a));
+    ///
+    /// let record = Record::builder().color(Color::Info).build();
+    /// assert_eq!(record.resolved_color(), Color::Info.to_rgba8());
+    /// ```
+    pub fn resolved_color(&self) -> [u8; 4] {
+        let [r, g, b, a] = self.color.to_rgba8();
+        let opacity = self.opacity.clamp(0.0, 1.0);
+        // `f32::round` is not available in `core`.
+        [r, g, b, (a as f32 * opacity + 0.5) as u8]
+    }
+
     /// The size of the visual element.
+    ///
+    /// A size `<= 0.0` means that the vlogger should use its default size,
+    /// see [`size_or_default`](Record::size_or_default).
     #[inline]
     pub fn size(&self) -> f64 {
         self.size
     }
 
+    /// The size of the visual element, or `default` if the size is `<= 0.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Record;
+    ///
+    /// assert_eq!(Record::builder().size(0.0).build().size_or_default(7.0), 7.0);
+    /// assert_eq!(Record::builder().size(-1.0).build().size_or_default(7.0), 7.0);
+    /// assert_eq!(Record::builder().size(3.0).build().size_or_default(7.0), 3.0);
+    /// ```
+    #[inline]
+    pub fn size_or_default(&self, default: f64) -> f64 {
+        if self.size <= 0.0 {
+            default
+        } else {
+            self.size
+        }
+    }
+
     /// Metadata about the vlog directive.
     #[inline]
     pub fn metadata(&self) -> &Metadata<'a> {
@@ -250,6 +491,44 @@ impl<'a> Record<'a> {
     }
 
     /// The name of the target of the directive.
+    ///
+    /// The macros default to the module path of the call site, so without a `target:`,
+    /// the target is equal to the [`module_path`](Record::module_path).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// use v_log::{label, message, point, polyline, Metadata, Record, VLog};
+    ///
+    /// struct Capture(RefCell<Vec<(String, Option<String>)>>);
+    ///
+    /// impl VLog for Capture {
+    ///     fn enabled(&self, _: &Metadata) -> bool { true }
+    ///     fn vlog(&self, record: &Record) {
+    ///         let module_path = record.module_path().map(|s| s.to_string());
+    ///         self.0.borrow_mut().push((record.target().to_string(), module_path));
+    ///     }
+    ///     fn clear(&self, _: &str) {}
+    ///     fn flush(&self) {}
+    /// }
+    ///
+    /// let vlogger = Capture(RefCell::new(Vec::new()));
+    /// message!(vlogger: vlogger, "main_surface", "text");
+    /// point!(vlogger: vlogger, "main_surface", [0.0, 0.0], 5.0, Base);
+    /// polyline!(vlogger: vlogger, "main_surface", ([0.0, 0.0], [1.0, 0.0]), 1.0, Base);
+    /// label!(vlogger: vlogger, "main_surface", [0.0, 0.0], "text");
+    /// for (target, module_path) in vlogger.0.take() {
+    ///     assert_eq!(target, module_path!());
+    ///     assert_eq!(Some(target), module_path);
+    /// }
+    ///
+    /// // with a target, the module path is still the one of the call site.
+    /// point!(vlogger: vlogger, target: "physics", "main_surface", [0.0, 0.0], 5.0, Base);
+    /// let (target, module_path) = vlogger.0.take().remove(0);
+    /// assert_eq!(target, "physics");
+    /// assert_eq!(module_path.as_deref(), Some(module_path!()));
+    /// ```
     #[inline]
     pub fn target(&self) -> &'a str {
         self.metadata.target()
@@ -261,6 +540,62 @@ impl<'a> Record<'a> {
         self.metadata.surface()
     }
 
+    /// A copy of this record, drawn to another surface.
+    ///
+    /// This is useful for vloggers which forward records to other surfaces,
+    /// e.g. to fan a record out to several surfaces.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{Color, PointStyle, Record, Visual};
+    ///
+    /// let record = Record::builder()
+    ///     .surface("physics")
+    ///     .target("my_target")
+    ///     .visual(Visual::Point { x: 1.0, y: 2.0, z: 0.0, style: PointStyle::Point })
+    ///     .color(Color::Info)
+    ///     .build();
+    /// let copy = record.with_surface("overview");
+    /// assert_eq!(copy.surface(), "overview");
+    /// assert_eq!(copy.target(), "my_target");
+    /// assert_eq!(copy.visual().position(), Some([1.0, 2.0, 0.0]));
+    /// assert!(matches!(copy.color(), Color::Info));
+    /// ```
+    #[inline]
+    pub fn with_surface(&self, surface: &'a str) -> Record<'a> {
+        Record {
+            metadata: Metadata {
+                surface,
+                ..self.metadata
+            },
+            ..self.clone()
+        }
+    }
+
+    /// A copy of this record with another target.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Record;
+    ///
+    /// let record = Record::builder().surface("physics").target("a").build();
+    /// let copy = record.with_target("b");
+    /// assert_eq!(copy.target(), "b");
+    /// assert_eq!(copy.surface(), "physics");
+    /// ```
+    #[inline]
+    pub fn with_target(&self, target: &'a str) -> Record<'a> {
+        Record {
+            metadata: Metadata {
+                target,
+                ..self.metadata
+            },
+            ..self.clone()
+        }
+    }
+
     /// The module path of the message.
     #[inline]
     pub fn module_path(&self) -> Option<&'a str> {
@@ -296,6 +631,880 @@ impl<'a> Record<'a> {
     pub fn line(&self) -> Option<u32> {
         self.line
     }
+
+    /// A stable identifier of the source location of the directive.
+    ///
+    /// The macros compute it from the file, line and column of the call site,
+    /// so two runs of the same program produce the same ids, independent of the
+    /// order of execution. This allows diffing the output of two runs structurally.
+    /// It is `0` if the record wasn't created by a macro.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use v_log::{Metadata, Record, VLog};
+    /// # struct Capture(RefCell<Vec<u64>>);
+    /// # impl VLog for Capture {
+    /// #     fn enabled(&self, _: &Metadata) -> bool { true }
+    /// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.call_site_id()) }
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// # }
+    /// use v_log::point;
+    ///
+    /// let capture = Capture(RefCell::new(Vec::new()));
+    /// for i in 0..2 {
+    ///     point!(vlogger: capture, "main_surface", [i as f64, 0.0], 5.0, Base);
+    /// }
+    /// point!(vlogger: capture, "main_surface", [2.0, 0.0], 5.0, Base);
+    ///
+    /// let ids = capture.0.borrow();
+    /// assert_eq!(ids[0], ids[1]);
+    /// assert_ne!(ids[1], ids[2]);
+    /// ```
+    #[inline]
+    pub fn call_site_id(&self) -> u64 {
+        self.call_site_id
+    }
+
+    /// Identifies the records which belong to the same path.
+    ///
+    /// All segments drawn by one call of [`polyline!`] with a list of points or
+    /// of [`path!`] share the same id, so vloggers can e.g. select the whole path.
+    /// Every call gets a new id. It is `0` if the record isn't part of a path.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use v_log::{Metadata, Record, VLog};
+    /// # struct Capture(RefCell<Vec<u64>>);
+    /// # impl VLog for Capture {
+    /// #     fn enabled(&self, _: &Metadata) -> bool { true }
+    /// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.path_id()) }
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// # }
+    /// use v_log::{point, polyline};
+    ///
+    /// let capture = Capture(RefCell::new(Vec::new()));
+    /// let points = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]];
+    /// for _ in 0..2 {
+    ///     polyline!(vlogger: capture, "main_surface", closed: points, 1.0, Base);
+    /// }
+    /// point!(vlogger: capture, "main_surface", [0.0, 0.0], 5.0, Base);
+    ///
+    /// let ids = capture.0.borrow();
+    /// // 3 segments and the (empty) label of the polygon
+    /// assert!(ids[0] != 0 && ids[..4].iter().all(|id| *id == ids[0]));
+    /// assert!(ids[4] != ids[0] && ids[4..8].iter().all(|id| *id == ids[4]));
+    /// assert_eq!(ids[8], 0);
+    /// ```
+    #[inline]
+    pub fn path_id(&self) -> u64 {
+        self.path_id
+    }
+
+    /// Additional text, which interactive vloggers show on demand, e.g. on hover.
+    ///
+    /// The [`args`](Record::args) stay the always visible label/message.
+    /// The macros set it with the `tip:` keyword.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use v_log::{Metadata, Record, VLog};
+    /// # struct Capture(RefCell<Vec<(String, Option<String>)>>);
+    /// # impl VLog for Capture {
+    /// #     fn enabled(&self, _: &Metadata) -> bool { true }
+    /// #     fn vlog(&self, record: &Record) {
+    /// #         self.0.borrow_mut().push((record.args().to_string(), record.tooltip().map(Into::into)))
+    /// #     }
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// # }
+    /// use v_log::point;
+    ///
+    /// let capture = Capture(RefCell::new(Vec::new()));
+    /// let residual = 0.25;
+    /// point!(vlogger: capture, "main_surface", [1.0, 2.0], 5.0, Base, "o", tip: &format!("residual: {}", residual), "p1");
+    /// point!(vlogger: capture, "main_surface", [1.0, 2.0], 5.0, Base, "o", "p2");
+    ///
+    /// let records = capture.0.borrow();
+    /// assert_eq!(records[0], ("p1".to_string(), Some("residual: 0.25".to_string())));
+    /// assert_eq!(records[1], ("p2".to_string(), None));
+    /// ```
+    #[inline]
+    pub fn tooltip(&self) -> Option<&'a str> {
+        self.tooltip
+    }
+
+    /// The draw order of the visual element on 2D surfaces.
+    ///
+    /// Visual elements with a higher `z_order` are drawn on top of the ones with a lower one,
+    /// and elements with the same `z_order` are drawn in the order they were sent. Set it with
+    /// the `z_order:` argument of the macros. The default is `0`, so a negative `z_order`
+    /// moves e.g. a background grid below everything else.
+    ///
+    /// This is independent of the z-coordinate of the visual, which always means depth.
+    /// 2D vloggers ignore the z-coordinate, 3D vloggers may ignore the `z_order`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use v_log::{Metadata, VLog};
+    /// # struct Capture(RefCell<Vec<i32>>);
+    /// # impl VLog for Capture {
+    /// #     fn enabled(&self, _: &Metadata) -> bool { true }
+    /// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.z_order()) }
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// # }
+    /// use v_log::{point, polyline, Record};
+    ///
+    /// assert_eq!(Record::builder().build().z_order(), 0);
+    /// assert_eq!(Record::builder().z_order(-1).build().z_order(), -1);
+    ///
+    /// let capture = Capture(RefCell::new(Vec::new()));
+    /// polyline!(vlogger: capture, "main_surface", ([0.0, 0.0], [1.0, 1.0]), 1.0, Base, z_order: -1);
+    /// point!(vlogger: capture, "main_surface", [0.5, 0.5, -3.0], 5.0, Error, z_order: 2);
+    /// point!(vlogger: capture, "main_surface", [0.5, 0.5], 5.0, Base);
+    /// assert_eq!(*capture.0.borrow(), [-1, 2, 0]);
+    /// ```
+    #[inline]
+    pub fn z_order(&self) -> i32 {
+        self.z_order
+    }
+
+    /// How long the visual element should stay visible, or `None` if it stays until it is cleared.
+    ///
+    /// Set it with the `ttl:` argument of the macros, e.g. to draw a velocity vector
+    /// in a live view which disappears on its own instead of requiring a [`clear`](VLog::clear).
+    /// The vlogger is responsible for removing the visual element once the duration has passed.
+    ///
+    /// The ttl is advisory. The clock starts when the vlogger receives the record, not when
+    /// it was created, so buffering adapters like [`Buffered`] delay the expiry. Vloggers which
+    /// don't redraw on their own, like file based ones, may ignore it.
+    ///
+    /// Requires the `std` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use v_log::{Metadata, VLog};
+    /// # struct Capture(RefCell<Vec<Option<Duration>>>);
+    /// # impl VLog for Capture {
+    /// #     fn enabled(&self, _: &Metadata) -> bool { true }
+    /// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.ttl()) }
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// # }
+    /// use std::time::Duration;
+    /// use v_log::{polyline, Record};
+    ///
+    /// assert_eq!(Record::builder().build().ttl(), None);
+    /// let record = Record::builder().ttl(Some(Duration::from_millis(500))).build();
+    /// assert_eq!(record.ttl(), Some(Duration::from_millis(500)));
+    ///
+    /// let capture = Capture(RefCell::new(Vec::new()));
+    /// let (pos, vel) = ([1.0, 2.0], [0.5, 0.0]);
+    /// polyline!(vlogger: capture, "main_surface", (pos, [pos[0] + vel[0], pos[1] + vel[1]]), 1.0, Base, "->", ttl: Duration::from_millis(200));
+    /// polyline!(vlogger: capture, "main_surface", (pos, [0.0, 0.0]), 1.0, Base);
+    /// assert_eq!(*capture.0.borrow(), [Some(Duration::from_millis(200)), None]);
+    /// ```
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn ttl(&self) -> Option<std::time::Duration> {
+        self.ttl
+    }
+
+    /// The structured key-value pairs of the directive, e.g. to show on hover.
+    ///
+    /// The macros set them with a trailing `; key = value, ...` section after
+    /// the message, see the [`kv`] module. Without it, this is empty.
+    ///
+    /// Requires the `kv` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// # use v_log::{Metadata, Record, VLog};
+    /// # struct Capture(RefCell<Vec<String>>);
+    /// # impl VLog for Capture {
+    /// #     fn enabled(&self, _: &Metadata) -> bool { true }
+    /// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(format!("{:?}", record.key_values())) }
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// # }
+    /// use v_log::kv::Value;
+    /// use v_log::point;
+    ///
+    /// let capture = Capture(RefCell::new(Vec::new()));
+    /// let name = String::from("wheel");
+    /// point!(vlogger: capture, "main_surface", [1.0, 2.0], 5.0, Base, "o", "p"; entity_id = 42, mass = 1.5, name = &name, asleep = false);
+    /// point!(vlogger: capture, "main_surface", [1.0, 2.0], 5.0, Base);
+    ///
+    /// let key_values = capture.0.borrow();
+    /// let expected = [
+    ///     ("entity_id", Value::I64(42)),
+    ///     ("mass", Value::F64(1.5)),
+    ///     ("name", Value::Str("wheel")),
+    ///     ("asleep", Value::Bool(false)),
+    /// ];
+    /// assert_eq!(key_values[0], format!("{:?}", expected));
+    /// assert_eq!(key_values[1], "[]");
+    /// ```
+    #[cfg(feature = "kv")]
+    #[inline]
+    pub fn key_values(&self) -> &'a [(&'a str, kv::Value<'a>)] {
+        self.key_values
+    }
+
+    /// The formatted message/label text.
+    ///
+    /// This formats the [`args`](Record::args), unless they are a plain string or
+    /// the record was created by [`with_rendered_message`](Record::with_rendered_message).
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    pub fn message(&self) -> alloc::borrow::Cow<'a, str> {
+        use alloc::borrow::Cow;
+        use alloc::string::ToString;
+
+        match self.message.or(self.args.as_str()) {
+            Some(message) => Cow::Borrowed(message),
+            None => Cow::Owned(self.args.to_string()),
+        }
+    }
+
+    /// Calls `f` with a copy of this record with the message formatted only once.
+    ///
+    /// Formatting the [`args`](Record::args) runs all the formatting code every time.
+    /// Vloggers which use the message multiple times, e.g. for filtering and drawing,
+    /// can use this, to make [`message`](Record::message) and formatting the `args`
+    /// of the copy a cheap copy of the cached string.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::cell::Cell;
+    /// use std::fmt;
+    /// use v_log::Record;
+    ///
+    /// struct Counted<'a>(&'a Cell<usize>);
+    ///
+    /// impl fmt::Display for Counted<'_> {
+    ///     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    ///         self.0.set(self.0.get() + 1);
+    ///         write!(f, "expensive")
+    ///     }
+    /// }
+    ///
+    /// fn vlog(record: &Record, count: &Cell<usize>) {
+    ///     let expected = format!("{}", record.args());
+    ///     assert_eq!(count.get(), 1);
+    ///     record.with_rendered_message(|record| {
+    ///         assert_eq!(record.message(), expected);
+    ///         assert_eq!(record.message(), expected);
+    ///         assert_eq!(record.args().to_string(), expected);
+    ///     });
+    ///     assert_eq!(count.get(), 2);
+    /// }
+    ///
+    /// let count = Cell::new(0);
+    /// vlog(&Record::builder().args(format_args!("{} text", Counted(&count))).build(), &count);
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn with_rendered_message<R>(&self, f: impl FnOnce(&Record) -> R) -> R {
+        use alloc::string::ToString;
+
+        if self.message.is_some() || self.args.as_str().is_some() {
+            return f(self);
+        }
+        let message = self.args.to_string();
+        f(&Record {
+            args: format_args!("{}", message),
+            message: Some(&message),
+            ..self.clone()
+        })
+    }
+
+    /// Compare two records with tolerances, e.g. for snapshot tests of geometry.
+    ///
+    /// The records are equal if the visuals are of the same kind and style, all
+    /// coordinates and the sizes differ by at most `coord_tol`, the channels of the
+    /// [resolved colors](Record::resolved_color) differ by at most `color_tol`, and the
+    /// [messages](Record::message) are equal. The metadata, source locations and ids
+    /// are not compared.
+    ///
+    /// Requires the `alloc` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{Color, PointStyle, Record, Visual};
+    ///
+    /// let point = |x| Visual::Point { x, y: 2.0, z: 0.0, style: PointStyle::Point };
+    /// let a = Record::builder().visual(point(1.0)).color(Color::Info).build();
+    /// let b = Record::builder().visual(point(1.0 + 1e-12)).color(Color::Hex(0x2297f3ff)).build();
+    /// let c = Record::builder().visual(point(2.0)).color(Color::Info).build();
+    /// assert!(a.approx_eq(&b, 1e-9, 1));
+    /// assert!(!a.approx_eq(&b, 1e-9, 0));
+    /// assert!(!a.approx_eq(&c, 1e-9, 1));
+    ///
+    /// let message = Record::builder().args(format_args!("{}", 1)).build();
+    /// assert!(!message.approx_eq(&Record::builder().build(), 1e-9, 0));
+    /// ```
+    #[cfg(feature = "alloc")]
+    pub fn approx_eq(&self, other: &Record, coord_tol: f64, color_tol: u8) -> bool {
+        // `f64::abs` is not available in `core`.
+        let close = |a: f64, b: f64| a == b || (if a < b { b - a } else { a - b }) <= coord_tol;
+        let all_close = |a: &[f64], b: &[f64]| a.iter().zip(b).all(|(&a, &b)| close(a, b));
+        let visual = match (&self.visual, &other.visual) {
+            (Visual::Message, Visual::Message) => true,
+            (
+                Visual::Label {
+                    x,
+                    y,
+                    z,
+                    alignment,
+                    rotation,
+                    max_width,
+                    style,
+                },
+                Visual::Label {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                    alignment: alignment2,
+                    rotation: rotation2,
+                    max_width: max_width2,
+                    style: style2,
+                },
+            ) => {
+                *alignment as u8 == *alignment2 as u8
+                    && *style as u8 == *style2 as u8
+                    && all_close(&[*x, *y, *z, *rotation], &[*x2, *y2, *z2, *rotation2])
+                    && match (max_width, max_width2) {
+                        (Some(a), Some(b)) => close(*a, *b),
+                        (a, b) => a.is_none() && b.is_none(),
+                    }
+            }
+            (
+                Visual::Point { x, y, z, style },
+                Visual::Point {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                    style: style2,
+                },
+            ) => *style as usize == *style2 as usize && all_close(&[*x, *y, *z], &[*x2, *y2, *z2]),
+            (
+                Visual::Line {
+                    x1,
+                    y1,
+                    z1,
+                    x2,
+                    y2,
+                    z2,
+                    style,
+                    world_thickness,
+                },
+                Visual::Line {
+                    x1: u1,
+                    y1: v1,
+                    z1: w1,
+                    x2: u2,
+                    y2: v2,
+                    z2: w2,
+                    style: style2,
+                    world_thickness: world_thickness2,
+                },
+            ) => {
+                *style as usize == *style2 as usize
+                    && all_close(
+                        &[*x1, *y1, *z1, *x2, *y2, *z2],
+                        &[*u1, *v1, *w1, *u2, *v2, *w2],
+                    )
+                    && match (world_thickness, world_thickness2) {
+                        (Some(a), Some(b)) => close(*a, *b),
+                        (a, b) => a.is_none() && b.is_none(),
+                    }
+            }
+            (
+                Visual::Arc {
+                    cx,
+                    cy,
+                    cz,
+                    radius,
+                    start_angle,
+                    end_angle,
+                    style,
+                    sector,
+                },
+                Visual::Arc {
+                    cx: cx2,
+                    cy: cy2,
+                    cz: cz2,
+                    radius: radius2,
+                    start_angle: start_angle2,
+                    end_angle: end_angle2,
+                    style: style2,
+                    sector: sector2,
+                },
+            ) => {
+                *style as usize == *style2 as usize
+                    && sector == sector2
+                    && all_close(
+                        &[*cx, *cy, *cz, *radius, *start_angle, *end_angle],
+                        &[*cx2, *cy2, *cz2, *radius2, *start_angle2, *end_angle2],
+                    )
+            }
+            (
+                Visual::Aabb { min, max, style },
+                Visual::Aabb {
+                    min: min2,
+                    max: max2,
+                    style: style2,
+                },
+            ) => {
+                *style as usize == *style2 as usize && all_close(min, min2) && all_close(max, max2)
+            }
+            (
+                Visual::Obb {
+                    center,
+                    half_extents,
+                    rotation,
+                },
+                Visual::Obb {
+                    center: center2,
+                    half_extents: half_extents2,
+                    rotation: rotation2,
+                },
+            ) => {
+                all_close(center, center2)
+                    && all_close(half_extents, half_extents2)
+                    && all_close(rotation, rotation2)
+            }
+            (
+                Visual::Ray {
+                    origin,
+                    direction,
+                    style,
+                },
+                Visual::Ray {
+                    origin: origin2,
+                    direction: direction2,
+                    style: style2,
+                },
+            ) => {
+                *style as usize == *style2 as usize
+                    && all_close(origin, origin2)
+                    && all_close(direction, direction2)
+            }
+            (
+                Visual::Axes {
+                    origin,
+                    rotation,
+                    length,
+                },
+                Visual::Axes {
+                    origin: origin2,
+                    rotation: rotation2,
+                    length: length2,
+                },
+            ) => {
+                close(*length, *length2)
+                    && all_close(origin, origin2)
+                    && all_close(rotation, rotation2)
+            }
+            (
+                Visual::Ellipse {
+                    cx,
+                    cy,
+                    cz,
+                    rx,
+                    ry,
+                    rotation,
+                    filled,
+                },
+                Visual::Ellipse {
+                    cx: cx2,
+                    cy: cy2,
+                    cz: cz2,
+                    rx: rx2,
+                    ry: ry2,
+                    rotation: rotation2,
+                    filled: filled2,
+                },
+            ) => {
+                filled == filled2
+                    && all_close(
+                        &[*cx, *cy, *cz, *rx, *ry, *rotation],
+                        &[*cx2, *cy2, *cz2, *rx2, *ry2, *rotation2],
+                    )
+            }
+            (
+                Visual::Bezier {
+                    p0,
+                    p1,
+                    p2,
+                    p3,
+                    style,
+                },
+                Visual::Bezier {
+                    p0: q0,
+                    p1: q1,
+                    p2: q2,
+                    p3: q3,
+                    style: style2,
+                },
+            ) => {
+                *style as usize == *style2 as usize
+                    && [(p0, q0), (p1, q1), (p2, q2), (p3, q3)]
+                        .iter()
+                        .all(|(a, b)| all_close(*a, *b))
+            }
+            (
+                Visual::Grid {
+                    spacing,
+                    extent,
+                    plane,
+                },
+                Visual::Grid {
+                    spacing: spacing2,
+                    extent: extent2,
+                    plane: plane2,
+                },
+            ) => {
+                *plane as u8 == *plane2 as u8
+                    && all_close(&[*spacing, *extent], &[*spacing2, *extent2])
+            }
+            (
+                Visual::Spline {
+                    points,
+                    tension,
+                    closed,
+                    style,
+                },
+                Visual::Spline {
+                    points: points2,
+                    tension: tension2,
+                    closed: closed2,
+                    style: style2,
+                },
+            ) => {
+                *style as usize == *style2 as usize
+                    && closed == closed2
+                    && close(*tension, *tension2)
+                    && points.len() == points2.len()
+                    && points.iter().zip(points2).all(|(a, b)| all_close(a, b))
+            }
+            (
+                Visual::Image {
+                    x,
+                    y,
+                    z,
+                    width,
+                    height,
+                    format,
+                    data,
+                },
+                Visual::Image {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                    width: width2,
+                    height: height2,
+                    format: format2,
+                    data: data2,
+                },
+            ) => {
+                (width, height) == (width2, height2)
+                    && *format as u8 == *format2 as u8
+                    && data == data2
+                    && all_close(&[*x, *y, *z], &[*x2, *y2, *z2])
+            }
+            (
+                Visual::PointCloud { points, style },
+                Visual::PointCloud {
+                    points: points2,
+                    style: style2,
+                },
+            ) => {
+                *style as usize == *style2 as usize
+                    && points.len() == points2.len()
+                    && points.iter().zip(points2).all(|(a, b)| all_close(a, b))
+            }
+            (
+                Visual::Mesh {
+                    vertices,
+                    indices,
+                    shaded,
+                },
+                Visual::Mesh {
+                    vertices: vertices2,
+                    indices: indices2,
+                    shaded: shaded2,
+                },
+            ) => {
+                shaded == shaded2
+                    && indices == indices2
+                    && vertices.len() == vertices2.len()
+                    && vertices.iter().zip(vertices2).all(|(a, b)| all_close(a, b))
+            }
+            (
+                Visual::VectorField {
+                    origins,
+                    vectors,
+                    scale,
+                },
+                Visual::VectorField {
+                    origins: origins2,
+                    vectors: vectors2,
+                    scale: scale2,
+                },
+            ) => {
+                close(*scale, *scale2)
+                    && origins.len() == origins2.len()
+                    && vectors.len() == vectors2.len()
+                    && origins.iter().zip(origins2).all(|(a, b)| all_close(a, b))
+                    && vectors.iter().zip(vectors2).all(|(a, b)| all_close(a, b))
+            }
+            (
+                Visual::Polygon {
+                    points,
+                    filled,
+                    style,
+                },
+                Visual::Polygon {
+                    points: points2,
+                    filled: filled2,
+                    style: style2,
+                },
+            ) => {
+                *style as usize == *style2 as usize
+                    && filled == filled2
+                    && points.len() == points2.len()
+                    && points.iter().zip(points2).all(|(a, b)| all_close(a, b))
+            }
+            _ => false,
+        };
+        let color = self
+            .resolved_color()
+            .iter()
+            .zip(other.resolved_color())
+            .all(|(a, b)| a.abs_diff(b) <= color_tol);
+        visual && color && close(self.size, other.size) && self.message() == other.message()
+    }
+}
+
+/// A single line summary of the record for debugging, e.g. for printing to a terminal.
+///
+/// The format is not stable and may change in future versions.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{Color, PointStyle, Record, Visual};
+///
+/// let record = Record::builder()
+///     .args(format_args!("start"))
+///     .surface("main_surface")
+///     .visual(Visual::Point { x: 1.0, y: 2.0, z: 0.0, style: PointStyle::Point })
+///     .size(5.0)
+///     .color(Color::Healthy)
+///     .build();
+/// assert_eq!(
+///     record.to_string(),
+///     "main_surface: Point at (1, 2, 0), style Point, size 5, color Healthy: start"
+/// );
+/// ```
+impl fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: ", self.surface())?;
+        match &self.visual {
+            Visual::Message => return write!(f, "{}", self.args),
+            Visual::Label { x, y, z, .. } => write!(f, "Label at ({}, {}, {})", x, y, z)?,
+            Visual::Point { x, y, z, style } => {
+                write!(f, "Point at ({}, {}, {}), style {:?}", x, y, z, style)?
+            }
+            Visual::Line {
+                x1,
+                y1,
+                z1,
+                x2,
+                y2,
+                z2,
+                style,
+                ..
+            } => write!(
+                f,
+                "Line from ({}, {}, {}) to ({}, {}, {}), style {:?}",
+                x1, y1, z1, x2, y2, z2, style
+            )?,
+            Visual::Ray {
+                origin,
+                direction,
+                style,
+            } => write!(
+                f,
+                "Ray from ({}, {}, {}) along ({}, {}, {}), style {:?}",
+                origin[0], origin[1], origin[2], direction[0], direction[1], direction[2], style
+            )?,
+            Visual::Arc {
+                cx,
+                cy,
+                cz,
+                radius,
+                start_angle,
+                end_angle,
+                style,
+                sector,
+            } => write!(
+                f,
+                "{} around ({}, {}, {}), radius {}, angles {} to {}, style {:?}",
+                if *sector { "Sector" } else { "Arc" },
+                cx,
+                cy,
+                cz,
+                radius,
+                start_angle,
+                end_angle,
+                style
+            )?,
+            Visual::Aabb { min, max, style } => write!(
+                f,
+                "Box from ({}, {}, {}) to ({}, {}, {}), style {:?}",
+                min[0], min[1], min[2], max[0], max[1], max[2], style
+            )?,
+            Visual::Obb {
+                center,
+                half_extents,
+                ..
+            } => write!(
+                f,
+                "Oriented box at ({}, {}, {}), half extents ({}, {}, {})",
+                center[0], center[1], center[2], half_extents[0], half_extents[1], half_extents[2]
+            )?,
+            Visual::Axes { origin, length, .. } => write!(
+                f,
+                "Axes at ({}, {}, {}), length {}",
+                origin[0], origin[1], origin[2], length
+            )?,
+            Visual::Ellipse {
+                cx,
+                cy,
+                cz,
+                rx,
+                ry,
+                rotation,
+                filled,
+            } => write!(
+                f,
+                "{} at ({}, {}, {}), radii {} and {}, rotation {}",
+                if *filled { "Filled ellipse" } else { "Ellipse" },
+                cx,
+                cy,
+                cz,
+                rx,
+                ry,
+                rotation
+            )?,
+            Visual::Bezier { p0, p3, style, .. } => write!(
+                f,
+                "Bezier from ({}, {}, {}) to ({}, {}, {}), style {:?}",
+                p0[0], p0[1], p0[2], p3[0], p3[1], p3[2], style
+            )?,
+            Visual::Grid {
+                spacing,
+                extent,
+                plane,
+            } => write!(
+                f,
+                "Grid in the {:?} plane, spacing {}, extent {}",
+                plane, spacing, extent
+            )?,
+            #[cfg(feature = "alloc")]
+            Visual::Spline {
+                points,
+                closed,
+                style,
+                ..
+            } => write!(
+                f,
+                "{} through {} points, style {:?}",
+                if *closed { "Closed spline" } else { "Spline" },
+                points.len(),
+                style
+            )?,
+            #[cfg(feature = "alloc")]
+            Visual::Image {
+                x,
+                y,
+                z,
+                width,
+                height,
+                format,
+                ..
+            } => write!(
+                f,
+                "Image at ({}, {}, {}), {}x{} {:?}",
+                x, y, z, width, height, format
+            )?,
+            #[cfg(feature = "alloc")]
+            Visual::PointCloud { points, style } => write!(
+                f,
+                "Point cloud of {} points, style {:?}",
+                points.len(),
+                style
+            )?,
+            #[cfg(feature = "alloc")]
+            Visual::Mesh {
+                vertices,
+                indices,
+                shaded,
+            } => write!(
+                f,
+                "{} with {} vertices and {} triangles",
+                if *shaded { "Shaded mesh" } else { "Mesh" },
+                vertices.len(),
+                indices.len() / 3
+            )?,
+            #[cfg(feature = "alloc")]
+            Visual::VectorField { origins, scale, .. } => write!(
+                f,
+                "Vector field with {} arrows, scale {}",
+                origins.len(),
+                scale
+            )?,
+            #[cfg(feature = "alloc")]
+            Visual::Polygon {
+                points,
+                filled,
+                style,
+            } => write!(
+                f,
+                "{} with {} points, style {:?}",
+                if *filled { "Filled polygon" } else { "Polygon" },
+                points.len(),
+                style
+            )?,
+        }
+        write!(f, ", size {}, color {:?}", self.size, self.color)?;
+        // avoid formatting the arguments twice to check if they are empty.
+        match self.args.as_str() {
+            Some("") => Ok(()),
+            _ => write!(f, ": {}", self.args),
+        }
+    }
 }
 
 /// Builder for [`Record`](struct.Record.html).
@@ -353,12 +1562,18 @@ impl<'a> RecordBuilder<'a> {
     ///
     /// - `visual`: [`Visual::Message`]
     /// - `color`: [`Color::Base`]
+    /// - `opacity`: `1.0`
     /// - `size`: `12.0`
     /// - `args`: [`format_args!("")`]
     /// - `metadata`: [`Metadata::builder().build()`]
     /// - `module_path`: `None`
     /// - `file`: `None`
     /// - `line`: `None`
+    /// - `call_site_id`: `0`
+    /// - `path_id`: `0`
+    /// - `tooltip`: `None`
+    /// - `z_order`: `0`
+    /// - `ttl`: `None`
     ///
     /// [`format_args!("")`]: https://doc.rust-lang.org/std/macro.format_args.html
     /// [`Metadata::builder().build()`]: struct.MetadataBuilder.html#method.build
@@ -368,12 +1583,23 @@ impl<'a> RecordBuilder<'a> {
             record: Record {
                 visual: Visual::Message,
                 color: Color::Base,
+                opacity: 1.0,
                 size: 12.0,
                 args: format_args!(""),
                 metadata: Metadata::builder().build(),
                 module_path: None,
                 file: None,
                 line: None,
+                call_site_id: 0,
+                path_id: 0,
+                tooltip: None,
+                z_order: 0,
+                #[cfg(feature = "std")]
+                ttl: None,
+                #[cfg(feature = "kv")]
+                key_values: &[],
+                #[cfg(feature = "alloc")]
+                message: None,
             },
         }
     }
@@ -390,6 +1616,12 @@ impl<'a> RecordBuilder<'a> {
         self
     }
 
+    /// Set [`opacity`](struct.Record.html#method.opacity).
+    pub fn opacity(&mut self, opacity: f32) -> &mut RecordBuilder<'a> {
+        self.record.opacity = opacity;
+        self
+    }
+
     /// Set [`size`](struct.Record.html#method.size).
     pub fn size(&mut self, size: f64) -> &mut RecordBuilder<'a> {
         self.record.size = size;
@@ -459,6 +1691,53 @@ impl<'a> RecordBuilder<'a> {
         self
     }
 
+    /// Set [`call_site_id`](struct.Record.html#method.call_site_id)
+    #[inline]
+    pub fn call_site_id(&mut self, id: u64) -> &mut RecordBuilder<'a> {
+        self.record.call_site_id = id;
+        self
+    }
+
+    /// Set [`path_id`](struct.Record.html#method.path_id)
+    #[inline]
+    pub fn path_id(&mut self, id: u64) -> &mut RecordBuilder<'a> {
+        self.record.path_id = id;
+        self
+    }
+
+    /// Set [`tooltip`](struct.Record.html#method.tooltip)
+    #[inline]
+    pub fn tooltip(&mut self, tooltip: Option<&'a str>) -> &mut RecordBuilder<'a> {
+        self.record.tooltip = tooltip;
+        self
+    }
+
+    /// Set [`z_order`](struct.Record.html#method.z_order)
+    #[inline]
+    pub fn z_order(&mut self, z_order: i32) -> &mut RecordBuilder<'a> {
+        self.record.z_order = z_order;
+        self
+    }
+
+    /// Set [`ttl`](struct.Record.html#method.ttl)
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn ttl(&mut self, ttl: Option<std::time::Duration>) -> &mut RecordBuilder<'a> {
+        self.record.ttl = ttl;
+        self
+    }
+
+    /// Set [`key_values`](struct.Record.html#method.key_values)
+    #[cfg(feature = "kv")]
+    #[inline]
+    pub fn key_values(
+        &mut self,
+        key_values: &'a [(&'a str, kv::Value<'a>)],
+    ) -> &mut RecordBuilder<'a> {
+        self.record.key_values = key_values;
+        self
+    }
+
     /// Invoke the builder and return a `Record`
     #[inline]
     pub fn build(&self) -> Record<'a> {
@@ -484,6 +1763,7 @@ impl Default for RecordBuilder<'_> {
 ///
 /// Users should use the `vlog_enabled!` macro in their code to avoid
 /// constructing expensive vlog messages.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Eq, PartialEq, Ord, PartialOrd, Hash, Debug)]
 pub struct Metadata<'a> {
     surface: &'a str,
@@ -504,6 +1784,34 @@ impl<'a> Metadata<'a> {
     }
 
     /// The name of the target of the directive.
+    ///
+    /// Like for the records, the macros for surfaces like [`clear!`] and
+    /// [`vlog_enabled!`] default to the module path of the call site.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// use v_log::{begin_frame, vlog_enabled, Metadata, Record, VLog};
+    ///
+    /// struct Targets(RefCell<Vec<String>>);
+    ///
+    /// impl VLog for Targets {
+    ///     fn enabled(&self, metadata: &Metadata) -> bool {
+    ///         self.0.borrow_mut().push(metadata.target().to_string());
+    ///         true
+    ///     }
+    ///     fn vlog(&self, _: &Record) {}
+    ///     fn clear(&self, _: &str) {}
+    ///     fn flush(&self) {}
+    /// }
+    ///
+    /// let vlogger = Targets(RefCell::new(Vec::new()));
+    /// vlog_enabled!(vlogger: vlogger, "main_surface");
+    /// begin_frame!(vlogger: vlogger, "main_surface");
+    /// vlog_enabled!(vlogger: vlogger, target: "physics", "main_surface");
+    /// assert_eq!(*vlogger.0.borrow(), [module_path!(), module_path!(), "physics"]);
+    /// ```
     #[inline]
     pub fn target(&self) -> &'a str {
         self.target
@@ -584,6 +1892,7 @@ impl Default for MetadataBuilder<'_> {
 ///    ellipses/hyperbolas in a perspective projection. The outlined cube is preferrably drawn as a wireframe cube.
 /// 2. Point billboard marker where the size is determined in screen coordinates instead of the same space as the position coordinates.
 ///    Zooming in the view will not change their apparent size. These are useful to mark points.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug)]
 #[non_exhaustive]
 pub enum PointStyle {
@@ -629,9 +1938,67 @@ pub enum PointStyle {
     /// A diamond outline. Dynamically scaled so the size is the pixel size.
     /// Shorthand: `"-d"`
     PointDiamondOutline,
+
+    /// Nothing is drawn. The macros skip the point entirely, so vloggers never
+    /// receive this style. This allows switching draws off with a computed style.
+    None,
+}
+
+impl PointStyle {
+    /// All variants in declaration order.
+    ///
+    /// As the enum is `#[non_exhaustive]`, a `match` in another crate needs a catch-all
+    /// arm, which silently swallows new variants. Vloggers can iterate over `ALL` in a
+    /// test to make sure their match handles every variant explicitly.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::PointStyle;
+    ///
+    /// fn draw(style: PointStyle) -> Option<&'static str> {
+    ///     Some(match style {
+    ///         PointStyle::FilledCircle | PointStyle::Point => "filled circle",
+    ///         PointStyle::Circle | PointStyle::PointOutline => "circle",
+    ///         PointStyle::DashedCircle => "dashed circle",
+    ///         PointStyle::FilledSquare | PointStyle::PointSquare => "filled square",
+    ///         PointStyle::Square | PointStyle::PointSquareOutline => "square",
+    ///         PointStyle::DashedSquare => "dashed square",
+    ///         PointStyle::PointCross => "cross",
+    ///         PointStyle::PointDiamond => "filled diamond",
+    ///         PointStyle::PointDiamondOutline => "diamond",
+    ///         PointStyle::None => "nothing",
+    ///         _ => return None,
+    ///     })
+    /// }
+    ///
+    /// assert!(PointStyle::ALL.iter().all(|style| draw(*style).is_some()));
+    /// // every variant is listed exactly once, in declaration order.
+    /// for (i, style) in PointStyle::ALL.iter().enumerate() {
+    ///     assert_eq!(*style as usize, i);
+    /// }
+    /// assert_eq!(PointStyle::ALL.len(), PointStyle::None as usize + 1);
+    /// ```
+    pub const ALL: &'static [PointStyle] = &[
+        PointStyle::FilledCircle,
+        PointStyle::Circle,
+        PointStyle::DashedCircle,
+        PointStyle::FilledSquare,
+        PointStyle::Square,
+        PointStyle::DashedSquare,
+        PointStyle::Point,
+        PointStyle::PointOutline,
+        PointStyle::PointSquare,
+        PointStyle::PointSquareOutline,
+        PointStyle::PointCross,
+        PointStyle::PointDiamond,
+        PointStyle::PointDiamondOutline,
+        PointStyle::None,
+    ];
 }
 
 /// The style of a line type visual.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug)]
 #[non_exhaustive]
 pub enum LineStyle {
@@ -651,10 +2018,38 @@ pub enum LineStyle {
     /// If a polygon is drawn in CW point order, the harpoon will be on the inside.
     /// Shorthand: `"<_"`
     InsideHarpoonCW,
+    /// Nothing is drawn. The macros skip the line entirely, so vloggers only
+    /// receive this style as the outline of a filled [`Visual::Polygon`], which
+    /// then only draws the fill. This allows switching draws off with a computed style.
+    None,
+}
+
+impl LineStyle {
+    /// All variants in declaration order, see [`PointStyle::ALL`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::LineStyle;
+    ///
+    /// for (i, style) in LineStyle::ALL.iter().enumerate() {
+    ///     assert_eq!(*style as usize, i);
+    /// }
+    /// assert_eq!(LineStyle::ALL.len(), LineStyle::None as usize + 1);
+    /// ```
+    pub const ALL: &'static [LineStyle] = &[
+        LineStyle::Simple,
+        LineStyle::Dashed,
+        LineStyle::Arrow,
+        LineStyle::InsideHarpoonCCW,
+        LineStyle::InsideHarpoonCW,
+        LineStyle::None,
+    ];
 }
 
 /// The text alignment relative to a specified spacepoint.
 /// All variants center the text vertically.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Default)]
 #[repr(u8)]
 pub enum TextAlignment {
@@ -674,12 +2069,134 @@ pub enum TextAlignment {
     Flexible = 3,
 }
 
-/// A visual element to be drawn by the vlogger.
-#[derive(Clone, Debug, Default)]
-pub enum Visual {
-    /// Just a vlog message to be shown in the vlogger instead of the regular vlogs.
+/// How a [`Visual::Label`] with a [`max_width`](Visual::Label::max_width) is wrapped.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub enum LabelStyle {
+    /// Break lines between words. Words longer than the width are broken anywhere.
     #[default]
-    Message,
+    WordWrap,
+    /// Break lines at any character.
+    CharWrap,
+    /// Cut off the lines at the width instead of breaking them.
+    Clip,
+}
+
+impl LabelStyle {
+    /// Splits the text into lines of at most `max_chars` characters.
+    ///
+    /// Embedded `'\n'` always break lines, `None` doesn't wrap.
+    #[cfg(feature = "std")]
+    pub(crate) fn wrap(&self, text: &str, max_chars: Option<usize>) -> Vec<String> {
+        let mut lines = Vec::new();
+        for line in text.split('\n') {
+            let Some(n) = max_chars.map(|n| n.max(1)) else {
+                lines.push(line.to_string());
+                continue;
+            };
+            let chars: Vec<char> = line.chars().collect();
+            match self {
+                LabelStyle::Clip => lines.push(chars.iter().take(n).collect()),
+                LabelStyle::CharWrap => {
+                    lines.extend(chars.chunks(n).map(|chunk| chunk.iter().collect()));
+                    if chars.is_empty() {
+                        lines.push(String::new());
+                    }
+                }
+                LabelStyle::WordWrap => {
+                    let mut current = String::new();
+                    let mut len = 0;
+                    for word in line.split_whitespace() {
+                        let word: Vec<char> = word.chars().collect();
+                        if len > 0 && len + 1 + word.len() <= n {
+                            current.push(' ');
+                            len += 1;
+                        } else if len > 0 {
+                            lines.push(core::mem::take(&mut current));
+                            len = 0;
+                        }
+                        for chunk in word.chunks(n) {
+                            if len + chunk.len() > n {
+                                lines.push(core::mem::take(&mut current));
+                                len = 0;
+                            }
+                            current.extend(chunk);
+                            len += chunk.len();
+                        }
+                    }
+                    lines.push(current);
+                }
+            }
+        }
+        lines
+    }
+}
+
+/// The pixel format of a [`Visual::Image`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
+pub enum ImageFormat {
+    /// 4 bytes per pixel: red, green, blue and alpha.
+    Rgba8,
+    /// 1 byte per pixel: the brightness.
+    Gray8,
+}
+
+impl ImageFormat {
+    /// The number of bytes of a single pixel.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::ImageFormat;
+    ///
+    /// assert_eq!(ImageFormat::Rgba8.bytes_per_pixel(), 4);
+    /// assert_eq!(ImageFormat::Gray8.bytes_per_pixel(), 1);
+    /// ```
+    pub const fn bytes_per_pixel(&self) -> usize {
+        match self {
+            ImageFormat::Rgba8 => 4,
+            ImageFormat::Gray8 => 1,
+        }
+    }
+}
+
+/// The plane of a [`Visual::Grid`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, Default)]
+#[non_exhaustive]
+pub enum GridPlane {
+    /// The plane spanned by the x- and y-axis.
+    #[default]
+    XY,
+    /// The plane spanned by the x- and z-axis.
+    XZ,
+    /// The plane spanned by the y- and z-axis.
+    YZ,
+}
+
+impl GridPlane {
+    /// The indices of the coordinates spanning the plane.
+    #[cfg(feature = "std")]
+    pub(crate) const fn axes(&self) -> (usize, usize) {
+        match self {
+            GridPlane::XY => (0, 1),
+            GridPlane::XZ => (0, 2),
+            GridPlane::YZ => (1, 2),
+        }
+    }
+}
+
+/// A visual element to be drawn by the vlogger.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Debug, Default)]
+#[non_exhaustive]
+pub enum Visual {
+    /// Just a vlog message to be shown in the vlogger instead of the regular vlogs.
+    #[default]
+    Message,
     /// A text label placed in space with the message string.
     Label {
         /// The spacepoint x-coordinate
@@ -690,6 +2207,15 @@ pub enum Visual {
         z: f64,
         /// The alignment of the text relative to the spacepoint.
         alignment: TextAlignment,
+        /// The counterclockwise rotation of the text in radians in the surface plane.
+        /// `0.0` is horizontal. Vloggers which can't rotate text may ignore this.
+        rotation: f64,
+        /// The maximum width of the lines in world units, like the position.
+        /// Longer lines are wrapped according to `style`. `None` means no wrapping.
+        /// Embedded `'\n'` in the text always start a new line.
+        max_width: Option<f64>,
+        /// How lines longer than `max_width` are wrapped.
+        style: LabelStyle,
     },
     /// A circle/point placed in space.
     Point {
@@ -718,10 +2244,786 @@ pub enum Visual {
         z2: f64,
         /// The drawing style of the line.
         style: LineStyle,
+        /// The line thickness in world units. If set, this takes precedence over
+        /// [`Record::size`], which is usually interpreted in pixels.
+        world_thickness: Option<f64>,
+    },
+    /// A ray, i.e. a half-line from an origin along a direction, e.g. for raycasting.
+    ///
+    /// Unlike [`Visual::Line`], which has two finite endpoints, the ray has no end.
+    /// The vlogger clips it to the visible bounds of the surface.
+    /// Arrowheads of the style would be at infinity and are therefore not drawn.
+    Ray {
+        /// The spacepoint the ray starts at.
+        origin: [f64; 3],
+        /// The direction of the ray. Never zero, and with the `std` feature of unit length.
+        direction: [f64; 3],
+        /// The drawing style of the ray.
+        style: LineStyle,
+    },
+    /// A circular arc placed in space, optionally closed into a pie sector.
+    ///
+    /// The arc lies in the plane with the z-coordinate of its center. The angles are
+    /// in radians, counterclockwise from the x-axis, and the arc runs from the start
+    /// to the end angle, so a larger end angle draws a counterclockwise arc.
+    /// Arrow styles put the arrow head at the end angle.
+    Arc {
+        /// The x-coordinate of the center
+        cx: f64,
+        /// The y-coordinate of the center
+        cy: f64,
+        /// The z-coordinate of the center for 3D visualisations.
+        cz: f64,
+        /// The radius in world units.
+        radius: f64,
+        /// The angle of the start of the arc in radians.
+        start_angle: f64,
+        /// The angle of the end of the arc in radians.
+        end_angle: f64,
+        /// The drawing style of the arc.
+        style: LineStyle,
+        /// Whether the ends of the arc are connected to the center, forming a pie sector.
+        sector: bool,
+    },
+    /// An axis aligned box, drawn as its 12 edges.
+    ///
+    /// 2D vloggers ignore the z-coordinates and draw a rectangle. The style controls
+    /// the edges, e.g. [`LineStyle::Simple`] for solid and [`LineStyle::Dashed`] for
+    /// dashed edges.
+    Aabb {
+        /// The corner with the smallest coordinates.
+        min: [f64; 3],
+        /// The corner with the largest coordinates.
+        max: [f64; 3],
+        /// The drawing style of the edges.
+        style: LineStyle,
+    },
+    /// An oriented box, e.g. for collision shapes.
+    ///
+    /// 3D vloggers draw the box as a wireframe of its 12 edges. 2D vloggers draw the
+    /// edges projected onto the xy-plane, which is a rectangle if the box is only
+    /// rotated around the z-axis.
+    Obb {
+        /// The center of the box.
+        center: [f64; 3],
+        /// The half of the size of the box along its local axes.
+        half_extents: [f64; 3],
+        /// The rotation from the local axes as a unit quaternion `[x, y, z, w]`.
+        rotation: [f64; 4],
+    },
+    /// A coordinate axes triad, e.g. to check the orientation of a transform.
+    ///
+    /// The vlogger draws three arrows from the origin along the rotated x-, y- and
+    /// z-axis, with [`Record::size`] as thickness. `length` scales all three axes equally.
+    /// The arrows are always colored [`Color::X`], [`Color::Y`] and [`Color::Z`],
+    /// so [`Record::color`] is not used. Vloggers without colors draw plain arrows.
+    Axes {
+        /// The spacepoint the arrows start at.
+        origin: [f64; 3],
+        /// The rotation of the axes as a unit quaternion `[x, y, z, w]`.
+        rotation: [f64; 4],
+        /// The length of each arrow in world units.
+        length: f64,
+    },
+    /// An ellipse placed in space, e.g. to show an uncertainty region.
+    ///
+    /// The ellipse lies in the plane with the z-coordinate of its center, also on
+    /// 3D surfaces. The radii are along the x- and y-axis before the rotation and
+    /// are never negative. The outline is drawn with [`Record::size`] as thickness.
+    Ellipse {
+        /// The x-coordinate of the center
+        cx: f64,
+        /// The y-coordinate of the center
+        cy: f64,
+        /// The z-coordinate of the center for 3D visualisations.
+        cz: f64,
+        /// The radius along the rotated x-axis in world units.
+        rx: f64,
+        /// The radius along the rotated y-axis in world units.
+        ry: f64,
+        /// The counterclockwise rotation in radians in the surface plane.
+        rotation: f64,
+        /// Whether the area of the ellipse is filled.
+        filled: bool,
+    },
+    /// A cubic Bézier curve from `p0` to `p3` with the control points `p1` and `p2`.
+    ///
+    /// Vloggers tessellate the curve themselves, e.g. into a polyline,
+    /// so callers don't have to send many line segments.
+    Bezier {
+        /// The start point.
+        p0: [f64; 3],
+        /// The first control point, which sets the direction at the start.
+        p1: [f64; 3],
+        /// The second control point, which sets the direction at the end.
+        p2: [f64; 3],
+        /// The end point.
+        p3: [f64; 3],
+        /// The drawing style of the curve.
+        style: LineStyle,
+    },
+    /// A reference grid through the origin, e.g. as a background of a surface.
+    ///
+    /// The vlogger draws lines every `spacing` units along both axes of the plane,
+    /// out to `±extent` from the origin. If `spacing <= 0.0`, nothing is drawn.
+    /// [`Record::color`] sets the color of the grid lines.
+    Grid {
+        /// The distance between neighbouring grid lines in world units.
+        spacing: f64,
+        /// How far the grid reaches from the origin along both axes in world units.
+        extent: f64,
+        /// The plane the grid lies in.
+        plane: GridPlane,
+    },
+    /// A smooth Catmull–Rom spline passing through all given spacepoints.
+    ///
+    /// The vlogger is responsible for the tessellation, including the tension.
+    /// Vloggers which can not draw splines should fall back to
+    /// drawing a polyline through the points.
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Spline {
+        /// The spacepoints to interpolate. Contains at least 2 points.
+        points: Vec<[f64; 3]>,
+        /// The tension of the cardinal spline. `0.0` is the classic Catmull–Rom spline,
+        /// `1.0` results in straight lines between the points.
+        tension: f64,
+        /// Whether the spline wraps from the last point back to the first point.
+        closed: bool,
+        /// The drawing style of the spline.
+        style: LineStyle,
+    },
+    /// A raster image placed in space, e.g. a debug texture.
+    ///
+    /// The rows of pixels are stored from top to bottom, and the position is the anchor
+    /// of the upper left corner. How large the image is drawn is up to the vlogger.
+    /// `width * height * format.bytes_per_pixel()` should equal `data.len()`, but
+    /// records with mismatched sizes can reach the vlogger, which may reject them in
+    /// [`VLog::enabled_record`].
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Image {
+        /// The spacepoint x-coordinate
+        x: f64,
+        /// The spacepoint y-coordinate
+        y: f64,
+        /// The spacepoint z-coordinate for 3D visualisations.
+        z: f64,
+        /// The number of pixels per row.
+        width: u32,
+        /// The number of rows.
+        height: u32,
+        /// The format of the pixels.
+        format: ImageFormat,
+        /// The bytes of the pixels, row by row.
+        data: Vec<u8>,
+    },
+    /// Many points sharing one style, e.g. a debug point cloud.
+    ///
+    /// The whole cloud is sent as a single record, which is much cheaper than
+    /// sending every point on its own. All points share the size and color of the
+    /// [`Record`], so clouds with different colors need one record per color.
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    PointCloud {
+        /// The spacepoints of the points.
+        points: Vec<[f64; 3]>,
+        /// The style of all points.
+        style: PointStyle,
+    },
+    /// An indexed triangle mesh, e.g. for debugging mesh processing.
+    ///
+    /// Every three indices form a triangle of vertices. If `shaded` is set, the
+    /// triangles are drawn filled with flat shading, otherwise as a wireframe of their
+    /// edges. [`Record::color`] tints the whole mesh.
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Mesh {
+        /// The spacepoints of the vertices.
+        vertices: Vec<[f64; 3]>,
+        /// The indices into `vertices`, three per triangle. All are valid indices.
+        indices: Vec<u32>,
+        /// Whether the triangles are drawn shaded or as a wireframe.
+        shaded: bool,
+    },
+    /// Many arrows at once, e.g. for debugging flow fields.
+    ///
+    /// Each arrow starts at an origin and ends at `origin + scale * vector`.
+    /// The arrowheads use the same sizing rule as [`LineStyle::Arrow`], so they
+    /// don't depend on the length of the vectors. Vloggers may render the arrows
+    /// instanced, which is much cheaper than sending them as separate lines.
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    VectorField {
+        /// The spacepoints the arrows start at.
+        origins: Vec<[f64; 3]>,
+        /// The vectors, one per origin.
+        vectors: Vec<[f64; 3]>,
+        /// The factor the vectors are multiplied with before drawing.
+        scale: f64,
+    },
+    /// A closed polygon through the given spacepoints, optionally filled.
+    ///
+    /// The fill uses [`Record::color`] with its alpha and the opacity of the record,
+    /// while the outline uses the same color at full alpha, so filled regions stay
+    /// visible through each other. The outline is drawn with the style, which may be
+    /// [`LineStyle::None`] to only draw the fill.
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Polygon {
+        /// The corners of the polygon. Contains at least 3 points.
+        points: Vec<[f64; 3]>,
+        /// Whether the area of the polygon is filled.
+        filled: bool,
+        /// The drawing style of the outline.
+        style: LineStyle,
     },
 }
 
+impl Visual {
+    /// Project the visual onto the xy-plane by setting all z-coordinates to `0.0`.
+    ///
+    /// This allows 2D vloggers to display 3D records.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{LineStyle, Visual};
+    ///
+    /// let line = Visual::Line {
+    ///     x1: 1.0, y1: 2.0, z1: 3.0,
+    ///     x2: 4.0, y2: 5.0, z2: 6.0,
+    ///     style: LineStyle::Simple,
+    ///     world_thickness: None,
+    /// };
+    /// assert!(matches!(
+    ///     line.project_2d(),
+    ///     Visual::Line { x1, z1, x2, z2, .. } if x1 == 1.0 && z1 == 0.0 && x2 == 4.0 && z2 == 0.0
+    /// ));
+    /// ```
+    pub fn project_2d(&self) -> Visual {
+        self.with_z_plane(0.0)
+    }
+
+    /// Move the visual into the plane with the given z-coordinate.
+    ///
+    /// This allows 3D vloggers to place 2D records on a layer plane.
+    /// Messages are returned unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{LineStyle, Visual};
+    ///
+    /// let line = Visual::Line {
+    ///     x1: 1.0, y1: 2.0, z1: 0.0,
+    ///     x2: 4.0, y2: 5.0, z2: 0.0,
+    ///     style: LineStyle::Simple,
+    ///     world_thickness: None,
+    /// };
+    /// assert!(matches!(
+    ///     line.with_z_plane(-2.0),
+    ///     Visual::Line { y1, z1, y2, z2, .. } if y1 == 2.0 && z1 == -2.0 && y2 == 5.0 && z2 == -2.0
+    /// ));
+    /// ```
+    pub fn with_z_plane(&self, z: f64) -> Visual {
+        let mut visual = self.clone();
+        match &mut visual {
+            // the grid always lies in a plane through the origin.
+            Visual::Message | Visual::Grid { .. } => {}
+            Visual::Label { z: z0, .. } | Visual::Point { z: z0, .. } => *z0 = z,
+            #[cfg(feature = "alloc")]
+            Visual::Image { z: z0, .. } => *z0 = z,
+            Visual::Line { z1, z2, .. } => {
+                *z1 = z;
+                *z2 = z;
+            }
+            Visual::Arc { cz, .. } | Visual::Ellipse { cz, .. } => *cz = z,
+            Visual::Aabb { min, max, .. } => {
+                min[2] = z;
+                max[2] = z;
+            }
+            Visual::Obb { center, .. } => center[2] = z,
+            Visual::Ray {
+                origin, direction, ..
+            } => {
+                origin[2] = z;
+                // the ray stays within the plane.
+                direction[2] = 0.0;
+            }
+            Visual::Axes { origin, .. } => origin[2] = z,
+            Visual::Bezier { p0, p1, p2, p3, .. } => {
+                for p in [p0, p1, p2, p3] {
+                    p[2] = z;
+                }
+            }
+            #[cfg(feature = "alloc")]
+            Visual::Spline { points, .. }
+            | Visual::Polygon { points, .. }
+            | Visual::PointCloud { points, .. }
+            | Visual::Mesh {
+                vertices: points, ..
+            } => {
+                for p in points {
+                    p[2] = z;
+                }
+            }
+            #[cfg(feature = "alloc")]
+            Visual::VectorField {
+                origins, vectors, ..
+            } => {
+                for p in origins {
+                    p[2] = z;
+                }
+                // the arrows stay within the plane.
+                for v in vectors {
+                    v[2] = 0.0;
+                }
+            }
+        }
+        visual
+    }
+
+    /// Multiply all coordinates of the visual by `factor`.
+    ///
+    /// This also scales [`world_thickness`](Visual::Line::world_thickness) of lines
+    /// and the sizes of arcs, boxes and ellipses, as they are in the same units. Messages are returned unchanged.
+    /// A negative factor mirrors the coordinates, while the sizes are scaled by its absolute value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{LineStyle, PointStyle, Visual};
+    ///
+    /// let point = Visual::Point { x: 1.0, y: 2.0, z: 4.0, style: PointStyle::Point };
+    /// assert_eq!(point.scale(0.5).position(), Some([0.5, 1.0, 2.0]));
+    ///
+    /// let arc = Visual::Arc {
+    ///     cx: 1.0, cy: 0.0, cz: 0.0, radius: 2.0,
+    ///     start_angle: 0.0, end_angle: 1.0, style: LineStyle::Simple, sector: false,
+    /// };
+    /// assert!(matches!(arc.scale(-2.0), Visual::Arc { cx, radius, .. } if cx == -2.0 && radius == 4.0));
+    /// ```
+    pub fn scale(&self, factor: f64) -> Visual {
+        // sizes stay non-negative, `f64::abs` is not available in `core`.
+        let size_factor = if factor < 0.0 { -factor } else { factor };
+        let mut visual = self.clone();
+        match &mut visual {
+            Visual::Message => {}
+            Visual::Grid {
+                spacing, extent, ..
+            } => {
+                *spacing *= size_factor;
+                *extent *= size_factor;
+            }
+            Visual::Label {
+                x, y, z, max_width, ..
+            } => {
+                *x *= factor;
+                *y *= factor;
+                *z *= factor;
+                if let Some(width) = max_width {
+                    *width *= size_factor;
+                }
+            }
+            Visual::Point { x, y, z, .. } => {
+                *x *= factor;
+                *y *= factor;
+                *z *= factor;
+            }
+            #[cfg(feature = "alloc")]
+            Visual::Image { x, y, z, .. } => {
+                *x *= factor;
+                *y *= factor;
+                *z *= factor;
+            }
+            Visual::Line {
+                x1,
+                y1,
+                z1,
+                x2,
+                y2,
+                z2,
+                world_thickness,
+                ..
+            } => {
+                for c in [x1, y1, z1, x2, y2, z2] {
+                    *c *= factor;
+                }
+                if let Some(thickness) = world_thickness {
+                    *thickness *= size_factor;
+                }
+            }
+            Visual::Arc {
+                cx, cy, cz, radius, ..
+            } => {
+                for c in [cx, cy, cz] {
+                    *c *= factor;
+                }
+                *radius *= size_factor;
+            }
+            Visual::Aabb { min, max, .. } => {
+                for c in [&mut *min, &mut *max].into_iter().flatten() {
+                    *c *= factor;
+                }
+                if factor < 0.0 {
+                    core::mem::swap(min, max);
+                }
+            }
+            Visual::Obb {
+                center,
+                half_extents,
+                ..
+            } => {
+                for c in center {
+                    *c *= factor;
+                }
+                for c in half_extents {
+                    *c *= size_factor;
+                }
+            }
+            Visual::Ray {
+                origin, direction, ..
+            } => {
+                for c in origin {
+                    *c *= factor;
+                }
+                // the direction keeps its length, but is mirrored for negative factors.
+                if factor < 0.0 {
+                    for c in direction {
+                        *c = -*c;
+                    }
+                }
+            }
+            Visual::Axes { origin, length, .. } => {
+                for c in origin {
+                    *c *= factor;
+                }
+                *length *= size_factor;
+            }
+            Visual::Ellipse {
+                cx, cy, cz, rx, ry, ..
+            } => {
+                for c in [cx, cy, cz] {
+                    *c *= factor;
+                }
+                *rx *= size_factor;
+                *ry *= size_factor;
+            }
+            Visual::Bezier { p0, p1, p2, p3, .. } => {
+                for c in [p0, p1, p2, p3].into_iter().flatten() {
+                    *c *= factor;
+                }
+            }
+            #[cfg(feature = "alloc")]
+            Visual::Spline { points, .. }
+            | Visual::Polygon { points, .. }
+            | Visual::PointCloud { points, .. }
+            | Visual::Mesh {
+                vertices: points, ..
+            } => {
+                for c in points.iter_mut().flatten() {
+                    *c *= factor;
+                }
+            }
+            #[cfg(feature = "alloc")]
+            Visual::VectorField {
+                origins, vectors, ..
+            } => {
+                for c in origins.iter_mut().chain(vectors).flatten() {
+                    *c *= factor;
+                }
+            }
+        }
+        visual
+    }
+
+    /// The spacepoint of a point or label.
+    ///
+    /// All other visuals, e.g. messages and lines, have no single position and return `None`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{PointStyle, Visual};
+    ///
+    /// let point = Visual::Point { x: 1.0, y: 2.0, z: 0.0, style: PointStyle::Point };
+    /// assert_eq!(point.position(), Some([1.0, 2.0, 0.0]));
+    /// assert_eq!(Visual::Message.position(), None);
+    /// ```
+    pub fn position(&self) -> Option<[f64; 3]> {
+        match *self {
+            Visual::Label { x, y, z, .. } | Visual::Point { x, y, z, .. } => Some([x, y, z]),
+            _ => None,
+        }
+    }
+
+    /// The kind of the visual without its data.
+    pub fn kind(&self) -> VisualKind {
+        match self {
+            Visual::Message => VisualKind::Message,
+            Visual::Label { .. } => VisualKind::Label,
+            Visual::Point { .. } => VisualKind::Point,
+            Visual::Line { .. } => VisualKind::Line,
+            Visual::Ray { .. } => VisualKind::Ray,
+            Visual::Arc { .. } => VisualKind::Arc,
+            Visual::Aabb { .. } => VisualKind::Aabb,
+            Visual::Obb { .. } => VisualKind::Obb,
+            Visual::Axes { .. } => VisualKind::Axes,
+            Visual::Ellipse { .. } => VisualKind::Ellipse,
+            Visual::Bezier { .. } => VisualKind::Bezier,
+            Visual::Grid { .. } => VisualKind::Grid,
+            #[cfg(feature = "alloc")]
+            Visual::Spline { .. } => VisualKind::Spline,
+            #[cfg(feature = "alloc")]
+            Visual::Image { .. } => VisualKind::Image,
+            #[cfg(feature = "alloc")]
+            Visual::PointCloud { .. } => VisualKind::PointCloud,
+            #[cfg(feature = "alloc")]
+            Visual::Mesh { .. } => VisualKind::Mesh,
+            #[cfg(feature = "alloc")]
+            Visual::VectorField { .. } => VisualKind::VectorField,
+            #[cfg(feature = "alloc")]
+            Visual::Polygon { .. } => VisualKind::Polygon,
+        }
+    }
+
+    /// The corners of an oriented box. Corner `i` is on the positive side of
+    /// local axis `k` if bit `k` of `i` is set, so edges connect corners differing in one bit.
+    #[cfg(feature = "std")]
+    pub(crate) fn obb_corners(&self) -> Option<[[f64; 3]; 8]> {
+        let Visual::Obb {
+            center,
+            half_extents: h,
+            rotation: [qx, qy, qz, qw],
+        } = *self
+        else {
+            return None;
+        };
+        Some(core::array::from_fn(|i| {
+            let v = [0, 1, 2].map(|k| if i >> k & 1 == 1 { h[k] } else { -h[k] });
+            let v = Self::rotate([qx, qy, qz, qw], v);
+            [0, 1, 2].map(|k| center[k] + v[k])
+        }))
+    }
+
+    /// The tips of the x-, y- and z-arrow of coordinate axes.
+    #[cfg(feature = "std")]
+    pub(crate) fn axes_tips(&self) -> Option<[[f64; 3]; 3]> {
+        let Visual::Axes {
+            origin,
+            rotation,
+            length,
+        } = *self
+        else {
+            return None;
+        };
+        Some(core::array::from_fn(|axis| {
+            let mut v = [0.0; 3];
+            v[axis] = length;
+            let v = Self::rotate(rotation, v);
+            [0, 1, 2].map(|k| origin[k] + v[k])
+        }))
+    }
+
+    /// Rotates `v` by the unit quaternion `[x, y, z, w]`.
+    #[cfg(feature = "std")]
+    fn rotate([qx, qy, qz, qw]: [f64; 4], v: [f64; 3]) -> [f64; 3] {
+        let cross = |a: [f64; 3], b: [f64; 3]| {
+            [
+                a[1] * b[2] - a[2] * b[1],
+                a[2] * b[0] - a[0] * b[2],
+                a[0] * b[1] - a[1] * b[0],
+            ]
+        };
+        let q = [qx, qy, qz];
+        // v + 2w (q × v) + 2 q × (q × v)
+        let t = cross(q, v);
+        let u = cross(q, t);
+        [0, 1, 2].map(|k| v[k] + 2.0 * (qw * t[k] + u[k]))
+    }
+
+    /// The points of a closed polyline approximating an ellipse.
+    #[cfg(feature = "std")]
+    pub(crate) fn ellipse_points(&self, segments: usize) -> Option<Vec<[f64; 3]>> {
+        let Visual::Ellipse {
+            cx,
+            cy,
+            cz,
+            rx,
+            ry,
+            rotation,
+            ..
+        } = *self
+        else {
+            return None;
+        };
+        let segments = segments.max(3);
+        let (sin, cos) = rotation.sin_cos();
+        let points = (0..=segments)
+            .map(|i| {
+                let angle = core::f64::consts::TAU * i as f64 / segments as f64;
+                let (x, y) = (rx * angle.cos(), ry * angle.sin());
+                [cx + x * cos - y * sin, cy + x * sin + y * cos, cz]
+            })
+            .collect();
+        Some(points)
+    }
+
+    /// The points of a polyline approximating a Bézier curve.
+    #[cfg(feature = "std")]
+    pub(crate) fn bezier_points(&self, segments: usize) -> Option<Vec<[f64; 3]>> {
+        let Visual::Bezier { p0, p1, p2, p3, .. } = *self else {
+            return None;
+        };
+        let segments = segments.max(1);
+        let points = (0..=segments)
+            .map(|i| {
+                let t = i as f64 / segments as f64;
+                let s = 1.0 - t;
+                let w = [s * s * s, 3.0 * s * s * t, 3.0 * s * t * t, t * t * t];
+                [0, 1, 2].map(|k| w[0] * p0[k] + w[1] * p1[k] + w[2] * p2[k] + w[3] * p3[k])
+            })
+            .collect();
+        Some(points)
+    }
+
+    /// The start and end points of the lines of a grid.
+    #[cfg(feature = "std")]
+    pub(crate) fn grid_lines(&self) -> Option<Vec<[[f64; 3]; 2]>> {
+        let Visual::Grid {
+            spacing,
+            extent,
+            plane,
+        } = *self
+        else {
+            return None;
+        };
+        if !(spacing > 0.0 && extent >= 0.0 && extent.is_finite()) {
+            return Some(Vec::new());
+        }
+        // a denser grid would be a solid area anyway.
+        let n = ((extent / spacing).floor() as i64).min(1000);
+        let (a, b) = plane.axes();
+        let mut lines = Vec::new();
+        for i in -n..=n {
+            let offset = i as f64 * spacing;
+            for (along, across) in [(a, b), (b, a)] {
+                let (mut start, mut end) = ([0.0; 3], [0.0; 3]);
+                start[across] = offset;
+                end[across] = offset;
+                start[along] = -extent;
+                end[along] = extent;
+                lines.push([start, end]);
+            }
+        }
+        Some(lines)
+    }
+
+    /// The points of a polyline approximating an arc, including the center for sectors.
+    #[cfg(feature = "std")]
+    pub(crate) fn arc_points(&self, segments: usize) -> Option<Vec<[f64; 3]>> {
+        let Visual::Arc {
+            cx,
+            cy,
+            cz,
+            radius,
+            start_angle,
+            end_angle,
+            sector,
+            ..
+        } = *self
+        else {
+            return None;
+        };
+        let segments = segments.max(1);
+        let mut points: Vec<_> = (0..=segments)
+            .map(|i| {
+                let angle = start_angle + (end_angle - start_angle) * i as f64 / segments as f64;
+                [cx + radius * angle.cos(), cy + radius * angle.sin(), cz]
+            })
+            .collect();
+        if sector {
+            points.insert(0, [cx, cy, cz]);
+            points.push([cx, cy, cz]);
+        }
+        Some(points)
+    }
+}
+
+/// The variants of [`Visual`] without their data, see [`Visual::kind`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[non_exhaustive]
+pub enum VisualKind {
+    /// A [`Visual::Message`].
+    Message,
+    /// A [`Visual::Label`].
+    Label,
+    /// A [`Visual::Point`].
+    Point,
+    /// A [`Visual::Line`].
+    Line,
+    /// A [`Visual::Ray`].
+    Ray,
+    /// A [`Visual::Arc`].
+    Arc,
+    /// A [`Visual::Aabb`].
+    Aabb,
+    /// A [`Visual::Obb`].
+    Obb,
+    /// A [`Visual::Axes`].
+    Axes,
+    /// A [`Visual::Ellipse`].
+    Ellipse,
+    /// A [`Visual::Bezier`].
+    Bezier,
+    /// A [`Visual::Grid`].
+    Grid,
+    /// A [`Visual::Spline`].
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Spline,
+    /// A [`Visual::Image`].
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Image,
+    /// A [`Visual::PointCloud`].
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    PointCloud,
+    /// A [`Visual::Mesh`].
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Mesh,
+    /// A [`Visual::VectorField`].
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    VectorField,
+    /// A [`Visual::Polygon`].
+    ///
+    /// Requires the `alloc` feature.
+    #[cfg(feature = "alloc")]
+    Polygon,
+}
+
 /// Basic debugging theme colors.
+///
+/// In the vlogging macros, colors are given as the name of a variant, e.g. `Info`,
+/// as a hexcode literal for [`Color::Hex`], e.g. `0xff8000ff`, as `[r, g, b]` or
+/// `[r, g, b, a]` for [`Color::Rgba`] or as any `Color` expression in parentheses,
+/// e.g. `(Color::series(i))`.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Clone, Copy, Debug, Default)]
 #[non_exhaustive]
 pub enum Color {
@@ -745,82 +3047,821 @@ pub enum Color {
     /// E.g. some shade of pink like the usual missing texture.
     Missing,
     /// A specific color by hexcode. The MSB is red, the LSB is alpha.
+    ///
+    /// The alpha is multiplied with the [`opacity`](Record::opacity) of the record.
     Hex(u32),
+    /// A specific color by floating point components in `[0, 1]`, see [`Color::rgba`].
+    ///
+    /// Components out of range are clamped when the color is resolved,
+    /// see [`to_rgba`](Color::to_rgba).
+    Rgba {
+        /// The red component.
+        r: f32,
+        /// The green component.
+        g: f32,
+        /// The blue component.
+        b: f32,
+        /// The alpha component, where `1.0` is opaque.
+        ///
+        /// It is multiplied with the [`opacity`](Record::opacity) of the record.
+        a: f32,
+    },
 }
 
-/// A trait encapsulating the operations required of a vlogger.
-pub trait VLog {
-    /// Determines if a vlog command with the specified metadata would be
-    /// vlogged.
+impl Color {
+    /// Resolve the color to RGBA bytes.
     ///
-    /// This is used by the `vlog_enabled!` macro to allow callers to avoid
-    /// expensive computation of vlog message arguments if the message would be
-    /// discarded anyway.
+    /// Vloggers are free to choose their own shades for the themed colors. This
+    /// returns the default shades, which are useful as a fallback and for comparisons:
     ///
-    /// # For implementors
+    /// | Color | Default shade |
+    /// |-------|---------------|
+    /// | [`Base`](Color::Base) | `#ffffff` |
+    /// | [`Healthy`](Color::Healthy) | `#4caf50` |
+    /// | [`Info`](Color::Info) | `#2196f3` |
+    /// | [`Warn`](Color::Warn) | `#ffc107` |
+    /// | [`Error`](Color::Error) | `#f44336` |
+    /// | [`X`](Color::X) | `#ff3352` |
+    /// | [`Y`](Color::Y) | `#8bdc00` |
+    /// | [`Z`](Color::Z) | `#2890ff` |
+    /// | [`Missing`](Color::Missing) | `#ff00ff` |
     ///
-    /// This method isn't called automatically by the vlogging macros.
-    /// It's up to an implementation of the `VLog` trait to call `enabled` in its own
-    /// `vlog` method implementation to guarantee that filtering is applied.
-    fn enabled(&self, metadata: &Metadata) -> bool;
-    /// Draw a point or line in 3D or 2D (ignoring z or using it as z-index).
+    /// All of them are fully opaque. The components of [`Color::Rgba`] are
+    /// clamped like in [`to_rgba`](Color::to_rgba) and rounded.
+    pub fn to_rgba8(&self) -> [u8; 4] {
+        let hex = match *self {
+            Color::Rgba { .. } => {
+                // `f32::round` is not available in `core`.
+                return self.to_rgba().map(|c| (c * 255.0 + 0.5) as u8);
+            }
+            Color::Base => 0xffffffff,
+            Color::Healthy => 0x4caf50ff,
+            Color::Info => 0x2196f3ff,
+            Color::Warn => 0xffc107ff,
+            Color::Error => 0xf44336ff,
+            Color::X => 0xff3352ff,
+            Color::Y => 0x8bdc00ff,
+            Color::Z => 0x2890ffff,
+            Color::Missing => 0xff00ffff,
+            Color::Hex(hex) => hex,
+        };
+        hex.to_be_bytes()
+    }
+
+    /// A color from floating point components in `[0, 1]`, see [`Color::Rgba`].
     ///
-    /// # For implementors
+    /// # Examples
     ///
-    /// Note that `enabled` is *not* necessarily called before this method.
-    /// Implementations of `vlog` should perform all necessary filtering
-    /// internally.
-    fn vlog(&self, record: &Record);
-    /// Clear a drawing surface e.g. to redraw its content.
+    /// ```
+    /// use v_log::{point, Color};
     ///
-    /// # For implementors
+    /// let t = 0.25;
+    /// let color = Color::rgba(t, 1.0 - t, 0.0, 1.0);
+    /// assert!(color.approx_eq(&Color::Hex(0x40bf00ff), 0));
+    /// point!("main_surface", [0.0, 0.0], 5.0, (color));
+    /// point!("main_surface", [1.0, 0.0], 5.0, [t, 1.0 - t, 0.0]);
+    /// ```
+    pub const fn rgba(r: f32, g: f32, b: f32, a: f32) -> Color {
+        Color::Rgba { r, g, b, a }
+    }
+
+    /// An opaque color from hue, saturation and lightness.
     ///
-    /// Note that `enabled` *is* called before this method.
-    fn clear(&self, surface: &str);
-    /// Flushes any buffered records.
+    /// The hue `h` is in degrees and wraps around modulo 360. The saturation `s`
+    /// and the lightness `l` are clamped to `[0, 1]`. Returns a [`Color::Rgba`].
     ///
-    /// # For implementors
+    /// # Examples
     ///
-    /// This method isn't called automatically by the vlogging macros.
-    /// It can be called manually on shut-down to ensure any in-flight records are flushed.
-    fn flush(&self);
-}
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// // zero saturation is gray.
+    /// assert_eq!(Color::from_hsl(123.0, 0.0, 0.5).to_rgba(), [0.5, 0.5, 0.5, 1.0]);
+    /// assert_eq!(Color::from_hsl(0.0, 1.0, 1.0).to_rgba(), [1.0; 4]);
+    /// // primary hues.
+    /// assert_eq!(Color::from_hsl(0.0, 1.0, 0.5).to_rgba(), [1.0, 0.0, 0.0, 1.0]);
+    /// assert_eq!(Color::from_hsl(120.0, 1.0, 0.5).to_rgba(), [0.0, 1.0, 0.0, 1.0]);
+    /// assert_eq!(Color::from_hsl(240.0, 1.0, 0.5).to_rgba(), [0.0, 0.0, 1.0, 1.0]);
+    /// // the hue wraps around.
+    /// assert_eq!(Color::from_hsl(480.0, 1.0, 0.5).to_rgba(), [0.0, 1.0, 0.0, 1.0]);
+    /// assert_eq!(Color::from_hsl(-120.0, 1.0, 0.5).to_rgba(), [0.0, 0.0, 1.0, 1.0]);
+    /// // out of range saturation and lightness are clamped.
+    /// assert_eq!(Color::from_hsl(0.0, 2.0, 0.5).to_rgba(), [1.0, 0.0, 0.0, 1.0]);
+    /// ```
+    pub fn from_hsl(h: f32, s: f32, l: f32) -> Color {
+        let (s, l) = (clamp01(s), clamp01(l));
+        let chroma = (1.0 - abs(2.0 * l - 1.0)) * s;
+        Color::from_hue(h, chroma, l - chroma * 0.5)
+    }
 
-/// A dummy initial value for VLOGGER.
-struct NopVLogger;
+    /// An opaque color from hue, saturation and value.
+    ///
+    /// The hue `h` is in degrees and wraps around modulo 360. The saturation `s`
+    /// and the value `v` are clamped to `[0, 1]`. Returns a [`Color::Rgba`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// // zero saturation is gray.
+    /// assert_eq!(Color::from_hsv(45.0, 0.0, 0.25).to_rgba(), [0.25, 0.25, 0.25, 1.0]);
+    /// // primary and secondary hues.
+    /// assert_eq!(Color::from_hsv(0.0, 1.0, 1.0).to_rgba(), [1.0, 0.0, 0.0, 1.0]);
+    /// assert_eq!(Color::from_hsv(60.0, 1.0, 1.0).to_rgba(), [1.0, 1.0, 0.0, 1.0]);
+    /// assert_eq!(Color::from_hsv(240.0, 1.0, 0.5).to_rgba(), [0.0, 0.0, 0.5, 1.0]);
+    /// // the hue wraps around.
+    /// assert_eq!(Color::from_hsv(360.0, 1.0, 1.0).to_rgba(), [1.0, 0.0, 0.0, 1.0]);
+    /// assert_eq!(Color::from_hsv(-300.0, 1.0, 1.0).to_rgba(), [1.0, 1.0, 0.0, 1.0]);
+    /// ```
+    pub fn from_hsv(h: f32, s: f32, v: f32) -> Color {
+        let (s, v) = (clamp01(s), clamp01(v));
+        let chroma = v * s;
+        Color::from_hue(h, chroma, v - chroma)
+    }
 
-impl VLog for NopVLogger {
-    fn enabled(&self, _: &Metadata) -> bool {
-        false
+    /// The opaque color of the hue `h` in degrees with the given chroma,
+    /// shifted by `m` in all components.
+    fn from_hue(h: f32, chroma: f32, m: f32) -> Color {
+        let mut h = h % 360.0;
+        if h < 0.0 {
+            h += 360.0;
+        }
+        let h = h / 60.0;
+        let x = chroma * (1.0 - abs(h % 2.0 - 1.0));
+        let (r, g, b) = match h as u32 {
+            0 => (chroma, x, 0.0),
+            1 => (x, chroma, 0.0),
+            2 => (0.0, chroma, x),
+            3 => (0.0, x, chroma),
+            4 => (x, 0.0, chroma),
+            _ => (chroma, 0.0, x),
+        };
+        Color::rgba(r + m, g + m, b + m, 1.0)
     }
 
-    fn vlog(&self, _: &Record) {}
-    fn clear(&self, _: &str) {}
-    fn flush(&self) {}
-}
+    /// One of `count` evenly spaced hues, to color `count` objects distinctly.
+    ///
+    /// The colors are fully saturated with a lightness of `0.5`, see [`from_hsl`](Color::from_hsl).
+    /// The index wraps around after `count` colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{point, Color};
+    ///
+    /// let points = [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]];
+    /// for (i, p) in points.iter().enumerate() {
+    ///     point!("main_surface", *p, 5.0, (Color::hue_wheel(i, points.len())));
+    /// }
+    /// assert_eq!(Color::hue_wheel(1, 3).to_rgba(), [0.0, 1.0, 0.0, 1.0]);
+    /// assert_eq!(Color::hue_wheel(3, 3).to_rgba(), Color::hue_wheel(0, 3).to_rgba());
+    /// ```
+    pub fn hue_wheel(index: usize, count: usize) -> Color {
+        let count = count.max(1);
+        Color::from_hsl((index % count) as f32 * 360.0 / count as f32, 1.0, 0.5)
+    }
 
-impl<T> VLog for &'_ T
-where
-    T: ?Sized + VLog,
-{
-    fn enabled(&self, metadata: &Metadata) -> bool {
-        (**self).enabled(metadata)
+    /// Resolve the color to floating point RGBA components in `[0, 1]`.
+    ///
+    /// The themed colors resolve to their default shades, see [`to_rgba8`](Color::to_rgba8).
+    /// The components of [`Color::Rgba`] are clamped to `[0, 1]`,
+    /// where `NaN` is treated as `0.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// assert_eq!(Color::Base.to_rgba(), [1.0; 4]);
+    /// assert_eq!(Color::Hex(0x00ff0000).to_rgba(), [0.0, 1.0, 0.0, 0.0]);
+    /// assert_eq!(Color::rgba(-0.5, 0.5, 2.0, f32::NAN).to_rgba(), [0.0, 0.5, 1.0, 0.0]);
+    /// ```
+    pub fn to_rgba(&self) -> [f32; 4] {
+        match *self {
+            Color::Rgba { r, g, b, a } => [r, g, b, a].map(clamp01),
+            _ => self.to_rgba8().map(|c| c as f32 / 255.0),
+        }
     }
 
-    fn vlog(&self, record: &Record) {
-        (**self).vlog(record);
+    /// Decode a color from its RGBA hexcode, the inverse of [`to_rgba8`](Color::to_rgba8).
+    ///
+    /// If the value matches the default shade of a themed color, that themed color is
+    /// returned, otherwise [`Color::Hex`]. This canonicalizes colors loaded from binary
+    /// data or configs back to the themed colors.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// let error = u32::from_be_bytes(Color::Error.to_rgba8());
+    /// assert!(matches!(Color::from_u32_named(error), Color::Error));
+    /// assert!(matches!(Color::from_u32_named(0x12345678), Color::Hex(0x12345678)));
+    /// // a different alpha doesn't match the themed color.
+    /// assert!(matches!(Color::from_u32_named(0xf4433680), Color::Hex(0xf4433680)));
+    /// ```
+    pub const fn from_u32_named(v: u32) -> Color {
+        match v {
+            0xffffffff => Color::Base,
+            0x4caf50ff => Color::Healthy,
+            0x2196f3ff => Color::Info,
+            0xffc107ff => Color::Warn,
+            0xf44336ff => Color::Error,
+            0xff3352ff => Color::X,
+            0x8bdc00ff => Color::Y,
+            0x2890ffff => Color::Z,
+            0xff00ffff => Color::Missing,
+            _ => Color::Hex(v),
+        }
     }
 
-    fn clear(&self, surface: &str) {
-        (**self).clear(surface);
+    /// A color of a categorical palette for the data series with the given index.
+    ///
+    /// The palette is the 10 color "Tableau 10" palette, which repeats after 10 series:
+    /// `#1f77b4` (blue), `#ff7f0e` (orange), `#2ca02c` (green), `#d62728` (red),
+    /// `#9467bd` (purple), `#8c564b` (brown), `#e377c2` (pink), `#7f7f7f` (gray),
+    /// `#bcbd22` (olive) and `#17becf` (cyan).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::{point, Color};
+    ///
+    /// for (i, series) in [[0.0, 1.0], [2.0, 3.0]].into_iter().enumerate() {
+    ///     point!("main_surface", series, 3.0, (Color::series(i)));
+    /// }
+    /// assert!(Color::series(0).approx_eq(&Color::series(10), 0));
+    /// assert!(!Color::series(0).approx_eq(&Color::series(1), 0));
+    /// ```
+    pub const fn series(index: usize) -> Color {
+        const PALETTE: [u32; 10] = [
+            0x1f77b4ff, 0xff7f0eff, 0x2ca02cff, 0xd62728ff, 0x9467bdff, 0x8c564bff, 0xe377c2ff,
+            0x7f7f7fff, 0xbcbd22ff, 0x17becfff,
+        ];
+        Color::Hex(PALETTE[index % PALETTE.len()])
     }
 
-    fn flush(&self) {
-        (**self).flush();
+    /// Compare the resolved RGBA bytes (see [`to_rgba8`](Color::to_rgba8))
+    /// of two colors with a tolerance per channel.
+    ///
+    /// This makes colors comparable regardless of how they were specified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// assert!(Color::Error.approx_eq(&Color::Hex(0xf44336ff), 0));
+    /// assert!(Color::Error.approx_eq(&Color::Hex(0xf24538ff), 2));
+    /// assert!(!Color::Error.approx_eq(&Color::Info, 2));
+    /// ```
+    pub fn approx_eq(&self, other: &Color, tol: u8) -> bool {
+        let (a, b) = (self.to_rgba8(), other.to_rgba8());
+        a.iter().zip(b.iter()).all(|(a, b)| a.abs_diff(*b) <= tol)
     }
-}
 
-#[cfg(feature = "std")]
+    /// Linearly interpolate between the resolved RGBA components
+    /// (see [`to_rgba`](Color::to_rgba)) of two colors.
+    ///
+    /// `t` is clamped to `[0, 1]`, where `0` results in `self` and `1` in `other`.
+    /// Themed colors are pinned to their default shades before blending, as listed
+    /// in [`to_rgba8`](Color::to_rgba8). The result is always a [`Color::Rgba`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// let gray = Color::Hex(0x000000ff).lerp(&Color::Hex(0xffffffff), 0.5);
+    /// assert_eq!(gray.to_rgba(), [0.5, 0.5, 0.5, 1.0]);
+    /// assert!(Color::Info.lerp(&Color::Error, 0.0).approx_eq(&Color::Info, 0));
+    /// assert!(Color::Info.lerp(&Color::Error, 1.0).approx_eq(&Color::Error, 0));
+    /// // `t` is clamped.
+    /// assert!(Color::Info.lerp(&Color::Error, 2.0).approx_eq(&Color::Error, 0));
+    /// ```
+    pub fn lerp(&self, other: &Color, t: f64) -> Color {
+        let (a, b) = (self.to_rgba(), other.to_rgba());
+        let t = t.clamp(0.0, 1.0) as f32;
+        let c = |i: usize| a[i] + (b[i] - a[i]) * t;
+        Color::rgba(c(0), c(1), c(2), c(3))
+    }
+
+    /// The color with its alpha replaced by `a`, which is clamped to `[0, 1]`.
+    ///
+    /// Returns a [`Color::Rgba`], see [`to_rgba`](Color::to_rgba).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// assert_eq!(Color::Base.with_alpha(0.25).to_rgba(), [1.0, 1.0, 1.0, 0.25]);
+    /// assert_eq!(Color::Base.with_alpha(-1.0).to_rgba(), [1.0, 1.0, 1.0, 0.0]);
+    /// assert_eq!(Color::Hex(0xff000080).with_alpha(2.0).to_rgba(), [1.0, 0.0, 0.0, 1.0]);
+    /// ```
+    pub fn with_alpha(&self, a: f32) -> Color {
+        let [r, g, b, _] = self.to_rgba();
+        Color::rgba(r, g, b, clamp01(a))
+    }
+
+    /// The color with its alpha multiplied by `mul`, e.g. to fade it out.
+    ///
+    /// The resulting alpha is clamped to `[0, 1]`.
+    /// Returns a [`Color::Rgba`], see [`to_rgba`](Color::to_rgba).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::Color;
+    ///
+    /// assert_eq!(Color::Base.opacity(0.5).to_rgba(), [1.0, 1.0, 1.0, 0.5]);
+    /// assert_eq!(Color::Base.with_alpha(0.5).opacity(0.5).to_rgba(), [1.0, 1.0, 1.0, 0.25]);
+    /// assert_eq!(Color::Base.with_alpha(0.5).opacity(4.0).to_rgba(), [1.0; 4]);
+    /// assert_eq!(Color::Base.opacity(-1.0).to_rgba(), [1.0, 1.0, 1.0, 0.0]);
+    /// ```
+    pub fn opacity(&self, mul: f32) -> Color {
+        let [r, g, b, a] = self.to_rgba();
+        Color::rgba(r, g, b, clamp01(a * mul))
+    }
+}
+
+/// Clamp to `[0, 1]`, where `NaN` becomes `0.0`.
+fn clamp01(c: f32) -> f32 {
+    if c > 1.0 {
+        1.0
+    } else if c > 0.0 {
+        c
+    } else {
+        0.0
+    }
+}
+
+/// `f32::abs` is not available in `core`.
+fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+/// How draws to a surface combine with the drawings already on it.
+///
+/// Set per surface with [`set_draw_mode`].
+///
+/// The mode is independent of explicit frames from [`begin_frame!`] and [`end_frame!`].
+/// Those only make the draws between them appear at once, in either mode. In
+/// [`Replace`](DrawMode::Replace) mode they also delimit the frames, so the surface
+/// is cleared at the first draw after `begin_frame`, and the old drawings stay
+/// visible until `end_frame`, like for a [`clear!`] inside the frame.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DrawMode {
+    /// Draws accumulate on the surface until it is cleared, e.g. for trails.
+    /// A frame from [`begin_frame!`] adds its draws to the surface without clearing it.
+    #[default]
+    Append,
+    /// The vlogger clears the surface before the first draw of every frame,
+    /// so only the drawings of the latest frame are shown.
+    /// Between [`begin_frame!`] and [`end_frame!`], the frame is the one they delimit.
+    /// Without them, what constitutes a frame is up to the vlogger, e.g. the time
+    /// between two [`flush`](VLog::flush) calls.
+    Replace,
+}
+
+/// A camera pose for a 3D surface.
+///
+/// Set per surface with [`set_view!`]. The view is advisory, the vlogger may
+/// ignore it, e.g. for 2D surfaces or when the user moved the camera.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct View {
+    /// The position of the camera.
+    pub eye: [f64; 3],
+    /// The point the camera looks at.
+    pub target: [f64; 3],
+    /// The up direction of the camera.
+    pub up: [f64; 3],
+    /// The vertical field of view in radians.
+    /// `fov <= 0.0` selects an orthographic projection.
+    pub fov: f64,
+}
+
+/// A trait encapsulating the operations required of a vlogger.
+pub trait VLog {
+    /// Determines if a vlog command with the specified metadata would be
+    /// vlogged.
+    ///
+    /// This is used by the `vlog_enabled!` macro to allow callers to avoid
+    /// expensive computation of vlog message arguments if the message would be
+    /// discarded anyway.
+    ///
+    /// The vlogging macros call this before building a record, so a disabled
+    /// target or surface skips the record entirely.
+    ///
+    /// # For implementors
+    ///
+    /// Records may also be sent to [`vlog`](VLog::vlog) directly, without the macros.
+    /// It's up to an implementation of the `VLog` trait to call `enabled` in its own
+    /// `vlog` method implementation to guarantee that filtering is applied.
+    fn enabled(&self, metadata: &Metadata) -> bool;
+    /// Determines if a complete record would be vlogged.
+    ///
+    /// The vlogging macros call this after [`enabled`](VLog::enabled) passed and the record
+    /// has been built, and only call [`vlog`](VLog::vlog) if it returns `true`. This allows filtering based on the content
+    /// of the record, e.g. the message, without overriding `vlog`. Note that at this point
+    /// the cost of building the record has already been paid, so cheap filtering
+    /// should be done in [`enabled`](VLog::enabled) instead.
+    ///
+    /// # For implementors
+    ///
+    /// The macros only ask this after `enabled` returned `true` for the metadata of the
+    /// record, so an override doesn't need to repeat that check. The default implementation
+    /// returns `self.enabled(record.metadata())`, so records sent directly are filtered too.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use std::cell::RefCell;
+    /// use v_log::{message, Metadata, Record, VLog};
+    ///
+    /// struct SkippingVLogger(RefCell<Vec<String>>);
+    ///
+    /// impl VLog for SkippingVLogger {
+    ///     fn enabled(&self, _: &Metadata) -> bool {
+    ///         true
+    ///     }
+    ///     fn enabled_record(&self, record: &Record) -> bool {
+    ///         !record.args().to_string().contains("skip")
+    ///     }
+    ///     fn vlog(&self, record: &Record) {
+    ///         self.0.borrow_mut().push(record.args().to_string());
+    ///     }
+    ///     // ...
+    /// #     fn clear(&self, _: &str) {}
+    /// #     fn flush(&self) {}
+    /// }
+    ///
+    /// let vlogger = SkippingVLogger(RefCell::new(Vec::new()));
+    /// message!(vlogger: vlogger, "main_surface", "keep {}", 1);
+    /// message!(vlogger: vlogger, "main_surface", "skip {}", 2);
+    /// message!(vlogger: vlogger, "main_surface", "keep {}", 3);
+    /// assert_eq!(*vlogger.0.borrow(), ["keep 1", "keep 3"]);
+    /// ```
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.enabled(record.metadata())
+    }
+    /// Draw a point or line in 3D or 2D (ignoring z, see [`Record::z_order`] for the draw order).
+    ///
+    /// # For implementors
+    ///
+    /// Note that `enabled` is *not* necessarily called before this method.
+    /// The vlogging macros only call it if [`enabled`](VLog::enabled) and
+    /// [`enabled_record`](VLog::enabled_record) return `true`, but records may
+    /// also be sent directly.
+    /// Implementations of `vlog` should perform all necessary filtering
+    /// internally.
+    fn vlog(&self, record: &Record);
+    /// Clear a drawing surface e.g. to redraw its content.
+    ///
+    /// # For implementors
+    ///
+    /// Note that `enabled` is *not* called before this method.
+    /// Clears reach the vlogger even if the target or surface is disabled,
+    /// so a vlogger which filters its records doesn't keep stale drawings.
+    fn clear(&self, surface: &str);
+    /// Remove a drawing surface entirely, see [`remove_surface!`].
+    ///
+    /// In contrast to [`clear`](VLog::clear), which empties a surface but keeps it
+    /// e.g. as an empty window or figure, this removes the surface itself.
+    /// Drawing to the surface afterwards creates it anew.
+    ///
+    /// # For implementors
+    ///
+    /// Vloggers which manage windows or figures should close the one of the surface
+    /// and release its resources. The default implementation calls
+    /// [`clear`](VLog::clear), which is enough for vloggers without such resources.
+    ///
+    /// Note that `enabled` is *not* called before this method.
+    fn remove_surface(&self, surface: &str) {
+        self.clear(surface);
+    }
+    /// Clear a surface and redraw it with the draws of `f`, see [`transaction!`].
+    ///
+    /// `f` is called with the vlogger to draw to.
+    ///
+    /// # For implementors
+    ///
+    /// The default implementation calls [`clear`](VLog::clear) and then `f` with `self`.
+    /// Vloggers which redraw continuously should override this to buffer the draws
+    /// of `f` and swap them in atomically, to avoid flicker and lock round-trips.
+    ///
+    /// Note that `enabled` *is* called before this method.
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        self.clear(surface);
+        f(&self);
+    }
+    /// Begin a frame on a surface, see [`begin_frame!`].
+    ///
+    /// Everything drawn to the surface until the matching [`end_frame`](VLog::end_frame)
+    /// forms one atomic frame.
+    ///
+    /// # For implementors
+    ///
+    /// The vlogger may hold back the draws of the frame and display them only on
+    /// `end_frame`, to avoid flicker. A [`clear`](VLog::clear) of the surface inside
+    /// the frame belongs to the frame as well, i.e. the old content should stay visible
+    /// until `end_frame` and then be replaced by the new draws.
+    /// The default implementation does nothing, so draws are displayed immediately.
+    ///
+    /// Note that `enabled` *is* called before this method.
+    fn begin_frame(&self, surface: &str) {
+        let _ = surface;
+    }
+    /// End a frame on a surface, see [`end_frame!`].
+    ///
+    /// # For implementors
+    ///
+    /// This is where a vlogger which buffers frames displays the draws and clears
+    /// since the matching [`begin_frame`](VLog::begin_frame).
+    /// A call without a matching `begin_frame` should be ignored.
+    /// The default implementation does nothing.
+    ///
+    /// Note that `enabled` *is* called before this method.
+    fn end_frame(&self, surface: &str) {
+        let _ = surface;
+    }
+    /// Push a group onto the group stack, see [`group!`].
+    ///
+    /// Groups tag the records drawn until the matching [`pop_group`](VLog::pop_group),
+    /// e.g. so a UI can toggle them. Nested groups form a path like
+    /// `["physics", "broadphase"]`. Groups are orthogonal to surfaces: a group may
+    /// span several surfaces and a surface may contain several groups.
+    ///
+    /// # For implementors
+    ///
+    /// Records drawn while groups are pushed belong to the path of all pushed groups.
+    /// The default implementation does nothing.
+    fn push_group(&self, name: &str) {
+        let _ = name;
+    }
+    /// Pop the innermost group pushed with [`push_group`](VLog::push_group).
+    ///
+    /// # For implementors
+    ///
+    /// A call without a pushed group should be ignored.
+    /// The default implementation does nothing.
+    fn pop_group(&self) {}
+    /// Flushes any buffered records.
+    ///
+    /// # For implementors
+    ///
+    /// This method isn't called automatically by the vlogging macros.
+    /// It can be called manually on shut-down to ensure any in-flight records are flushed.
+    fn flush(&self);
+    /// Called by [`shutdown`] before the program exits.
+    ///
+    /// # For implementors
+    ///
+    /// The global vlogger is never dropped, so this is the place to flush files,
+    /// join background threads and release other resources.
+    /// The vlogger may still receive records afterwards, which it is free to ignore.
+    /// The default implementation does nothing.
+    fn on_shutdown(&self) {}
+    /// Vlog a batch of owned records at once.
+    ///
+    /// This is used e.g. by [`LocalBatch`] to submit buffered records.
+    /// Requires the `alloc` feature.
+    ///
+    /// # For implementors
+    ///
+    /// The default implementation calls [`vlog`](VLog::vlog) for every record.
+    /// Vloggers which take a lock per record should override this to take it only once.
+    #[cfg(feature = "alloc")]
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        for record in records {
+            record.with_record(|record| self.vlog(record));
+        }
+    }
+    /// Set the [`DrawMode`] of a surface.
+    ///
+    /// # For implementors
+    ///
+    /// The default implementation does nothing, which corresponds to [`DrawMode::Append`]
+    /// for all surfaces. In [`DrawMode::Replace`] the vlogger should clear the surface
+    /// before the first draw of each frame, where a frame started by
+    /// [`begin_frame`](VLog::begin_frame) ends at the matching [`end_frame`](VLog::end_frame).
+    fn set_draw_mode(&self, surface: &str, mode: DrawMode) {
+        let _ = (surface, mode);
+    }
+    /// Set the camera [`View`] of a surface, see [`set_view!`].
+    ///
+    /// # For implementors
+    ///
+    /// The view is advisory. 3D vloggers should move their camera to it,
+    /// using an orthographic projection if `view.fov <= 0.0`.
+    /// 2D vloggers may ignore it. The default implementation does nothing.
+    ///
+    /// Note that `enabled` *is* called before this method.
+    fn set_view(&self, surface: &str, view: &View) {
+        let _ = (surface, view);
+    }
+    /// Save the current content of a surface to a file, see [`snapshot!`].
+    ///
+    /// # For implementors
+    ///
+    /// The file format is implementation-defined and should be chosen from the
+    /// extension of `path`, e.g. PNG for `.png` or SVG for `.svg`. Return
+    /// [`VLogError::UnsupportedFormat`] for unknown extensions.
+    /// The default implementation returns [`VLogError::Unsupported`].
+    ///
+    /// Note that `enabled` *is* called before this method.
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        let _ = (surface, path);
+        Err(VLogError::Unsupported)
+    }
+    /// Snap a color to the colors supported by the vlogger.
+    ///
+    /// This allows code in front of the vlogger, e.g. middleware, to
+    /// pre-quantize colors consistently with the output of the vlogger.
+    ///
+    /// # For implementors
+    ///
+    /// The default implementation returns the color unchanged. Vloggers
+    /// with a limited palette, e.g. terminal vloggers, should map
+    /// arbitrary [`Color::Hex`] colors to their closest supported color.
+    fn quantize_color(&self, color: Color) -> Color {
+        color
+    }
+    /// The maximum number of records to keep for a surface.
+    ///
+    /// This is an advisory for vloggers which buffer records. The facade can't
+    /// evict records from a vlogger, so it is up to the vlogger to drop the oldest
+    /// records of a surface when this limit is exceeded.
+    ///
+    /// # For implementors
+    ///
+    /// The default implementation returns the cap set with [`set_surface_record_cap`].
+    /// Buffering vloggers opt in by calling this method when storing records.
+    /// Requires the `std` feature, otherwise there is no limit by default.
+    fn max_records(&self, surface: &str) -> Option<usize> {
+        #[cfg(feature = "std")]
+        return surface_record_cap(surface);
+        #[cfg(not(feature = "std"))]
+        {
+            let _ = surface;
+            None
+        }
+    }
+    /// The vlogger as [`Any`](core::any::Any), to reach the concrete vlogger
+    /// with [`downcast_ref`](trait.VLog.html#method.downcast_ref).
+    ///
+    /// # For implementors
+    ///
+    /// The default implementation returns `None`, so vloggers are not downcastable
+    /// unless they opt in by returning `Some(self)`. This requires the vlogger to be `'static`.
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        None
+    }
+}
+
+impl dyn VLog {
+    /// Returns the concrete vlogger if it is of type `T`, e.g. to reach the vlogger
+    /// installed with [`set_vlogger`] through [`vlogger()`].
+    ///
+    /// Only vloggers which opt in by implementing [`as_any`](VLog::as_any) can be
+    /// downcast, for all others this returns `None`. References and smart pointers
+    /// forward to the vlogger they point to, while adapters like [`FlattenVLogger`]
+    /// are only downcastable to themselves, if at all.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::sync::atomic::{AtomicUsize, Ordering};
+    /// use v_log::{point, Metadata, Record, VLog};
+    ///
+    /// struct CountingVLogger(AtomicUsize);
+    ///
+    /// impl VLog for CountingVLogger {
+    ///     fn enabled(&self, _: &Metadata) -> bool { true }
+    ///     fn vlog(&self, _: &Record) {
+    ///         self.0.fetch_add(1, Ordering::SeqCst);
+    ///     }
+    ///     fn clear(&self, _: &str) {}
+    ///     fn flush(&self) {}
+    ///     fn as_any(&self) -> Option<&dyn std::any::Any> {
+    ///         Some(self)
+    ///     }
+    /// }
+    ///
+    /// static VLOGGER: CountingVLogger = CountingVLogger(AtomicUsize::new(0));
+    /// v_log::set_vlogger(&VLOGGER).unwrap();
+    ///
+    /// point!("main_surface", [0.0, 0.0], 5.0, Base);
+    /// let counting = v_log::vlogger().downcast_ref::<CountingVLogger>().unwrap();
+    /// assert_eq!(counting.0.load(Ordering::SeqCst), 1);
+    /// ```
+    pub fn downcast_ref<T: VLog + 'static>(&self) -> Option<&T> {
+        self.as_any()?.downcast_ref()
+    }
+}
+
+/// A dummy initial value for VLOGGER.
+struct NopVLogger;
+
+impl VLog for NopVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        false
+    }
+
+    fn vlog(&self, _: &Record) {}
+    fn clear(&self, _: &str) {}
+    fn flush(&self) {}
+}
+
+impl<T> VLog for &'_ T
+where
+    T: ?Sized + VLog,
+{
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        (**self).enabled(metadata)
+    }
+
+    fn vlog(&self, record: &Record) {
+        (**self).vlog(record);
+    }
+
+    fn clear(&self, surface: &str) {
+        (**self).clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        (**self).remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        (**self).transaction(surface, f);
+    }
+
+    fn begin_frame(&self, surface: &str) {
+        (**self).begin_frame(surface);
+    }
+
+    fn end_frame(&self, surface: &str) {
+        (**self).end_frame(surface);
+    }
+
+    fn push_group(&self, name: &str) {
+        (**self).push_group(name);
+    }
+
+    fn pop_group(&self) {
+        (**self).pop_group();
+    }
+
+    fn flush(&self) {
+        (**self).flush();
+    }
+
+    fn set_draw_mode(&self, surface: &str, mode: DrawMode) {
+        (**self).set_draw_mode(surface, mode);
+    }
+
+    fn set_view(&self, surface: &str, view: &View) {
+        (**self).set_view(surface, view);
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        (**self).snapshot(surface, path)
+    }
+
+    fn quantize_color(&self, color: Color) -> Color {
+        (**self).quantize_color(color)
+    }
+
+    fn max_records(&self, surface: &str) -> Option<usize> {
+        (**self).max_records(surface)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        (**self).enabled_record(record)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        (**self).vlog_batch(records);
+    }
+
+    fn on_shutdown(&self) {
+        (**self).on_shutdown();
+    }
+
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        (**self).as_any()
+    }
+}
+
+#[cfg(feature = "std")]
 impl<T> VLog for std::boxed::Box<T>
 where
     T: ?Sized + VLog,
@@ -829,38 +3870,280 @@ where
         self.as_ref().enabled(metadata)
     }
 
-    fn vlog(&self, record: &Record) {
-        self.as_ref().vlog(record);
+    fn vlog(&self, record: &Record) {
+        self.as_ref().vlog(record);
+    }
+
+    fn clear(&self, surface: &str) {
+        self.as_ref().clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        self.as_ref().remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        self.as_ref().transaction(surface, f);
+    }
+
+    fn begin_frame(&self, surface: &str) {
+        self.as_ref().begin_frame(surface);
+    }
+
+    fn end_frame(&self, surface: &str) {
+        self.as_ref().end_frame(surface);
+    }
+
+    fn push_group(&self, name: &str) {
+        self.as_ref().push_group(name);
+    }
+
+    fn pop_group(&self) {
+        self.as_ref().pop_group();
+    }
+
+    fn flush(&self) {
+        self.as_ref().flush();
+    }
+
+    fn set_draw_mode(&self, surface: &str, mode: DrawMode) {
+        self.as_ref().set_draw_mode(surface, mode);
+    }
+
+    fn set_view(&self, surface: &str, view: &View) {
+        self.as_ref().set_view(surface, view);
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        self.as_ref().snapshot(surface, path)
+    }
+
+    fn quantize_color(&self, color: Color) -> Color {
+        self.as_ref().quantize_color(color)
+    }
+
+    fn max_records(&self, surface: &str) -> Option<usize> {
+        self.as_ref().max_records(surface)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.as_ref().enabled_record(record)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        self.as_ref().vlog_batch(records);
+    }
+
+    fn on_shutdown(&self) {
+        self.as_ref().on_shutdown();
+    }
+
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        self.as_ref().as_any()
+    }
+}
+
+#[cfg(feature = "std")]
+impl<T> VLog for std::sync::Arc<T>
+where
+    T: ?Sized + VLog,
+{
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.as_ref().enabled(metadata)
+    }
+
+    fn vlog(&self, record: &Record) {
+        self.as_ref().vlog(record);
+    }
+
+    fn clear(&self, surface: &str) {
+        self.as_ref().clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        self.as_ref().remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        self.as_ref().transaction(surface, f);
+    }
+
+    fn begin_frame(&self, surface: &str) {
+        self.as_ref().begin_frame(surface);
+    }
+
+    fn end_frame(&self, surface: &str) {
+        self.as_ref().end_frame(surface);
+    }
+
+    fn push_group(&self, name: &str) {
+        self.as_ref().push_group(name);
+    }
+
+    fn pop_group(&self) {
+        self.as_ref().pop_group();
+    }
+
+    fn flush(&self) {
+        self.as_ref().flush();
+    }
+
+    fn set_draw_mode(&self, surface: &str, mode: DrawMode) {
+        self.as_ref().set_draw_mode(surface, mode);
+    }
+
+    fn set_view(&self, surface: &str, view: &View) {
+        self.as_ref().set_view(surface, view);
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        self.as_ref().snapshot(surface, path)
+    }
+
+    fn quantize_color(&self, color: Color) -> Color {
+        self.as_ref().quantize_color(color)
+    }
+
+    fn max_records(&self, surface: &str) -> Option<usize> {
+        self.as_ref().max_records(surface)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.as_ref().enabled_record(record)
+    }
+
+    #[cfg(feature = "alloc")]
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        self.as_ref().vlog_batch(records);
+    }
+
+    fn on_shutdown(&self) {
+        self.as_ref().on_shutdown();
+    }
+
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        self.as_ref().as_any()
+    }
+}
+
+/// Forwards to the inner vlogger, e.g. for single threaded targets without `Arc`.
+///
+/// An `Rc` can't be installed as the global vlogger, as that requires a
+/// `&'static` reference to a `Sync` vlogger. Use it with the `vlogger:` argument
+/// of the macros instead.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use std::cell::Cell;
+/// use std::rc::Rc;
+/// use v_log::{point, Metadata, Record, VLog};
+///
+/// struct CountingVLogger(Cell<usize>);
+///
+/// impl VLog for CountingVLogger {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, _: &Record) {
+///         self.0.set(self.0.get() + 1);
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// let vlogger = Rc::new(CountingVLogger(Cell::new(0)));
+/// let shared = Rc::clone(&vlogger);
+/// point!(vlogger: shared, "main_surface", [0.0, 0.0], 5.0, Base);
+/// shared.vlog(&Record::builder().build());
+/// shared.flush();
+/// assert_eq!(vlogger.0.get(), 2);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[cfg(feature = "alloc")]
+impl<T> VLog for alloc::rc::Rc<T>
+where
+    T: ?Sized + VLog,
+{
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.as_ref().enabled(metadata)
+    }
+
+    fn vlog(&self, record: &Record) {
+        self.as_ref().vlog(record);
+    }
+
+    fn clear(&self, surface: &str) {
+        self.as_ref().clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        self.as_ref().remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        self.as_ref().transaction(surface, f);
+    }
+
+    fn begin_frame(&self, surface: &str) {
+        self.as_ref().begin_frame(surface);
+    }
+
+    fn end_frame(&self, surface: &str) {
+        self.as_ref().end_frame(surface);
+    }
+
+    fn push_group(&self, name: &str) {
+        self.as_ref().push_group(name);
+    }
+
+    fn pop_group(&self) {
+        self.as_ref().pop_group();
+    }
+
+    fn flush(&self) {
+        self.as_ref().flush();
+    }
+
+    fn set_draw_mode(&self, surface: &str, mode: DrawMode) {
+        self.as_ref().set_draw_mode(surface, mode);
+    }
+
+    fn set_view(&self, surface: &str, view: &View) {
+        self.as_ref().set_view(surface, view);
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        self.as_ref().snapshot(surface, path)
     }
 
-    fn clear(&self, surface: &str) {
-        self.as_ref().clear(surface);
+    fn quantize_color(&self, color: Color) -> Color {
+        self.as_ref().quantize_color(color)
     }
 
-    fn flush(&self) {
-        self.as_ref().flush();
+    fn max_records(&self, surface: &str) -> Option<usize> {
+        self.as_ref().max_records(surface)
     }
-}
 
-#[cfg(feature = "std")]
-impl<T> VLog for std::sync::Arc<T>
-where
-    T: ?Sized + VLog,
-{
-    fn enabled(&self, metadata: &Metadata) -> bool {
-        self.as_ref().enabled(metadata)
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.as_ref().enabled_record(record)
     }
 
-    fn vlog(&self, record: &Record) {
-        self.as_ref().vlog(record);
+    fn vlog_batch(&self, records: &[RecordOwned]) {
+        self.as_ref().vlog_batch(records);
     }
 
-    fn clear(&self, surface: &str) {
-        self.as_ref().clear(surface);
+    fn on_shutdown(&self) {
+        self.as_ref().on_shutdown();
     }
 
-    fn flush(&self) {
-        self.as_ref().flush();
+    fn as_any(&self) -> Option<&dyn core::any::Any> {
+        self.as_ref().as_any()
     }
 }
 
@@ -938,10 +4221,13 @@ where
         Ordering::Relaxed,
     ) {
         Ok(UNINITIALIZED) => {
+            let vlogger = make_vlogger();
             unsafe {
-                VLOGGER = make_vlogger();
+                VLOGGER = vlogger;
             }
             STATE.store(INITIALIZED, Ordering::Release);
+            #[cfg(feature = "std")]
+            replay_draw_modes(vlogger);
             Ok(())
         }
         Err(INITIALIZING) => {
@@ -954,6 +4240,91 @@ where
     }
 }
 
+/// Replaces the global vlogger after it has been set, e.g. to switch the backend during a debug session.
+///
+/// Unlike [`set_vlogger`], this can be called any number of times, but only after
+/// a vlogger has been set. The new vlogger is swapped in atomically. Vlog calls which
+/// are in flight on other threads may still reach the previous vlogger, so a directive
+/// may see either of them. The previous vlogger is never dropped, as it may still be
+/// in use, and each call leaks a small allocation.
+///
+/// Returns the previous vlogger, so it can e.g. be [flushed](VLog::flush), or `None`
+/// without replacing anything if no vlogger has been set yet.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use v_log::{point, Metadata, Record, VLog};
+///
+/// struct CountingVLogger(AtomicUsize);
+///
+/// impl VLog for CountingVLogger {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, _: &Record) {
+///         self.0.fetch_add(1, Ordering::SeqCst);
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// static FIRST: CountingVLogger = CountingVLogger(AtomicUsize::new(0));
+/// static SECOND: CountingVLogger = CountingVLogger(AtomicUsize::new(0));
+///
+/// // there is nothing to replace yet.
+/// assert!(v_log::replace_vlogger(&SECOND).is_none());
+///
+/// v_log::set_vlogger(&FIRST).unwrap();
+/// point!("main_surface", [0.0, 0.0], 5.0, Base);
+/// let previous = v_log::replace_vlogger(&SECOND).unwrap();
+/// previous.flush();
+/// point!("main_surface", [0.0, 0.0], 5.0, Base);
+/// assert_eq!(FIRST.0.load(Ordering::SeqCst), 1);
+/// assert_eq!(SECOND.0.load(Ordering::SeqCst), 1);
+///
+/// // set_vlogger still only works once.
+/// assert!(v_log::set_vlogger(&FIRST).is_err());
+/// ```
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+pub fn replace_vlogger(vlogger: &'static dyn VLog) -> Option<&'static dyn VLog> {
+    replace_vlogger_inner(|| vlogger)
+}
+
+/// Replaces the global vlogger with a `Box<VLog>` after it has been set.
+///
+/// This is a simple convenience wrapper over [`replace_vlogger`], which takes a
+/// `Box<VLog>` rather than a `&'static VLog`. The box is only leaked if the
+/// vlogger is replaced.
+///
+/// Requires the `std` feature.
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+pub fn replace_boxed_vlogger(vlogger: Box<dyn VLog>) -> Option<&'static dyn VLog> {
+    replace_vlogger_inner(|| Box::leak(vlogger))
+}
+
+#[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+fn replace_vlogger_inner<F>(make_vlogger: F) -> Option<&'static dyn VLog>
+where
+    F: FnOnce() -> &'static dyn VLog,
+{
+    // VLOGGER is never written again once the state is INITIALIZED,
+    // so it can be read after observing that.
+    if STATE.load(Ordering::Acquire) != INITIALIZED {
+        return None;
+    }
+    let vlogger = make_vlogger();
+    let new: *mut &'static dyn VLog = Box::leak(Box::new(vlogger));
+    let previous = REPLACED.swap(new, Ordering::AcqRel);
+    replay_draw_modes(vlogger);
+    if previous.is_null() {
+        Some(unsafe { VLOGGER })
+    } else {
+        Some(unsafe { *previous })
+    }
+}
+
 /// A thread-unsafe version of [`set_vlogger`].
 ///
 /// This function is available on all platforms, even those that do not have
@@ -972,6 +4343,43 @@ where
 /// It is safe to use other vlogging functions while this function runs
 /// (including all vlogging macros).
 ///
+/// # Errors
+///
+/// An error is returned if a vlogger has already been set. Calling this concurrently
+/// with [`set_vlogger`] is still a logic error, but if this observes the other call
+/// while it installs its vlogger, it fails with an error instead of being unsound.
+///
+/// # Examples
+///
+/// ```
+/// use core::sync::atomic::{AtomicUsize, Ordering};
+/// use v_log::{point, Metadata, Record, VLog};
+///
+/// struct CountingVLogger(AtomicUsize);
+///
+/// impl VLog for CountingVLogger {
+///     fn enabled(&self, _: &Metadata) -> bool {
+///         true
+///     }
+///     fn vlog(&self, _: &Record) {
+///         self.0.fetch_add(1, Ordering::Relaxed);
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// static VLOGGER: CountingVLogger = CountingVLogger(AtomicUsize::new(0));
+///
+/// // there are no other threads yet.
+/// unsafe { v_log::set_vlogger_racy(&VLOGGER).unwrap() };
+///
+/// point!("main_surface", [1.0, 2.0], 5.0, Base, "o", "p = {}", 1);
+/// assert_eq!(VLOGGER.0.load(Ordering::Relaxed), 1);
+///
+/// // the vlogger can only be set once.
+/// assert!(unsafe { v_log::set_vlogger_racy(&VLOGGER) }.is_err());
+/// ```
+///
 /// [`set_vlogger`]: fn.set_vlogger.html
 pub unsafe fn set_vlogger_racy(vlogger: &'static dyn VLog) -> Result<(), SetVLoggerError> {
     match STATE.load(Ordering::Acquire) {
@@ -980,11 +4388,14 @@ pub unsafe fn set_vlogger_racy(vlogger: &'static dyn VLog) -> Result<(), SetVLog
                 VLOGGER = vlogger;
             }
             STATE.store(INITIALIZED, Ordering::Release);
+            #[cfg(feature = "std")]
+            replay_draw_modes(vlogger);
             Ok(())
         }
         INITIALIZING => {
-            // This is just plain UB, since we were racing another initialization function
-            unreachable!("set_vlogger_racy must not be used with other initialization functions")
+            // We are racing another initialization function, which is a logic error of the
+            // caller. The other call installs its vlogger, so fail like after it.
+            Err(SetVLoggerError(()))
         }
         _ => Err(SetVLoggerError(())),
     }
@@ -1007,6 +4418,35 @@ impl fmt::Display for SetVLoggerError {
 #[cfg(feature = "std")]
 impl error::Error for SetVLoggerError {}
 
+/// The error returned by fallible [`VLog`] requests like [`VLog::snapshot`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum VLogError {
+    /// The vlogger doesn't support the request.
+    Unsupported,
+    /// The vlogger doesn't support the requested file format.
+    UnsupportedFormat,
+    /// The vlogger supports the request, but it failed, e.g. with an I/O error.
+    Failed,
+    /// The request was skipped, as the target or surface is disabled in the vlogger.
+    Disabled,
+}
+
+impl fmt::Display for VLogError {
+    fn fmt(&self, fmt: &mut fmt::Formatter) -> fmt::Result {
+        fmt.write_str(match self {
+            VLogError::Unsupported => "the vlogger does not support this request",
+            VLogError::UnsupportedFormat => "the vlogger does not support this file format",
+            VLogError::Failed => "the vlogger failed to perform the request",
+            VLogError::Disabled => "the target or surface is disabled in the vlogger",
+        })
+    }
+}
+
+// The Error trait is not available in libcore
+#[cfg(feature = "std")]
+impl error::Error for VLogError {}
+
 /// Returns a reference to the vlogger.
 ///
 /// If a vlogger has not been set, a no-op implementation is returned.
@@ -1023,6 +4463,542 @@ pub fn vlogger() -> &'static dyn VLog {
         static NOP: NopVLogger = NopVLogger;
         &NOP
     } else {
+        #[cfg(all(feature = "std", target_has_atomic = "ptr"))]
+        {
+            let replaced = REPLACED.load(Ordering::Acquire);
+            if !replaced.is_null() {
+                // the pointers in REPLACED are leaked and never freed.
+                return unsafe { *replaced };
+            }
+        }
         unsafe { VLOGGER }
     }
 }
+
+/// Clears all the given surfaces of the global vlogger in order.
+///
+/// In contrast to [`clear_many!`], the clears are never [coalesced](set_coalesce_clears),
+/// so every clear reaches the vlogger.
+///
+/// # Examples
+///
+/// ```
+/// // e.g. at the start of every frame in a render loop
+/// v_log::clear_surfaces(&["main_surface", "debug_surface"]);
+/// ```
+pub fn clear_surfaces(surfaces: &[&str]) {
+    let vlogger = __private_api::GlobalVLogger;
+    for surface in surfaces {
+        vlogger.clear_always(surface);
+    }
+}
+
+/// Flushes the global vlogger and tells it to wind down.
+///
+/// This calls [`VLog::flush`] and then [`VLog::on_shutdown`], which gives vloggers
+/// with files or background threads the chance to finish their work, as the global
+/// vlogger is never dropped. Call it before the program exits.
+///
+/// This doesn't uninstall the vlogger, so draws after the shutdown still reach it.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use v_log::{Metadata, Record, VLog};
+///
+/// struct FileVLogger {
+///     shutdowns: AtomicUsize,
+/// }
+///
+/// impl VLog for FileVLogger {
+///     // ...
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+///     fn on_shutdown(&self) {
+///         // e.g. close the file
+///         self.shutdowns.fetch_add(1, Ordering::SeqCst);
+///     }
+/// }
+///
+/// static VLOGGER: FileVLogger = FileVLogger { shutdowns: AtomicUsize::new(0) };
+/// v_log::set_vlogger(&VLOGGER).unwrap();
+///
+/// v_log::shutdown();
+/// assert_eq!(VLOGGER.shutdowns.load(Ordering::SeqCst), 1);
+/// v_log::shutdown();
+/// assert_eq!(VLOGGER.shutdowns.load(Ordering::SeqCst), 2);
+/// ```
+pub fn shutdown() {
+    let vlogger = __private_api::GlobalVLogger;
+    vlogger.flush();
+    vlogger.on_shutdown();
+}
+
+/// Sets the [`DrawMode`] of a surface of the global vlogger.
+///
+/// The mode is forwarded to the vlogger. With the `std` feature, it is also
+/// remembered per surface and forwarded again whenever a vlogger is installed,
+/// so it can be set before the vlogger.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, VLog};
+/// use v_log::DrawMode;
+/// # struct Capture(Mutex<Vec<(String, DrawMode)>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// #     fn set_draw_mode(&self, surface: &str, mode: DrawMode) {
+/// #         self.0.lock().unwrap().push((surface.to_string(), mode));
+/// #     }
+/// # }
+/// # static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+///
+/// v_log::set_draw_mode("current_state", DrawMode::Replace);
+/// v_log::set_vlogger(&CAPTURE).unwrap();
+/// v_log::set_draw_mode("trails", DrawMode::Append);
+///
+/// assert_eq!(v_log::draw_mode("current_state"), DrawMode::Replace);
+/// # assert_eq!(
+/// #     *CAPTURE.0.lock().unwrap(),
+/// #     [("current_state".to_string(), DrawMode::Replace), ("trails".to_string(), DrawMode::Append)]
+/// # );
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+pub fn set_draw_mode(surface: &str, mode: DrawMode) {
+    #[cfg(feature = "std")]
+    surfaces::update(surface, |config| config.draw_mode = Some(mode));
+    __private_api::GlobalVLogger.set_draw_mode(surface, mode);
+}
+
+/// Returns the [`DrawMode`] of a surface set with [`set_draw_mode`],
+/// or [`DrawMode::Append`] if it hasn't been set.
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn draw_mode(surface: &str) -> DrawMode {
+    surfaces::get(surface, |config| config.draw_mode)
+        .flatten()
+        .unwrap_or_default()
+}
+
+/// Forwards the draw modes from [`set_draw_mode`] to a newly installed vlogger.
+#[cfg(feature = "std")]
+fn replay_draw_modes(vlogger: &dyn VLog) {
+    for (surface, config) in surfaces::snapshot() {
+        if let Some(mode) = config.draw_mode {
+            vlogger.set_draw_mode(&surface, mode);
+        }
+    }
+}
+
+/// Snaps a color to the colors supported by the global vlogger.
+///
+/// See [`VLog::quantize_color`].
+///
+/// # Examples
+///
+/// ```
+/// # use v_log::{Metadata, Record, VLog};
+/// use v_log::Color;
+///
+/// /// A vlogger which can only display shades of gray.
+/// struct GrayVLogger;
+///
+/// impl VLog for GrayVLogger {
+///     // ...
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+///     fn quantize_color(&self, color: Color) -> Color {
+///         match color {
+///             Color::Hex(rgba) => {
+///                 let [r, g, b, a] = rgba.to_be_bytes();
+///                 let gray = ((r as u32 + g as u32 + b as u32) / 3) as u8;
+///                 Color::Hex(u32::from_be_bytes([gray, gray, gray, a]))
+///             }
+///             color => color,
+///         }
+///     }
+/// }
+///
+/// static VLOGGER: GrayVLogger = GrayVLogger;
+/// v_log::set_vlogger(&VLOGGER).unwrap();
+///
+/// let red = Color::Hex(0xff0000ff);
+/// assert!(matches!(v_log::quantize_color(red), Color::Hex(0x555555ff)));
+/// ```
+pub fn quantize_color(color: Color) -> Color {
+    vlogger().quantize_color(color)
+}
+
+/// Enables or disables capturing the source location of vlog directives.
+///
+/// If disabled, the records created by the macros have no [`file`](Record::file),
+/// [`module_path`](Record::module_path) and [`line`](Record::line).
+/// The [`call_site_id`](Record::call_site_id) is still set.
+/// Location capture is enabled by default.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(Mutex<Vec<(Option<String>, Option<u32>, Visual)>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) {
+/// #         self.0.lock().unwrap().push((record.file().map(Into::into), record.line(), record.visual().clone()))
+/// #     }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::point;
+///
+/// let capture = Capture(Mutex::new(Vec::new()));
+/// v_log::set_capture_location(false);
+/// point!(vlogger: capture, "main_surface", [1.0, 2.0], 5.0, Base);
+///
+/// let records = capture.0.lock().unwrap();
+/// assert_eq!(records[0].0, None);
+/// assert_eq!(records[0].1, None);
+/// assert!(matches!(records[0].2, Visual::Point { x, y, .. } if x == 1.0 && y == 2.0));
+/// ```
+pub fn set_capture_location(enabled: bool) {
+    CAPTURE_LOCATION.store(enabled as usize, Ordering::Relaxed);
+}
+
+pub(crate) fn capture_location() -> bool {
+    CAPTURE_LOCATION.load(Ordering::Relaxed) != 0
+}
+
+/// Sets the point style used by [`point!`] if the style argument is omitted.
+///
+/// The initial default is [`PointStyle::Point`] (`"o"`).
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(Mutex<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.lock().unwrap().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// # static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+/// # v_log::set_vlogger(&CAPTURE).unwrap();
+/// use v_log::{point, PointStyle, Visual};
+///
+/// point!("main_surface", [0.0, 0.0], 5.0, Base);
+/// v_log::set_default_point_style(PointStyle::PointCross);
+/// point!("main_surface", [1.0, 0.0], 5.0, Base);
+/// point!("main_surface", [2.0, 0.0], 5.0, Base, "o");
+/// let visuals = CAPTURE.0.lock().unwrap();
+/// assert!(matches!(visuals[0], Visual::Point { style: PointStyle::Point, .. }));
+/// assert!(matches!(visuals[1], Visual::Point { style: PointStyle::PointCross, .. }));
+/// assert!(matches!(visuals[2], Visual::Point { style: PointStyle::Point, .. }));
+/// ```
+pub fn set_default_point_style(style: PointStyle) {
+    DEFAULT_POINT_STYLE.store(style as usize, Ordering::Relaxed);
+}
+
+/// Returns the point style used by [`point!`] if the style argument is omitted,
+/// see [`set_default_point_style`].
+pub fn default_point_style() -> PointStyle {
+    PointStyle::ALL[DEFAULT_POINT_STYLE.load(Ordering::Relaxed)]
+}
+
+/// Sets the line style used by [`polyline!`] if the style argument is omitted.
+///
+/// The initial default is [`LineStyle::Simple`] (`"-"`).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::LineStyle;
+///
+/// assert!(matches!(v_log::default_line_style(), LineStyle::Simple));
+/// v_log::set_default_line_style(LineStyle::Dashed);
+/// assert!(matches!(v_log::default_line_style(), LineStyle::Dashed));
+/// ```
+pub fn set_default_line_style(style: LineStyle) {
+    DEFAULT_LINE_STYLE.store(style as usize, Ordering::Relaxed);
+}
+
+/// Returns the line style used by [`polyline!`] if the style argument is omitted,
+/// see [`set_default_line_style`].
+pub fn default_line_style() -> LineStyle {
+    LineStyle::ALL[DEFAULT_LINE_STYLE.load(Ordering::Relaxed)]
+}
+
+/// Sets the maximum number of records vloggers should keep for a surface.
+///
+/// This is an advisory, which buffering vloggers receive through [`VLog::max_records`].
+/// They are expected to drop the oldest records of the surface beyond the cap.
+/// This bounds the memory of long running sessions independent of the installed vlogger.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::{BTreeMap, VecDeque};
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, VLog};
+/// /// A vlogger which keeps the x-coordinates of the points of every surface.
+/// struct BufferingVLogger(Mutex<BTreeMap<String, VecDeque<f64>>>);
+///
+/// impl VLog for BufferingVLogger {
+///     fn vlog(&self, record: &Record) {
+///         if let v_log::Visual::Point { x, .. } = record.visual() {
+///             let mut surfaces = self.0.lock().unwrap();
+///             let buffer = surfaces.entry(record.surface().to_string()).or_default();
+///             buffer.push_back(*x);
+///             if let Some(cap) = self.max_records(record.surface()) {
+///                 while buffer.len() > cap {
+///                     buffer.pop_front();
+///                 }
+///             }
+///         }
+///     }
+///     // ...
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// }
+///
+/// static VLOGGER: BufferingVLogger = BufferingVLogger(Mutex::new(BTreeMap::new()));
+/// v_log::set_vlogger(&VLOGGER).unwrap();
+///
+/// v_log::set_surface_record_cap("main_surface", 2);
+/// for x in 0..3 {
+///     v_log::point!("main_surface", [x as f64, 0.0], 1.0, Base);
+/// }
+/// assert_eq!(VLOGGER.0.lock().unwrap()["main_surface"], [1.0, 2.0]);
+/// ```
+#[cfg(feature = "std")]
+pub fn set_surface_record_cap(surface: &str, cap: usize) {
+    surfaces::update(surface, |config| config.record_cap = Some(cap));
+}
+
+/// Returns the record cap of a surface set with [`set_surface_record_cap`].
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn surface_record_cap(surface: &str) -> Option<usize> {
+    surfaces::get(surface, |config| config.record_cap).flatten()
+}
+
+/// Sets a uniform scale for the coordinates of the records drawn to a surface.
+///
+/// The macros multiply all coordinates with the scale before the vlogger receives
+/// them, e.g. `0.001` to convert millimeters to meters. Sizes are not scaled, except
+/// for the world units thickness of lines, see [`Visual::scale`].
+/// The default scale is `1.0`.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(Mutex<Vec<Option<[f64; 3]>>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.lock().unwrap().push(record.visual().position()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// # static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+/// # v_log::set_vlogger(&CAPTURE).unwrap();
+/// v_log::set_surface_scale("part_mm", 0.001);
+/// v_log::point!("part_mm", [1500.0, 250.0], 5.0, Base);
+/// v_log::point!("other", [1500.0, 250.0], 5.0, Base);
+/// assert_eq!(
+///     *CAPTURE.0.lock().unwrap(),
+///     [Some([1.5, 0.25, 0.0]), Some([1500.0, 250.0, 0.0])]
+/// );
+/// assert_eq!(v_log::surface_scale("part_mm"), 0.001);
+/// ```
+#[cfg(feature = "std")]
+pub fn set_surface_scale(surface: &str, scale: f64) {
+    surfaces::set_scale(surface, scale);
+}
+
+/// Returns the scale of a surface set with [`set_surface_scale`].
+///
+/// Requires the `std` feature.
+#[cfg(feature = "std")]
+pub fn surface_scale(surface: &str) -> f64 {
+    surfaces::scale(surface).unwrap_or(1.0)
+}
+
+/// Advances the global frame counter and returns the new frame number.
+///
+/// Frames start at `0`. Surfaces with [`set_auto_clear`] get cleared on their first
+/// draw after the frame advanced. Call this e.g. once per iteration of a render loop.
+/// This also resets the suppression of repeated clears, see [`set_coalesce_clears`].
+///
+/// # Examples
+///
+/// ```
+/// assert_eq!(v_log::frame(), 0);
+/// assert_eq!(v_log::next_frame(), 1);
+/// assert_eq!(v_log::frame(), 1);
+/// ```
+pub fn next_frame() -> u64 {
+    #[cfg(feature = "std")]
+    surfaces::reset_cleared();
+    FRAME.fetch_add(1, Ordering::Relaxed).wrapping_add(1) as u64
+}
+
+/// Returns the current frame number, see [`next_frame`].
+pub fn frame() -> u64 {
+    FRAME.load(Ordering::Relaxed) as u64
+}
+
+/// Enables or disables suppressing repeated clears of the same surface.
+///
+/// If enabled, [`clear!`] and [`clear_many!`] only forward the first clear of a surface
+/// to the global vlogger. Further clears of the surface from the same thread are dropped
+/// until something is drawn to it through the macros, or [`next_frame`] advances the frame.
+/// This avoids flooding buffering backends from render loops which clear the same surface
+/// many times per frame. It is disabled by default.
+///
+/// Only the global vlogger is affected, clears passed to a vlogger with `vlogger:`
+/// and [`clear_surfaces`] always reach it. Draws which don't go through the macros,
+/// e.g. direct calls to [`VLog::vlog`] or replaying a recording, are not noticed,
+/// so a clear after them may be dropped. Keep this disabled if the global vlogger
+/// is drawn to like that.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(Mutex<Vec<String>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, surface: &str) { self.0.lock().unwrap().push(surface.into()) }
+/// #     fn flush(&self) {}
+/// # }
+/// # impl Capture {
+/// #     fn clears(&self) -> usize { self.0.lock().unwrap().len() }
+/// # }
+/// # static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+/// use v_log::{clear, point};
+///
+/// v_log::set_vlogger(&CAPTURE).unwrap();
+/// v_log::set_coalesce_clears(true);
+///
+/// clear!("main_surface");
+/// clear!("main_surface");
+/// clear!("main_surface");
+/// assert_eq!(CAPTURE.clears(), 1);
+/// point!("main_surface", [0.0, 0.0], 5.0, Base);
+/// clear!("main_surface");
+/// assert_eq!(CAPTURE.clears(), 2);
+///
+/// // other threads and other vloggers get their own clears.
+/// std::thread::spawn(|| clear!("main_surface")).join().unwrap();
+/// assert_eq!(CAPTURE.clears(), 3);
+/// let (a, b) = (Capture(Mutex::new(Vec::new())), Capture(Mutex::new(Vec::new())));
+/// clear!(vlogger: a, "main_surface");
+/// clear!(vlogger: b, "main_surface");
+/// clear!(vlogger: b, "main_surface");
+/// assert_eq!((a.clears(), b.clears()), (1, 2));
+///
+/// v_log::set_coalesce_clears(false);
+/// clear!("main_surface");
+/// assert_eq!(CAPTURE.clears(), 4);
+/// ```
+#[cfg(feature = "std")]
+pub fn set_coalesce_clears(enabled: bool) {
+    surfaces::set_coalesce_clears(enabled);
+}
+
+/// Enables or disables clearing a surface automatically once per frame.
+///
+/// If enabled, the first draw to the surface after [`next_frame`] advanced the frame
+/// clears the surface before it is drawn. This removes explicit [`clear!`] calls from
+/// render loops. The surface isn't cleared in the frame this is called in.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(Mutex<Vec<String>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.lock().unwrap().push(record.args().to_string()) }
+/// #     fn clear(&self, surface: &str) { self.0.lock().unwrap().push(format!("clear {}", surface)) }
+/// #     fn flush(&self) {}
+/// # }
+/// # static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+/// # v_log::set_vlogger(&CAPTURE).unwrap();
+/// v_log::set_auto_clear("current_state", true);
+/// for frame in 0..2 {
+///     v_log::point!("current_state", [frame as f64, 0.0], 5.0, Base, "o", "frame {}", frame);
+///     v_log::point!("current_state", [frame as f64, 1.0], 5.0, Base, "o", "frame {}", frame);
+///     v_log::next_frame();
+/// }
+/// assert_eq!(
+///     *CAPTURE.0.lock().unwrap(),
+///     ["frame 0", "frame 0", "clear current_state", "frame 1", "frame 1"]
+/// );
+/// ```
+///
+/// The clear keeps its place among the records buffered by a [`LocalBatch`]:
+///
+/// ```
+/// # use std::sync::Mutex;
+/// # use v_log::{Metadata, Record, RecordOwned, VLog};
+/// # struct Capture(Mutex<Vec<String>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.lock().unwrap().push(record.args().to_string()) }
+/// #     fn vlog_batch(&self, records: &[RecordOwned]) {
+/// #         self.0.lock().unwrap().extend(records.iter().map(|r| r.args().to_string()))
+/// #     }
+/// #     fn clear(&self, surface: &str) { self.0.lock().unwrap().push(format!("clear {}", surface)) }
+/// #     fn flush(&self) {}
+/// # }
+/// # static CAPTURE: Capture = Capture(Mutex::new(Vec::new()));
+/// # v_log::set_vlogger(&CAPTURE).unwrap();
+/// use v_log::{message, LocalBatch};
+///
+/// v_log::set_auto_clear("t", true);
+/// {
+///     let _batch = LocalBatch::new();
+///     message!("t", "frame 0");
+///     v_log::next_frame();
+///     message!("t", "frame 1");
+/// }
+/// assert_eq!(*CAPTURE.0.lock().unwrap(), ["frame 0", "clear t", "frame 1"]);
+/// ```
+#[cfg(feature = "std")]
+pub fn set_auto_clear(surface: &str, enabled: bool) {
+    surfaces::set_auto_clear(surface, FRAME.load(Ordering::Relaxed), enabled);
+}
@@ -0,0 +1,116 @@
+//! A vlogger which forwards messages to the `log` crate.
+
+use crate::{Metadata, Record, VLog, Visual};
+
+/// Forwards the text of [`Visual::Message`] records to the [`log`] crate.
+///
+/// Every message becomes a log line with the formatted message, target,
+/// module path, file and line of the vlog directive, at the configured level
+/// ([`log::Level::Info`] by default). This shows the text of the vlogs with an
+/// existing `log` setup, e.g. `env_logger`, without a visual backend.
+///
+/// All other visuals are dropped. As [`Metadata`] doesn't contain the visual,
+/// [`enabled`](VLog::enabled) only checks the level and target with the `log` crate
+/// and [`enabled_record`](VLog::enabled_record) returns `true` only for messages.
+/// Clears are ignored.
+///
+/// Requires the `log-bridge` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::Mutex;
+/// use v_log::{message, point, LogBridge};
+///
+/// struct Lines(Mutex<Vec<String>>);
+///
+/// impl log::Log for Lines {
+///     fn enabled(&self, _: &log::Metadata) -> bool { true }
+///     fn log(&self, record: &log::Record) {
+///         let line = format!("{} {}: {}", record.level(), record.target(), record.args());
+///         self.0.lock().unwrap().push(line);
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// static LINES: Lines = Lines(Mutex::new(Vec::new()));
+/// log::set_logger(&LINES).unwrap();
+/// log::set_max_level(log::LevelFilter::Info);
+///
+/// let vlogger = LogBridge::new();
+/// message!(vlogger: vlogger, target: "physics", "main_surface", "{} contacts", 3);
+/// point!(vlogger: vlogger, target: "physics", "main_surface", [0.0, 0.0], 5.0, Base, "o", "dropped");
+/// assert_eq!(*LINES.0.lock().unwrap(), ["INFO physics: 3 contacts"]);
+///
+/// // debug lines are disabled in the logger.
+/// message!(vlogger: vlogger.with_level(log::Level::Debug), "main_surface", "hidden");
+/// assert_eq!(LINES.0.lock().unwrap().len(), 1);
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct LogBridge {
+    level: log::Level,
+}
+
+impl LogBridge {
+    /// Create a new `LogBridge` which logs at [`log::Level::Info`].
+    pub const fn new() -> Self {
+        LogBridge {
+            level: log::Level::Info,
+        }
+    }
+
+    /// Set the level of the log lines.
+    pub const fn with_level(mut self, level: log::Level) -> Self {
+        self.level = level;
+        self
+    }
+
+    /// The level of the log lines.
+    pub const fn level(&self) -> log::Level {
+        self.level
+    }
+}
+
+impl Default for LogBridge {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl VLog for LogBridge {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.level <= log::max_level()
+            && log::logger().enabled(
+                &log::Metadata::builder()
+                    .level(self.level)
+                    .target(metadata.target())
+                    .build(),
+            )
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        matches!(record.visual(), Visual::Message)
+    }
+
+    fn vlog(&self, record: &Record) {
+        if !matches!(record.visual(), Visual::Message) {
+            return;
+        }
+        log::logger().log(
+            &log::Record::builder()
+                .args(*record.args())
+                .level(self.level)
+                .target(record.target())
+                .module_path(record.module_path())
+                .file(record.file())
+                .line(record.line())
+                .build(),
+        );
+    }
+
+    fn clear(&self, _: &str) {}
+
+    fn flush(&self) {
+        log::logger().flush();
+    }
+}
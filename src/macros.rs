@@ -10,7 +10,12 @@
 
 //! Import this as `use v_log::macros::*` to import only the macros.
 
-pub use crate::{arrow, clear, label, message, point, polyline, vlog_enabled};
+pub use crate::{
+    aabb, arc, arrow, axes, begin_frame, bezier, clear, clear_many, ellipse, end_frame, errorbar,
+    grid, image, label, mesh, message, obb, point, point_cloud, polygon, polyline,
+    polyline_gradient, quiver, ray, remove_surface, set_view, snapshot, spline, timed_label,
+    transaction, vlog_enabled, vlog_every,
+};
 
 /// Clear a surface of the vlogger, including the messages that have been sent to it.
 ///
@@ -21,13 +26,41 @@ pub use crate::{arrow, clear, label, message, point, polyline, vlog_enabled};
 ///
 /// clear!("main_surface");
 /// ```
+///
+/// Clears don't check [`VLog::enabled`](crate::VLog::enabled), so they reach the vlogger
+/// even if it filters the records of the target:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{clear, point, Metadata, Record, VLog};
+///
+/// struct OnlyPhysics(RefCell<Vec<String>>);
+///
+/// impl VLog for OnlyPhysics {
+///     fn enabled(&self, metadata: &Metadata) -> bool {
+///         metadata.target() == "physics"
+///     }
+///     fn vlog(&self, record: &Record) {
+///         self.0.borrow_mut().push(format!("vlog {}", record.surface()));
+///     }
+///     fn clear(&self, surface: &str) {
+///         self.0.borrow_mut().push(format!("clear {}", surface));
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// let vlogger = OnlyPhysics(RefCell::new(Vec::new()));
+/// point!(vlogger: vlogger, target: "render", "main_surface", [0.0, 0.0], 5.0, Base);
+/// clear!(vlogger: vlogger, target: "render", "main_surface");
+/// assert_eq!(*vlogger.0.borrow(), ["clear main_surface"]);
+/// ```
 #[macro_export]
 macro_rules! clear {
     // clear!(vlogger: my_vlogger, target: "my_target", "my_surface")
     (vlogger: $vlogger:expr, $surface:expr) => {
         $crate::__private_api::clear(
             $crate::__vlog_vlogger!($vlogger),
-            $crate::__private_api::module_path!(),
+            $crate::__default_target!(),
             $surface,
         )
     };
@@ -50,506 +83,3616 @@ macro_rules! clear {
     ($surface:expr) => {
         $crate::__private_api::clear(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
-            $crate::__private_api::module_path!(),
+            $crate::__default_target!(),
             $surface,
         )
     };
 }
 
-/// Logs a message to the vlogger.
+/// Remove a surface of the vlogger entirely, e.g. close its window.
+///
+/// [`clear!`] only empties a surface and keeps it, while this removes the surface itself.
+/// Vloggers without windows or figures treat it like [`clear!`].
 ///
 /// # Examples
 ///
 /// ```
-/// use v_log::message;
+/// use v_log::remove_surface;
 ///
-/// let pos = [3.234, -1.223];
+/// remove_surface!("debug_surface");
+/// ```
+///
+/// A vlogger which manages windows:
 ///
-/// message!("main_surface", color: Healthy, "Correct position");
-/// message!("main_surface", "Position is: x: {}, y: {}", pos[0], pos[1]);
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{clear, point, remove_surface, Metadata, Record, VLog};
+///
+/// struct Windows(RefCell<Vec<String>>);
+///
+/// impl VLog for Windows {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         let mut windows = self.0.borrow_mut();
+///         if !windows.iter().any(|w| w == record.surface()) {
+///             windows.push(record.surface().to_string());
+///         }
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+///     fn remove_surface(&self, surface: &str) {
+///         self.0.borrow_mut().retain(|w| w != surface);
+///     }
+/// }
+///
+/// let windows = Windows(RefCell::new(Vec::new()));
+/// point!(vlogger: windows, "main_surface", [0.0, 0.0], 5.0, Base);
+/// point!(vlogger: windows, "debug_surface", [0.0, 0.0], 5.0, Base);
+/// clear!(vlogger: windows, "debug_surface");
+/// assert_eq!(*windows.0.borrow(), ["main_surface", "debug_surface"]);
+/// remove_surface!(vlogger: windows, "debug_surface");
+/// assert_eq!(*windows.0.borrow(), ["main_surface"]);
 /// ```
 #[macro_export]
-macro_rules! message {
-    // message!(vlogger: my_vlogger, target: "my_target", "my_surface", color: Base, "a {} event", "log")
-    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__message!(
-            $crate::__vlog_vlogger!($vlogger),
-            $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
-        )
-    });
+macro_rules! remove_surface {
+    // remove_surface!(vlogger: my_vlogger, target: "my_target", "my_surface")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr) => {
+        $crate::__private_api::remove_surface($crate::__vlog_vlogger!($vlogger), $target, $surface)
+    };
 
-    // message!(vlogger: my_vlogger, "my_surface", color: Base, "a {} event", "log")
-    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__message!(
+    // remove_surface!(vlogger: my_vlogger, "my_surface")
+    (vlogger: $vlogger:expr, $surface:expr) => {
+        $crate::__private_api::remove_surface(
             $crate::__vlog_vlogger!($vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
         )
-    });
+    };
 
-    // message!(target: "my_target", "my_surface", color: Base, "a {} event", "log")
-    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__message!(
+    // remove_surface!(target: "my_target", "my_surface")
+    (target: $target:expr, $surface:expr) => {
+        $crate::__private_api::remove_surface(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $target,
             $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
         )
-    });
+    };
 
-    // message!("my_surface", color: Base, "a {} event", "log")
-    ($surface:expr, $($arg:tt)+) => (
-        $crate::__message!(
+    // remove_surface!("my_surface")
+    ($surface:expr) => {
+        $crate::__private_api::remove_surface(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
         )
-    )
+    };
 }
 
-/// Sends a point to the vlogger.
+/// Clear multiple surfaces of the vlogger in the given order.
+///
+/// This is the same as calling [`clear!`] for every surface in the list.
 ///
 /// # Examples
 ///
 /// ```
-/// use v_log::point;
+/// use v_log::clear_many;
 ///
-/// let pos1 = [3.234, -1.223];
-/// let pos2 = [2.713, 0.577];
+/// clear_many!(["main_surface", "debug_surface", "overlay"]);
+/// ```
 ///
-/// point!("main_surface", pos1, 5.0, Base, "o", "Position is: x: {}, y: {}", pos1[0], pos1[1]);
-/// point!("main_surface", pos2, 5.0, Base);
+/// The surfaces are cleared in order:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<String>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, surface: &str) { self.0.borrow_mut().push(surface.to_string()) }
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::clear_many;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// clear_many!(vlogger: capture, ["a", "b", "c"]);
+/// assert_eq!(*capture.0.borrow(), ["a", "b", "c"]);
 /// ```
 #[macro_export]
-macro_rules! point {
-    // point!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
-    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__point!(
+macro_rules! clear_many {
+    // clear_many!(vlogger: my_vlogger, target: "my_target", ["surface_a", "surface_b"])
+    (vlogger: $vlogger:expr, target: $target:expr, $surfaces:expr) => {
+        $crate::__private_api::clear_surfaces($crate::__vlog_vlogger!($vlogger), $target, $surfaces)
+    };
+
+    // clear_many!(vlogger: my_vlogger, ["surface_a", "surface_b"])
+    (vlogger: $vlogger:expr, $surfaces:expr) => {
+        $crate::__private_api::clear_surfaces(
             $crate::__vlog_vlogger!($vlogger),
-            $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
+            $crate::__default_target!(),
+            $surfaces,
         )
-    });
+    };
 
-    // point!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
-    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__point!(
+    // clear_many!(target: "my_target", ["surface_a", "surface_b"])
+    (target: $target:expr, $surfaces:expr) => {
+        $crate::__private_api::clear_surfaces(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $target,
+            $surfaces,
+        )
+    };
+
+    // clear_many!(["surface_a", "surface_b"])
+    ($surfaces:expr) => {
+        $crate::__private_api::clear_surfaces(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $crate::__default_target!(),
+            $surfaces,
+        )
+    };
+}
+
+/// Clear a surface and redraw it with the draws of a closure as one transaction.
+///
+/// The closure gets the vlogger to draw to, which has to be passed to the
+/// drawing macros with `vlogger:`. Vloggers may buffer these draws and apply them
+/// together with the clear, see [`VLog::transaction`](crate::VLog::transaction).
+/// By default, this is the same as [`clear!`] followed by the draws.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{point, transaction};
+///
+/// transaction!("main_surface", |v| {
+///     point!(vlogger: v, "main_surface", [0.0, 0.0], 5.0, Base);
+///     point!(vlogger: v, "main_surface", [1.0, 0.0], 5.0, Base);
+/// });
+/// ```
+///
+/// A vlogger which buffers the draws of a transaction:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{point, transaction, Metadata, Record, VLog};
+///
+/// struct Buffer(RefCell<Vec<String>>);
+///
+/// impl VLog for Buffer {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.args().to_string()) }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// struct Screen(RefCell<Vec<Vec<String>>>);
+///
+/// impl VLog for Screen {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         self.0.borrow_mut().push(vec![record.args().to_string()])
+///     }
+///     fn clear(&self, _: &str) { self.0.borrow_mut().clear() }
+///     fn flush(&self) {}
+///     fn transaction(&self, _: &str, f: &dyn Fn(&dyn VLog)) {
+///         let buffer = Buffer(RefCell::new(Vec::new()));
+///         f(&buffer);
+///         // swap in all draws at once.
+///         *self.0.borrow_mut() = vec![buffer.0.into_inner()];
+///     }
+/// }
+///
+/// let screen = Screen(RefCell::new(Vec::new()));
+/// point!(vlogger: screen, "main_surface", [0.0, 0.0], 5.0, Base, "o", "old");
+/// transaction!(vlogger: screen, "main_surface", |v| {
+///     point!(vlogger: v, "main_surface", [0.0, 0.0], 5.0, Base, "o", "a");
+///     point!(vlogger: v, "main_surface", [1.0, 0.0], 5.0, Base, "o", "b");
+/// });
+/// assert_eq!(*screen.0.borrow(), [["a", "b"]]);
+/// ```
+#[macro_export]
+macro_rules! transaction {
+    // transaction!(vlogger: my_vlogger, target: "my_target", "my_surface", |v| { ... })
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $f:expr) => {
+        $crate::__private_api::transaction($crate::__vlog_vlogger!($vlogger), $target, $surface, $f)
+    };
+
+    // transaction!(vlogger: my_vlogger, "my_surface", |v| { ... })
+    (vlogger: $vlogger:expr, $surface:expr, $f:expr) => {
+        $crate::__private_api::transaction(
             $crate::__vlog_vlogger!($vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
+            $f,
         )
-    });
+    };
 
-    // point!(target: "my_target", "my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
-    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__point!(
+    // transaction!(target: "my_target", "my_surface", |v| { ... })
+    (target: $target:expr, $surface:expr, $f:expr) => {
+        $crate::__private_api::transaction(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $target,
             $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
+            $f,
         )
-    });
+    };
 
-    // point!("my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
-    ($surface:expr, $($arg:tt)+) => (
-        $crate::__point!(
+    // transaction!("my_surface", |v| { ... })
+    ($surface:expr, $f:expr) => {
+        $crate::__private_api::transaction(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
+            $f,
         )
-    )
+    };
 }
 
-/// Sends a label/text annotation to the vlogger.
+/// Save the current content of a surface to a file.
+///
+/// The file format is implementation-defined and chosen by the vlogger from the
+/// extension of the path, e.g. PNG or SVG. This evaluates to a
+/// `Result<(), VLogError>`, which is [`VLogError::Unsupported`](crate::VLogError::Unsupported)
+/// if the vlogger can't take snapshots and [`VLogError::Disabled`](crate::VLogError::Disabled)
+/// if the target or surface is disabled.
 ///
 /// # Examples
 ///
 /// ```
-/// use v_log::label;
+/// use v_log::{snapshot, VLogError};
 ///
-/// let pos = [3.234, -1.223];
+/// // no vlogger is installed, so everything is disabled.
+/// assert_eq!(snapshot!("main_surface", "main_surface.png"), Err(VLogError::Disabled));
+/// ```
 ///
-/// label!("main_surface", pos, (12.0, Base, "<"), "Position is: x: {}, y: {}", pos[0], pos[1]);
-/// label!("main_surface", pos, "Flexible position"); // with size 12.0, flexible alignment and "Base" color
+/// The [`AsciiVLogger`](crate::AsciiVLogger) writes text files:
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// use v_log::{point, snapshot, AsciiVLogger, VLogError};
+///
+/// let vlogger = AsciiVLogger::new(3, 1);
+/// point!(vlogger: vlogger, "main_surface", [1.5, 0.5], 1.0, Base);
+///
+/// let path = std::env::temp_dir().join("v_log_snapshot_example.txt");
+/// let path = path.to_str().unwrap();
+/// snapshot!(vlogger: vlogger, "main_surface", path).unwrap();
+/// assert_eq!(std::fs::read_to_string(path).unwrap(), " o ");
+/// # std::fs::remove_file(path).unwrap();
+///
+/// assert_eq!(
+///     snapshot!(vlogger: vlogger, "main_surface", "main_surface.png"),
+///     Err(VLogError::UnsupportedFormat)
+/// );
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
 /// ```
 #[macro_export]
-macro_rules! label {
-    // label!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
-    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__label!(
-            $crate::__vlog_vlogger!($vlogger),
-            $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
-        )
-    });
+macro_rules! snapshot {
+    // snapshot!(vlogger: my_vlogger, target: "my_target", "my_surface", "my_file.png")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $path:expr) => {
+        $crate::__private_api::snapshot($crate::__vlog_vlogger!($vlogger), $target, $surface, $path)
+    };
 
-    // label!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
-    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__label!(
+    // snapshot!(vlogger: my_vlogger, "my_surface", "my_file.png")
+    (vlogger: $vlogger:expr, $surface:expr, $path:expr) => {
+        $crate::__private_api::snapshot(
             $crate::__vlog_vlogger!($vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
+            $path,
         )
-    });
+    };
 
-    // label!(target: "my_target", "my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
-    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__label!(
+    // snapshot!(target: "my_target", "my_surface", "my_file.png")
+    (target: $target:expr, $surface:expr, $path:expr) => {
+        $crate::__private_api::snapshot(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $target,
             $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
+            $path,
         )
-    });
+    };
 
-    // label!("my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
-    ($surface:expr, $($arg:tt)+) => (
-        $crate::__label!(
+    // snapshot!("my_surface", "my_file.png")
+    ($surface:expr, $path:expr) => {
+        $crate::__private_api::snapshot(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
+            $path,
         )
-    )
+    };
 }
 
-/// Sends an open or closed polyline to the vlogger.
+/// Set the camera [`View`](crate::View) of a 3D surface.
+///
+/// The view is advisory: the vlogger may ignore it, e.g. for 2D surfaces.
+/// A `fov <= 0.0` selects an orthographic projection.
 ///
 /// # Examples
 ///
 /// ```
-/// use v_log::polyline;
+/// use v_log::{set_view, View};
 ///
-/// // Points must be of the same type for arrays to work,
-/// // but are only required to implement IntoIterator.
-/// // They can be arbitrary dimension, but only the first 3 are used.
-/// let pos1 = [3.234, -1.223];
-/// let pos2 = [2.713, 0.577];
-/// let pos3 = [6.283, 0.692];
+/// set_view!(
+///     "main_surface",
+///     View {
+///         eye: [5.0, -5.0, 3.0],
+///         target: [0.0, 0.0, 0.0],
+///         up: [0.0, 0.0, 1.0],
+///         fov: 0.8,
+///     }
+/// );
+/// ```
 ///
-/// // Draw a single line with thickness 5.0 and color `Base` from pos1 to pos2.
-/// // The label may get displayed, but the size, position and and format is up
-/// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
-/// polyline!("main_surface", (pos1, pos2), 5.0, Base, "--", "Position is: x: {}, y: {}", pos1[0], pos1[1]);
-/// polyline!("main_surface", (pos1, pos2), 5.0, Base, "->");
-/// polyline!("main_surface", (pos1, pos2), 5.0, Base);
-/// // Draw two connected lines (polyline). These can not be labelled in
-/// // the macro, as a label on polylines is hard to control in implementations.
-/// // If you need a label, consider drawing the first segment with a label.
-/// polyline!("main_surface", [pos1, pos2, pos3], 5.0, Base, "--");
-/// polyline!("main_surface", [pos1, pos2, pos3], 5.0, Base);
-/// // Draw a triangle (polygon). These can be labelled and the label is placed
-/// // in the centeroid of them. Note, this may not be inside of the polygon.
-/// // The textsize of the label must be specified before the text.
-/// polyline!("main_surface", closed: [pos1, pos2, pos3], 5.0, Base, "--", 12.0, "Position is: x: {}, y: {}", pos1[0], pos1[1]);
-/// polyline!("main_surface", closed: [pos1, pos2, pos3], 5.0, Base, "_>");
-/// polyline!("main_surface", closed: [pos1, pos2, pos3], 5.0, Base);
+/// The view reaches the vlogger:
+///
+/// ```
+/// # use std::cell::Cell;
+/// use v_log::{set_view, Metadata, Record, VLog, View};
+///
+/// struct Camera(Cell<Option<View>>);
+///
+/// impl VLog for Camera {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, _: &Record) {}
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+///     fn set_view(&self, _: &str, view: &View) {
+///         self.0.set(Some(*view));
+///     }
+/// }
+///
+/// let camera = Camera(Cell::new(None));
+/// let top_down = View { eye: [0.0, 0.0, 10.0], target: [0.0; 3], up: [0.0, 1.0, 0.0], fov: 0.0 };
+/// set_view!(vlogger: camera, "main_surface", top_down);
+/// assert_eq!(camera.0.get(), Some(top_down));
 /// ```
 #[macro_export]
-macro_rules! polyline {
-    // polyline!(vlogger: my_vlogger, target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
-    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__line!(
+macro_rules! set_view {
+    // set_view!(vlogger: my_vlogger, target: "my_target", "my_surface", view)
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $view:expr) => {
+        $crate::__private_api::set_view(
             $crate::__vlog_vlogger!($vlogger),
+            $target,
             $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
+            &$view,
         )
-    });
+    };
 
-    // polyline!(vlogger: my_vlogger, "my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
-    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__line!(
+    // set_view!(vlogger: my_vlogger, "my_surface", view)
+    (vlogger: $vlogger:expr, $surface:expr, $view:expr) => {
+        $crate::__private_api::set_view(
             $crate::__vlog_vlogger!($vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
+            &$view,
         )
-    });
+    };
 
-    // polyline!(target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
-    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__line!(
+    // set_view!(target: "my_target", "my_surface", view)
+    (target: $target:expr, $surface:expr, $view:expr) => {
+        $crate::__private_api::set_view(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $target,
             $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
+            &$view,
         )
-    });
+    };
 
-    // polyline!("my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
-    ($surface:expr, $($arg:tt)+) => (
-        $crate::__line!(
+    // set_view!("my_surface", view)
+    ($surface:expr, $view:expr) => {
+        $crate::__private_api::set_view(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $crate::__default_target!(),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
+            &$view,
         )
-    )
+    };
 }
 
-/// Sends an arrow or multiple arrows to the vlogger.
+/// Draw the visuals of a block inside a named group.
+///
+/// The group is pushed with [`VLog::push_group`](crate::VLog::push_group) before the block and popped with
+/// [`VLog::pop_group`](crate::VLog::pop_group) after it, even when the block is left early with `return`,
+/// `break`, `?` or a panic. The records drawn inside the block belong to the group.
+/// Nested groups form a path, e.g. `"physics"` and `"broadphase"` below.
+/// Groups are independent of surfaces and can contain draws to any number of them.
+/// The macro evaluates to the value of the block.
 ///
 /// # Examples
 ///
 /// ```
-/// use v_log::arrow;
+/// use v_log::{group, point};
 ///
-/// // Points must be of the same type for arrays to work,
-/// // but are only required to implement IntoIterator.
-/// // They can be arbitrary dimension, but only the first 3 are used.
-/// let pos = [3.234, -1.223];
-/// let dir = [0.7071, 0.7071];
-/// let space = [[1.0, 0.0], [0.0, 1.0]];
+/// group!("physics" => {
+///     group!("broadphase" => {
+///         point!("main_surface", [0.0, 0.0], 5.0, Base);
+///     });
+///     group!("narrowphase" => {
+///         point!("main_surface", [1.0, 0.0], 5.0, Base);
+///     });
+/// });
+/// ```
 ///
-/// // Draw an arrow from `pos` pointing in direction `dir` with length 50.0 and thickness 5.0.
-/// // If `dir` has length zero or zero length is specified, a zero length arrow will get sent.
-/// // The label may get displayed, but the size, position and and format is up
+/// A vlogger which tags records with the group path:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{group, point, Metadata, Record, VLog};
+///
+/// #[derive(Default)]
+/// struct Tagger {
+///     stack: RefCell<Vec<String>>,
+///     records: RefCell<Vec<String>>,
+/// }
+///
+/// impl VLog for Tagger {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         let path = self.stack.borrow().join("/");
+///         self.records.borrow_mut().push(format!("{}: {}", path, record.args()));
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+///     fn push_group(&self, name: &str) {
+///         self.stack.borrow_mut().push(name.to_string());
+///     }
+///     fn pop_group(&self) {
+///         self.stack.borrow_mut().pop();
+///     }
+/// }
+///
+/// fn step(v: &Tagger, hit: bool) -> Option<u32> {
+///     group!(vlogger: v, "physics" => {
+///         group!(vlogger: v, "broadphase" => {
+///             point!(vlogger: v, "main_surface", [0.0, 0.0], 5.0, Base, "o", "pair");
+///             if !hit {
+///                 return None;
+///             }
+///         });
+///         point!(vlogger: v, "main_surface", [1.0, 0.0], 5.0, Base, "o", "contact");
+///         Some(1)
+///     })
+/// }
+///
+/// let tagger = Tagger::default();
+/// assert_eq!(step(&tagger, false), None);
+/// assert_eq!(step(&tagger, true), Some(1));
+/// point!(vlogger: tagger, "main_surface", [0.0, 0.0], 5.0, Base, "o", "outside");
+/// assert_eq!(
+///     *tagger.records.borrow(),
+///     [
+///         "physics/broadphase: pair",
+///         "physics/broadphase: pair",
+///         "physics: contact",
+///         ": outside",
+///     ]
+/// );
+/// ```
+#[macro_export]
+macro_rules! group {
+    // group!(vlogger: my_vlogger, "my_group" => { ... })
+    (vlogger: $vlogger:expr, $name:expr => $body:block) => {{
+        let _vlog_group_guard =
+            $crate::__private_api::group($crate::__vlog_vlogger!($vlogger), $name);
+        $body
+    }};
+
+    // group!("my_group" => { ... })
+    ($name:expr => $body:block) => {{
+        let _vlog_group_guard =
+            $crate::__private_api::group($crate::__vlog_vlogger!(__vlog_global_vlogger), $name);
+        $body
+    }};
+}
+
+/// Begin an atomic frame on a surface.
+///
+/// Everything drawn to the surface until the matching [`end_frame!`] forms one frame,
+/// which the vlogger may display only once the frame ends, to avoid flicker.
+/// A [`clear!`] inside the frame is part of the frame, so the old content stays
+/// visible until [`end_frame!`]. Vloggers without frame support display the draws
+/// immediately. Unlike [`transaction!`], the draws don't have to happen in a closure.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{begin_frame, clear, end_frame, point};
+///
+/// begin_frame!("main_surface");
+/// clear!("main_surface");
+/// point!("main_surface", [0.0, 0.0], 5.0, Base);
+/// end_frame!("main_surface");
+/// ```
+///
+/// A vlogger which displays a frame only when it ends:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{begin_frame, clear, end_frame, point, Metadata, Record, VLog};
+///
+/// #[derive(Default)]
+/// struct Screen {
+///     shown: RefCell<Vec<String>>,
+///     pending: RefCell<Option<Vec<String>>>,
+/// }
+///
+/// impl VLog for Screen {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         match &mut *self.pending.borrow_mut() {
+///             Some(pending) => pending.push(record.args().to_string()),
+///             None => self.shown.borrow_mut().push(record.args().to_string()),
+///         }
+///     }
+///     fn clear(&self, _: &str) {
+///         match &mut *self.pending.borrow_mut() {
+///             Some(pending) => pending.clear(),
+///             None => self.shown.borrow_mut().clear(),
+///         }
+///     }
+///     fn flush(&self) {}
+///     fn begin_frame(&self, _: &str) {
+///         *self.pending.borrow_mut() = Some(Vec::new());
+///     }
+///     fn end_frame(&self, _: &str) {
+///         if let Some(pending) = self.pending.borrow_mut().take() {
+///             *self.shown.borrow_mut() = pending;
+///         }
+///     }
+/// }
+///
+/// let screen = Screen::default();
+/// point!(vlogger: screen, "main_surface", [0.0, 0.0], 5.0, Base, "o", "old");
+/// begin_frame!(vlogger: screen, "main_surface");
+/// clear!(vlogger: screen, "main_surface");
+/// point!(vlogger: screen, "main_surface", [1.0, 0.0], 5.0, Base, "o", "new");
+/// // the old frame is still shown.
+/// assert_eq!(*screen.shown.borrow(), ["old"]);
+/// end_frame!(vlogger: screen, "main_surface");
+/// assert_eq!(*screen.shown.borrow(), ["new"]);
+/// ```
+#[macro_export]
+macro_rules! begin_frame {
+    // begin_frame!(vlogger: my_vlogger, target: "my_target", "my_surface")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr) => {
+        $crate::__private_api::begin_frame($crate::__vlog_vlogger!($vlogger), $target, $surface)
+    };
+
+    // begin_frame!(vlogger: my_vlogger, "my_surface")
+    (vlogger: $vlogger:expr, $surface:expr) => {
+        $crate::__private_api::begin_frame(
+            $crate::__vlog_vlogger!($vlogger),
+            $crate::__default_target!(),
+            $surface,
+        )
+    };
+
+    // begin_frame!(target: "my_target", "my_surface")
+    (target: $target:expr, $surface:expr) => {
+        $crate::__private_api::begin_frame(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $target,
+            $surface,
+        )
+    };
+
+    // begin_frame!("my_surface")
+    ($surface:expr) => {
+        $crate::__private_api::begin_frame(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $crate::__default_target!(),
+            $surface,
+        )
+    };
+}
+
+/// End an atomic frame on a surface, started with [`begin_frame!`].
+///
+/// See [`begin_frame!`] for details.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{begin_frame, end_frame, point};
+///
+/// begin_frame!("main_surface");
+/// point!("main_surface", [0.0, 0.0], 5.0, Base);
+/// end_frame!("main_surface");
+/// ```
+#[macro_export]
+macro_rules! end_frame {
+    // end_frame!(vlogger: my_vlogger, target: "my_target", "my_surface")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr) => {
+        $crate::__private_api::end_frame($crate::__vlog_vlogger!($vlogger), $target, $surface)
+    };
+
+    // end_frame!(vlogger: my_vlogger, "my_surface")
+    (vlogger: $vlogger:expr, $surface:expr) => {
+        $crate::__private_api::end_frame(
+            $crate::__vlog_vlogger!($vlogger),
+            $crate::__default_target!(),
+            $surface,
+        )
+    };
+
+    // end_frame!(target: "my_target", "my_surface")
+    (target: $target:expr, $surface:expr) => {
+        $crate::__private_api::end_frame(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $target,
+            $surface,
+        )
+    };
+
+    // end_frame!("my_surface")
+    ($surface:expr) => {
+        $crate::__private_api::end_frame(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $crate::__default_target!(),
+            $surface,
+        )
+    };
+}
+
+/// Logs a message to the vlogger.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::message;
+///
+/// let pos = [3.234, -1.223];
+///
+/// message!("main_surface", color: Healthy, "Correct position");
+/// message!("main_surface", "Position is: x: {}, y: {}", pos[0], pos[1]);
+/// ```
+///
+/// Text which is already formatted, e.g. user controlled text, can be passed with
+/// the `msg:` keyword. It is displayed verbatim instead of being used as a format string.
+/// The keyword works for the text of all vlogging macros.
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<String>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.args().to_string()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{label, message};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// let text = String::from("set {} is empty");
+///
+/// message!(vlogger: capture, "main_surface", msg: text);
+/// message!(vlogger: capture, "main_surface", color: Warn, msg: &text);
+/// label!(vlogger: capture, "main_surface", [1.0, 2.0], msg: "{}");
+/// assert_eq!(*capture.0.borrow(), ["set {} is empty", "set {} is empty", "{}"]);
+/// ```
+///
+/// Used as a format string, the braces would be interpreted as an argument:
+///
+/// ```compile_fail
+/// use v_log::message;
+///
+/// message!("main_surface", "set {} is empty");
+/// ```
+#[macro_export]
+macro_rules! message {
+    // message!(vlogger: my_vlogger, target: "my_target", "my_surface", color: Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__message!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // message!(vlogger: my_vlogger, "my_surface", color: Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__message!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // message!(target: "my_target", "my_surface", color: Base, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__message!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // message!("my_surface", color: Base, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__message!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a point to the vlogger.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::point;
+///
+/// let pos1 = [3.234, -1.223];
+/// let pos2 = [2.713, 0.577];
+///
+/// point!("main_surface", pos1, 5.0, Base, "o", "Position is: x: {}, y: {}", pos1[0], pos1[1]);
+/// point!("main_surface", pos2, 5.0, Base);
+/// // A size of 0.0 (or less) lets the vlogger choose its default size.
+/// point!("main_surface", pos2, 0.0, Base);
+/// // Draw a half transparent point, see `Record::opacity`.
+/// point!("main_surface", pos2, 5.0, Base, opacity: 0.5);
+/// ```
+///
+/// The `show_pos` keyword appends the coordinates of the point to the message:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<String>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.args().to_string()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::point;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// point!(vlogger: capture, "s", [1.0, 2.0], 3.0, Base, "o", show_pos, "p");
+/// point!(vlogger: capture, "s", [1.0, 2.0, 0.5], 3.0, Base, show_pos);
+/// assert_eq!(*capture.0.borrow(), ["p (1.00, 2.00)", "(1.00, 2.00, 0.50)"]);
+/// ```
+///
+/// A computed style can switch the draw off with [`PointStyle::None`](crate::PointStyle::None),
+/// which skips the point entirely:
+///
+/// ```
+/// # use std::cell::Cell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Counter(Cell<usize>);
+/// # impl VLog for Counter {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) { self.0.set(self.0.get() + 1) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{point, polyline, LineStyle, PointStyle};
+///
+/// let counter = Counter(Cell::new(0));
+/// for show in [false, true] {
+///     let style = if show { PointStyle::Point } else { PointStyle::None };
+///     point!(vlogger: counter, "main_surface", [1.0, 2.0], 5.0, Base, style);
+///     let style = if show { LineStyle::Simple } else { LineStyle::None };
+///     polyline!(vlogger: counter, "main_surface", ([0.0, 0.0], [1.0, 2.0]), 1.0, Base, style);
+/// }
+/// assert_eq!(counter.0.get(), 2);
+/// ```
+#[macro_export]
+macro_rules! point {
+    // point!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__point!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // point!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__point!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // point!(target: "my_target", "my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__point!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // point!("my_surface", [1.0, 2.0], 5.0, Base, "o", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__point!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a label/text annotation to the vlogger.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::label;
+///
+/// let pos = [3.234, -1.223];
+///
+/// label!("main_surface", pos, (12.0, Base, "<"), "Position is: x: {}, y: {}", pos[0], pos[1]);
+/// label!("main_surface", pos, "Flexible position"); // with size 12.0, flexible alignment and "Base" color
+/// label!("main_surface", pos, show_pos, "Position is:"); // "Position is: (3.23, -1.22)"
+/// ```
+///
+/// Line breaks (`'\n'`) in the text start a new line. Long text can be wrapped by
+/// adding a maximum width in world units, like the position, to the group, optionally
+/// followed by a [`LabelStyle`](crate::LabelStyle). Without it, lines are not wrapped.
+///
+/// ```
+/// use v_log::label;
+///
+/// let pos = [3.234, -1.223];
+///
+/// label!("main_surface", pos, (12.0, Base, "<"), "x: {}\ny: {}", pos[0], pos[1]);
+/// label!("main_surface", pos, (12.0, Base, "<", 2.0), "wrapped at word boundaries");
+/// label!("main_surface", pos, (12.0, Base, "<", 2.0, Clip), "cut off at 2.0 world units");
+/// ```
+///
+/// Labels are horizontal by default. They can be rotated counterclockwise
+/// with the `rot:` keyword in radians, e.g. to annotate angled features:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{label, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// label!(vlogger: capture, "main_surface", [1.0, 1.0], rot: 0.5, "ramp");
+/// label!(vlogger: capture, "main_surface", [1.0, 1.0], "flat");
+///
+/// let visuals = capture.0.borrow();
+/// assert!(matches!(visuals[0], Visual::Label { rotation, .. } if rotation == 0.5));
+/// assert!(matches!(visuals[1], Visual::Label { rotation, .. } if rotation == 0.0));
+/// ```
+#[macro_export]
+macro_rules! label {
+    // label!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__label!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // label!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__label!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // label!(target: "my_target", "my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__label!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // label!("my_surface", [1.0, 2.0], (12.0, Base, "<"), "a {} label", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__label!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends an open or closed polyline to the vlogger.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::polyline;
+///
+/// // Points must be of the same type for arrays to work,
+/// // but are only required to implement IntoIterator.
+/// // They can be arbitrary dimension, but only the first 3 are used.
+/// let pos1 = [3.234, -1.223];
+/// let pos2 = [2.713, 0.577];
+/// let pos3 = [6.283, 0.692];
+///
+/// // Draw a single line with thickness 5.0 and color `Base` from pos1 to pos2.
+/// // The label may get displayed, but the size, position and and format is up
+/// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
+/// polyline!("main_surface", (pos1, pos2), 5.0, Base, "--", "Position is: x: {}, y: {}", pos1[0], pos1[1]);
+/// polyline!("main_surface", (pos1, pos2), 5.0, Base, "->");
+/// polyline!("main_surface", (pos1, pos2), 5.0, Base);
+/// // Draw two connected lines (polyline). These can not be labelled in
+/// // the macro, as a label on polylines is hard to control in implementations.
+/// // If you need a label, consider drawing the first segment with a label.
+/// polyline!("main_surface", [pos1, pos2, pos3], 5.0, Base, "--");
+/// polyline!("main_surface", [pos1, pos2, pos3], 5.0, Base);
+/// // Draw a triangle (polygon). These can be labelled and the label is placed
+/// // in the centeroid of them. Note, this may not be inside of the polygon.
+/// // The textsize of the label must be specified before the text.
+/// polyline!("main_surface", closed: [pos1, pos2, pos3], 5.0, Base, "--", 12.0, "Position is: x: {}, y: {}", pos1[0], pos1[1]);
+/// polyline!("main_surface", closed: [pos1, pos2, pos3], 5.0, Base, "_>");
+/// polyline!("main_surface", closed: [pos1, pos2, pos3], 5.0, Base);
+/// ```
+///
+/// The thickness is usually interpreted in pixels. With the `world_width:` keyword
+/// after the style, it can be set in world units instead, which takes precedence:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<(f64, Visual)>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push((record.size(), record.visual().clone())) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{polyline, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// polyline!(vlogger: capture, "main_surface", ([0.0, 0.0], [1.0, 0.5]), 5.0, Base, "--", world_width: 0.1, "road");
+/// polyline!(vlogger: capture, "main_surface", closed: [[0.0, 0.0], [1.0, 0.5], [1.5, 1.5]], 5.0, Base, world_width: 0.1);
+/// polyline!(vlogger: capture, "main_surface", ([0.0, 0.0], [1.0, 0.5]), 5.0, Base);
+///
+/// let visuals = capture.0.borrow();
+/// let (last, lines) = visuals.split_last().unwrap();
+/// for (size, visual) in lines.iter().filter(|(_, v)| matches!(v, Visual::Line { .. })) {
+///     // the size is kept, but vloggers should use the world thickness instead.
+///     assert_eq!(*size, 5.0);
+///     assert!(matches!(visual, Visual::Line { world_thickness: Some(w), .. } if *w == 0.1));
+/// }
+/// assert!(matches!(last.1, Visual::Line { world_thickness: None, .. }));
+/// ```
+///
+/// Every form is an expression, so it can be used e.g. in a `match` arm,
+/// and it doesn't introduce variables into the surrounding scope:
+///
+/// ```
+/// use v_log::polyline;
+///
+/// let last = [1.0, 1.0];
+/// let closed = true;
+/// match closed {
+///     true => polyline!("main_surface", [[0.0, 0.0], [1.0, 0.0], last], 1.0, Base),
+///     false => polyline!("main_surface", ([0.0, 0.0], last), 1.0, Base),
+/// }
+/// polyline!("main_surface", [[0.0, 0.0], [0.0, 1.0], last], 1.0, Base, "--");
+/// assert_eq!(last, [1.0, 1.0]);
+/// let _ = polyline!("main_surface", [[0.0, 0.0], [0.0, 1.0], last], 1.0, Base);
+/// ```
+#[macro_export]
+macro_rules! polyline {
+    // polyline!(vlogger: my_vlogger, target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__line!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // polyline!(vlogger: my_vlogger, "my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__line!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // polyline!(target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__line!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // polyline!("my_surface", ([1.0, 2.0], [1.0, 3.0]), 5.0, Base, "-", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__line!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a ray, i.e. a half-line from an origin along a direction, to the vlogger.
+///
+/// The grammar is parallel to a single line of [`polyline!`](crate::polyline), with
+/// the origin and direction in place of the two points. The vlogger clips the ray
+/// to the visible bounds of the surface, so no far endpoint needs to be computed.
+/// A zero direction draws nothing. With the `std` feature, the direction is normalized.
+/// See [`Visual::Ray`](crate::Visual::Ray).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::ray;
+///
+/// let origin = [3.234, -1.223];
+/// let direction = [1.0, 0.5];
+///
+/// // Draw a ray with thickness 2.0 and color `Base`.
+/// ray!("main_surface", (origin, direction), 2.0, Base, "--", "hit at distance {}", 4.2);
+/// ray!("main_surface", (origin, direction), 2.0, Warn, "--");
+/// ray!("main_surface", (origin, direction), 2.0, Base);
+/// // A zero direction draws nothing.
+/// ray!("main_surface", (origin, [0.0, 0.0]), 2.0, Base);
+/// ```
+#[macro_export]
+macro_rules! ray {
+    // ray!(vlogger: my_vlogger, target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__ray!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // ray!(vlogger: my_vlogger, "my_surface", ([1.0, 2.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__ray!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // ray!(target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__ray!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // ray!("my_surface", ([1.0, 2.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__ray!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a directed path through a list of points to the vlogger.
+///
+/// Every segment is drawn as an arrow to show the direction of travel, e.g. of the
+/// iterates of an optimizer. If a line style is given, it is used for all segments
+/// except for the last one, which is always an arrow. In contrast to [`polyline!`],
+/// the points may be given as a runtime slice and paths with less than two points draw nothing.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::path;
+///
+/// let iterates = vec![[0.0, 0.0], [1.0, 0.5], [1.5, 1.5]];
+///
+/// // Draw all segments as arrows with thickness 2.0 and color `Info`.
+/// path!("main_surface", &iterates, 2.0, Info);
+/// // Draw dashed segments with an arrow only at the end.
+/// path!("main_surface", &iterates, 2.0, Info, "--");
+/// ```
+///
+/// A path through 3 points consists of 2 arrows:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{path, LineStyle, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// path!(vlogger: capture, "main_surface", &[[0.0, 0.0], [1.0, 0.5], [1.5, 1.5]], 2.0, Info);
+///
+/// let visuals = capture.0.borrow();
+/// assert_eq!(visuals.len(), 2);
+/// assert!(visuals
+///     .iter()
+///     .all(|v| matches!(v, Visual::Line { style: LineStyle::Arrow, .. })));
+/// ```
+#[macro_export]
+macro_rules! path {
+    // path!(vlogger: my_vlogger, target: "my_target", "my_surface", &points, 2.0, Info, "-")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__path!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // path!(vlogger: my_vlogger, "my_surface", &points, 2.0, Info, "-")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__path!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // path!(target: "my_target", "my_surface", &points, 2.0, Info, "-")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__path!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // path!("my_surface", &points, 2.0, Info, "-")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__path!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends an axis aligned box to the vlogger.
+///
+/// The box is given by two opposite corners in any order, they get sorted per axis.
+/// On 2D surfaces, the z-coordinates are ignored and the box is drawn as a rectangle.
+/// The style controls the edges, e.g. `"-"` for solid and `"--"` for dashed edges.
+/// The remaining arguments are the same as for [`polyline!`].
+/// See [`Visual::Aabb`](crate::Visual::Aabb).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::aabb;
+///
+/// // Corners must be of the same type for arrays to work,
+/// // but are only required to implement IntoIterator.
+/// // They can be arbitrary dimension, but only the first 3 are used.
+/// let corner1 = [3.234, -1.223, 0.5];
+/// let corner2 = [2.713, 0.577, 1.5];
+///
+/// // Draw a box with thickness 5.0.
+/// // The label may get displayed, but the size, position and and format is up
+/// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
+/// aabb!("main_surface", (corner1, corner2), 5.0, Base, "--", "node {}", 7);
+/// aabb!("main_surface", (corner1, corner2), 5.0, Base, "-");
+/// aabb!("main_surface", (corner1, corner2), 5.0, Base);
+/// ```
+///
+/// The corners are sorted:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{aabb, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// aabb!(vlogger: capture, "main_surface", ([2.0, 0.0, 1.0], [1.0, 3.0, 0.0]), 1.0, Base);
+///
+/// let visuals = capture.0.borrow();
+/// assert!(matches!(
+///     visuals[0],
+///     Visual::Aabb { min, max, .. } if min == [1.0, 0.0, 0.0] && max == [2.0, 3.0, 1.0]
+/// ));
+/// ```
+#[macro_export]
+macro_rules! aabb {
+    // aabb!(vlogger: my_vlogger, target: "my_target", "my_surface", ([1.0, 2.0], [3.0, 4.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__aabb!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // aabb!(vlogger: my_vlogger, "my_surface", ([1.0, 2.0], [3.0, 4.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__aabb!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // aabb!(target: "my_target", "my_surface", ([1.0, 2.0], [3.0, 4.0]), 5.0, Base, "-", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__aabb!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // aabb!("my_surface", ([1.0, 2.0], [3.0, 4.0]), 5.0, Base, "-", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__aabb!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends coordinate axes to the vlogger, e.g. to check the orientation of a transform.
+///
+/// The axes are given by their origin, optionally `rotation:` as a quaternion
+/// `[x, y, z, w]`, the length of the arrows and their thickness. The rotation is
+/// normalized like for [`obb!`](crate::obb) and defaults to the identity when
+/// omitted. `length` scales all three axes equally. The arrows are colored
+/// [`Color::X`](crate::Color::X), [`Color::Y`](crate::Color::Y) and
+/// [`Color::Z`](crate::Color::Z).
+/// See [`Visual::Axes`](crate::Visual::Axes).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::axes;
+///
+/// let origin = [3.234, -1.223, 0.0];
+/// // rotated by 90 degrees around the z-axis.
+/// let rotation = [0.0, 0.0, 0.7071, 0.7071];
+///
+/// // Draw the world axes with length 1.0 and thickness 2.0.
+/// axes!("main_surface", [0.0, 0.0, 0.0], 1.0, 2.0);
+/// // Draw the axes of a transform.
+/// axes!("main_surface", origin, rotation: rotation, 0.5, 1.0, "body {}", 3);
+/// ```
+#[macro_export]
+macro_rules! axes {
+    // axes!(vlogger: my_vlogger, target: "my_target", "my_surface", origin, rotation: q, 1.0, 1.0, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__axes!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // axes!(vlogger: my_vlogger, "my_surface", origin, rotation: q, 1.0, 1.0, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__axes!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // axes!(target: "my_target", "my_surface", origin, rotation: q, 1.0, 1.0, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__axes!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // axes!("my_surface", origin, rotation: q, 1.0, 1.0, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__axes!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends an oriented box to the vlogger.
+///
+/// The box is given by its center, the half extents along its local axes and its
+/// rotation as a quaternion `[x, y, z, w]`. Like positions, these only need to
+/// implement `IntoIterator`, and a missing `w` is `1.0`. The quaternion gets
+/// normalized if its length deviates from `1.0` by more than `1e-6`.
+/// A zero quaternion is replaced by the identity rotation.
+/// 3D vloggers draw the box as a wireframe, 2D vloggers project the edges onto the xy-plane.
+/// See [`Visual::Obb`](crate::Visual::Obb).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::obb;
+///
+/// let center = [3.234, -1.223, 0.0];
+/// let half_extents = [1.0, 0.5, 0.25];
+/// // rotated by 90 degrees around the z-axis.
+/// let rotation = [0.0, 0.0, 0.7071, 0.7071];
+///
+/// // Draw the box with thickness 2.0.
+/// // The label may get displayed, but the size, position and and format is up
+/// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
+/// obb!("main_surface", center, half_extents, rotation, 2.0, Base, "collider {}", 3);
+/// obb!("main_surface", center, half_extents, rotation, 2.0, Base);
+/// ```
+///
+/// The rotation is normalized:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{obb, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// obb!(vlogger: capture, "main_surface", [0.0, 0.0], [1.0, 1.0], [0.0, 0.0, 2.0, 0.0], 1.0, Base);
+/// obb!(vlogger: capture, "main_surface", [0.0, 0.0], [1.0, 1.0], [0.0, 0.0, 3.0, 4.0], 1.0, Base);
+/// obb!(vlogger: capture, "main_surface", [0.0, 0.0], [1.0, 1.0], [0.0; 4], 1.0, Base);
+///
+/// let visuals = capture.0.borrow();
+/// assert!(matches!(visuals[0], Visual::Obb { rotation, .. } if rotation == [0.0, 0.0, 1.0, 0.0]));
+/// assert!(matches!(visuals[1], Visual::Obb { rotation, .. } if rotation == [0.0, 0.0, 0.6, 0.8]));
+/// assert!(matches!(visuals[2], Visual::Obb { rotation, .. } if rotation == [0.0, 0.0, 0.0, 1.0]));
+/// ```
+#[macro_export]
+macro_rules! obb {
+    // obb!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], [0.5, 0.5], [0.0, 0.0, 0.0, 1.0], 2.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__obb!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // obb!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], [0.5, 0.5], [0.0, 0.0, 0.0, 1.0], 2.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__obb!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // obb!(target: "my_target", "my_surface", [1.0, 2.0], [0.5, 0.5], [0.0, 0.0, 0.0, 1.0], 2.0, Base, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__obb!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // obb!("my_surface", [1.0, 2.0], [0.5, 0.5], [0.0, 0.0, 0.0, 1.0], 2.0, Base, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__obb!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a circular arc or pie sector to the vlogger.
+///
+/// The arc is given by its center, radius and `(start, end)` angles in radians,
+/// counterclockwise from the x-axis. A trailing comma in the angles, `(start, end,)`,
+/// connects the ends of the arc to the center, closing it into a pie sector.
+/// The remaining arguments are the same as for [`polyline!`]. See [`Visual::Arc`](crate::Visual::Arc).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::arc;
+///
+/// // The center can be arbitrary dimension, but only the first 3 are used.
+/// let center = [3.234, -1.223];
+///
+/// // Draw a quarter circle with radius 2.0 and thickness 5.0.
+/// // The label may get displayed, but the size, position and and format is up
+/// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
+/// arc!("main_surface", center, 2.0, (0.0, 1.5708), 5.0, Base, "->", "turn of {} degrees", 90);
+/// arc!("main_surface", center, 2.0, (0.0, 1.5708), 5.0, Base, "--");
+/// arc!("main_surface", center, 2.0, (0.0, 1.5708), 5.0, Base);
+/// // Draw a pie sector in 3D.
+/// arc!("main_surface", [1.0, 2.0, 3.0], 2.0, (0.0, 1.5708,), 5.0, Info);
+/// ```
+///
+/// The trailing comma sets [`sector`](crate::Visual::Arc::sector):
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{arc, LineStyle, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// arc!(vlogger: capture, "main_surface", [1.0, 2.0], 0.5, (0.0, 3.0), 1.0, Base, "--");
+/// arc!(vlogger: capture, "main_surface", [1.0, 2.0], 0.5, (0.0, 3.0,), 1.0, Base);
+///
+/// let visuals = capture.0.borrow();
+/// assert!(matches!(
+///     visuals[0],
+///     Visual::Arc { cx, cy, radius, end_angle, style: LineStyle::Dashed, sector: false, .. }
+///         if cx == 1.0 && cy == 2.0 && radius == 0.5 && end_angle == 3.0
+/// ));
+/// assert!(matches!(visuals[1], Visual::Arc { sector: true, .. }));
+/// ```
+#[macro_export]
+macro_rules! arc {
+    // arc!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], 2.0, (0.0, 1.0), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__arc!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // arc!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], 2.0, (0.0, 1.0), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__arc!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // arc!(target: "my_target", "my_surface", [1.0, 2.0], 2.0, (0.0, 1.0), 5.0, Base, "-", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__arc!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // arc!("my_surface", [1.0, 2.0], 2.0, (0.0, 1.0), 5.0, Base, "-", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__arc!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a cubic Bézier curve to the vlogger.
+///
+/// The curve starts at the first point, ends at the last point and is pulled towards
+/// the two control points in between. The vlogger tessellates the curve, so it is
+/// a single record. The remaining arguments are the same as for [`polyline!`].
+/// See [`Visual::Bezier`](crate::Visual::Bezier).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::bezier;
+///
+/// // Points must be of the same type for arrays to work,
+/// // but are only required to implement IntoIterator.
+/// // They can be arbitrary dimension, but only the first 3 are used.
+/// let start = [0.0, 0.0];
+/// let end = [4.0, 0.0];
+///
+/// // Draw a curve with thickness 5.0, which leaves the start upwards
+/// // and arrives at the end from below.
+/// bezier!("main_surface", (start, [0.0, 2.0], [4.0, -2.0], end), 5.0, Base, "->", "planned path {}", 1);
+/// bezier!("main_surface", (start, [0.0, 2.0], [4.0, -2.0], end), 5.0, Base, "--");
+/// bezier!("main_surface", (start, [0.0, 2.0], [4.0, -2.0], end), 5.0, Base);
+/// ```
+///
+/// Missing coordinates are `0.0`:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{bezier, LineStyle, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// bezier!(vlogger: capture, "main_surface", ([0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]), 1.0, Base);
+///
+/// let visuals = capture.0.borrow();
+/// assert!(matches!(
+///     visuals[0],
+///     Visual::Bezier { p1, p3, style: LineStyle::Simple, .. }
+///         if p1 == [0.0, 1.0, 0.0] && p3 == [1.0, 0.0, 0.0]
+/// ));
+/// ```
+#[macro_export]
+macro_rules! bezier {
+    // bezier!(vlogger: my_vlogger, target: "my_target", "my_surface", ([0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__bezier!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // bezier!(vlogger: my_vlogger, "my_surface", ([0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__bezier!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // bezier!(target: "my_target", "my_surface", ([0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__bezier!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // bezier!("my_surface", ([0.0, 0.0], [0.0, 1.0], [1.0, 1.0], [1.0, 0.0]), 5.0, Base, "-", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__bezier!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a reference grid through the origin to the vlogger.
+///
+/// The vlogger draws lines every `spacing` units along both axes of the plane,
+/// out to `±extent`. The plane is the xy-plane unless given as `plane: XZ` or
+/// `plane: YZ`. If `spacing <= 0.0`, nothing is drawn. The color sets the color
+/// of the grid lines and defaults to `Base`. A message can only follow a color.
+/// See [`Visual::Grid`](crate::Visual::Grid).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::grid;
+///
+/// // Draw lines at -10, -9, ..., 10 along the x- and y-axis.
+/// grid!("main_surface", spacing: 1.0, extent: 10.0);
+/// // Draw a coarser grid on the floor of a 3D surface.
+/// grid!("main_surface", spacing: 5.0, extent: 50.0, plane: XZ, 0x80808080);
+/// grid!("main_surface", spacing: 0.1, extent: 1.0, Info, "unit square {}", 1);
+/// ```
+#[macro_export]
+macro_rules! grid {
+    // grid!(vlogger: my_vlogger, target: "my_target", "my_surface", spacing: 1.0, extent: 10.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__grid!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // grid!(vlogger: my_vlogger, "my_surface", spacing: 1.0, extent: 10.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__grid!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // grid!(target: "my_target", "my_surface", spacing: 1.0, extent: 10.0, Base, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__grid!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // grid!("my_surface", spacing: 1.0, extent: 10.0, Base, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__grid!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends an arrow or multiple arrows to the vlogger.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::arrow;
+///
+/// // Points must be of the same type for arrays to work,
+/// // but are only required to implement IntoIterator.
+/// // They can be arbitrary dimension, but only the first 3 are used.
+/// let pos = [3.234, -1.223];
+/// let dir = [0.7071, 0.7071];
+/// let space = [[1.0, 0.0], [0.0, 1.0]];
+///
+/// // Draw an arrow from `pos` pointing in direction `dir` with length 50.0 and thickness 5.0.
+/// // If `dir` has length zero or zero length is specified, a zero length arrow will get sent.
+/// // The label may get displayed, but the size, position and and format is up
+/// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
+/// # #[cfg(feature = "std")]
+/// arrow!("main_surface", pos, dir, (50.0), 5.0, Base, "Position is: x: {}, y: {}", pos[0], pos[1]);
+/// # #[cfg(feature = "std")]
+/// arrow!("main_surface", pos, dir, (50.0), 5.0, Base);
+/// // Draw an arrow from `pos` pointing in direction `dir` with the length of `dir` and thickness 5.0.
+/// arrow!("main_surface", pos, dir, 5.0, Base, "Position is: x: {}, y: {}", pos[0], pos[1]);
+/// arrow!("main_surface", pos, dir, 5.0, Base);
+/// // Draw multiple arrows from `pos` and scale them with 50.0.
+/// // The colors are X, Y, Z and then "Missing" for all others.
+/// arrow!("main_surface", pos, space, (50.0), 5.0);
+/// ```
+#[macro_export]
+macro_rules! arrow {
+    // arrow!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__arrow!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // arrow!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__arrow!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // arrow!(target: "my_target", "my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__arrow!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // arrow!("my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__arrow!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends an ellipse to the vlogger, e.g. to show a covariance or uncertainty region.
+///
+/// The ellipse is given by its center, the radii `(rx, ry)` along the x- and y-axis
+/// and a counterclockwise rotation in radians. The rotation is applied in the plane
+/// of the surface, also for 3D positions. Negative radii are treated as their absolute
+/// value. With `filled:` in front of the center, the area of the ellipse is filled.
+/// The outline is drawn with the default thickness of the vlogger.
+/// See [`Visual::Ellipse`](crate::Visual::Ellipse).
+///
+/// # Examples
+///
+/// ```
+/// use v_log::ellipse;
+///
+/// // The center can be arbitrary dimension, but only the first 3 are used.
+/// let mean = [3.234, -1.223];
+///
+/// // Draw an ellipse with radii 2.0 and 0.5, rotated by 45 degrees.
+/// // The label may get displayed, but the size, position and and format is up
+/// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
+/// ellipse!("main_surface", mean, (2.0, 0.5), 0.7854, Info, "estimate {}", 1);
+/// ellipse!("main_surface", mean, (2.0, 0.5), 0.7854, Info);
+/// ellipse!("main_surface", filled: mean, (2.0, 0.5), 0.0, 0x2196f340);
+/// ```
+///
+/// The radii are stored as absolute values:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{ellipse, Visual};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// ellipse!(vlogger: capture, "main_surface", filled: [1.0, 2.0], (-2.0, 0.5), 0.5, Info);
+///
+/// let visuals = capture.0.borrow();
+/// assert!(matches!(
+///     visuals[0],
+///     Visual::Ellipse { cx, rx, ry, rotation, filled: true, .. }
+///         if cx == 1.0 && rx == 2.0 && ry == 0.5 && rotation == 0.5
+/// ));
+/// ```
+#[macro_export]
+macro_rules! ellipse {
+    // ellipse!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], (2.0, 1.0), 0.5, Info, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__ellipse!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // ellipse!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], (2.0, 1.0), 0.5, Info, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__ellipse!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // ellipse!(target: "my_target", "my_surface", [1.0, 2.0], (2.0, 1.0), 0.5, Info, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__ellipse!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // ellipse!("my_surface", [1.0, 2.0], (2.0, 1.0), 0.5, Info, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__ellipse!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a point with error bars to the vlogger.
+///
+/// The point is drawn at the position together with a whisker line for each
+/// direction of the error extents, so `(ex, ey)` draws four whiskers from
+/// `x - ex` to `x + ex` and `y - ey` to `y + ey`. In 3D, a third extent `ez`
+/// adds two whiskers along the z-axis, otherwise the bars lie in the plane of the point.
+/// The size is the diameter of the point and the thickness of the whiskers,
+/// which all share the color. The message is attached to the point.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::errorbar;
+///
+/// let pos = [3.234, -1.223];
+/// errorbar!("main_surface", pos, (0.5, 0.2), 2.0, Info);
+/// errorbar!("main_surface", pos, (0.5, 0.2), 2.0, Info, "mean of {} samples", 10);
+/// // 3D error bars with an extent in z.
+/// errorbar!("main_surface", [1.0, 2.0, 3.0], (0.5, 0.2, 0.1), 2.0, Info);
+/// ```
+///
+/// The point is followed by the whisker lines:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::errorbar;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// errorbar!(vlogger: capture, "main_surface", [1.0, 2.0], (0.5, 0.25), 2.0, Info);
+/// {
+///     let visuals = capture.0.borrow();
+///     assert_eq!(visuals.len(), 5);
+///     assert!(matches!(visuals[0], Visual::Point { x, y, .. } if x == 1.0 && y == 2.0));
+///     assert!(matches!(visuals[1], Visual::Line { x2, y2, .. } if x2 == 1.5 && y2 == 2.0));
+///     assert!(matches!(visuals[4], Visual::Line { x2, y2, .. } if x2 == 1.0 && y2 == 1.75));
+/// }
+///
+/// capture.0.borrow_mut().clear();
+/// errorbar!(vlogger: capture, "main_surface", [1.0, 2.0, 3.0], (0.5, 0.25, 1.0), 2.0, Info);
+/// let visuals = capture.0.borrow();
+/// assert_eq!(visuals.len(), 7);
+/// assert!(matches!(visuals[6], Visual::Line { z2, .. } if z2 == 2.0));
+/// ```
+#[macro_export]
+macro_rules! errorbar {
+    // errorbar!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], (0.1, 0.2), 2.0, Info, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__errorbar!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // errorbar!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], (0.1, 0.2), 2.0, Info, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__errorbar!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // errorbar!(target: "my_target", "my_surface", [1.0, 2.0], (0.1, 0.2), 2.0, Info, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__errorbar!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // errorbar!("my_surface", [1.0, 2.0], (0.1, 0.2), 2.0, Info, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__errorbar!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a smooth spline through a list of points to the vlogger.
+///
+/// The spline is a cardinal spline with the given tension, where `0.0` is the
+/// classic Catmull–Rom spline. The vlogger is responsible for the tessellation.
+/// Like for [`polyline!`](crate::polyline), the points can be given as a list,
+/// optionally with `closed:` in front, or in parentheses, where a trailing comma
+/// closes the spline. A closed spline wraps from the last point back to the first.
+/// Splines with less than two points draw nothing.
+/// See [`Visual::Spline`](crate::Visual::Spline).
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use v_log::spline;
+///
+/// // Points must be of the same type for arrays to work,
+/// // but are only required to implement IntoIterator.
+/// // They can be arbitrary dimension, but only the first 3 are used.
+/// let pos1 = [3.234, -1.223];
+/// let pos2 = [2.713, 0.577];
+/// let pos3 = [6.283, 0.692];
+///
+/// // Draw a Catmull–Rom spline (tension 0.0) with thickness 5.0 and color `Base`.
+/// // The label may get displayed, but the size, position and and format is up
 /// // to the vlogger implementation. E.g. it may only be displayed as a tooltip.
+/// spline!("main_surface", [pos1, pos2, pos3], 0.0, 5.0, Base, "--", "Spline through {} points", 3);
+/// spline!("main_surface", [pos1, pos2, pos3], 0.0, 5.0, Base, "->");
+/// spline!("main_surface", [pos1, pos2, pos3], 0.5, 5.0, Base);
+/// // Draw the same open spline and a closed loop through the points.
+/// spline!("main_surface", (pos1, pos2, pos3), 0.0, 5.0, Base);
+/// spline!("main_surface", (pos1, pos2, pos3,), 0.0, 5.0, Base, "-", "loop");
+/// spline!("main_surface", closed: [pos1, pos2, pos3], 0.0, 5.0, Base);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// The points and the tension are stored in the visual:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::spline;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// spline!(vlogger: capture, "main_surface", [[0.0, 0.0], [1.0, 2.0]], 0.25, 1.0, Base);
+///
+/// let visuals = capture.0.borrow();
+/// match &visuals[0] {
+///     Visual::Spline { points, tension, .. } => {
+///         assert_eq!(points, &[[0.0, 0.0, 0.0], [1.0, 2.0, 0.0]]);
+///         assert_eq!(*tension, 0.25);
+///     }
+///     _ => panic!("expected a spline"),
+/// }
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// A spline requires at least two points, otherwise nothing is drawn:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::spline;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// spline!(vlogger: capture, "main_surface", [[0.0, 0.0]], 0.0, 1.0, Base);
+/// assert!(capture.0.borrow().is_empty());
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! spline {
+    // spline!(vlogger: my_vlogger, target: "my_target", "my_surface", [[1.0, 2.0], [1.0, 3.0]], 0.0, 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__spline!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // spline!(vlogger: my_vlogger, "my_surface", [[1.0, 2.0], [1.0, 3.0]], 0.0, 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__spline!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // spline!(target: "my_target", "my_surface", [[1.0, 2.0], [1.0, 3.0]], 0.0, 5.0, Base, "-", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__spline!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // spline!("my_surface", [[1.0, 2.0], [1.0, 3.0]], 0.0, 5.0, Base, "-", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__spline!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a raster image to the vlogger, e.g. a debug texture.
+///
+/// The image is given by the anchor position of its upper left corner, its size
+/// `(width, height)` in pixels, an [`ImageFormat`](crate::ImageFormat) and the bytes
+/// of the pixels, row by row from the top. The bytes are copied into the record.
+/// `width * height * bytes_per_pixel` should equal the number of bytes,
+/// otherwise vloggers may ignore the image.
+/// See [`Visual::Image`](crate::Visual::Image).
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use v_log::image;
+///
+/// // A 2x2 checkerboard.
+/// let tile = [0u8, 255, 255, 0];
+/// image!("main_surface", [1.0, 2.0], (2, 2), Gray8, &tile);
+/// image!("main_surface", [1.0, 2.0], (2, 2), Gray8, &tile, "heightmap tile {}", 7);
+///
+/// let pixels = vec![255u8; 4 * 16 * 16];
+/// image!("main_surface", [5.0, 2.0, 1.0], (16, 16), Rgba8, &pixels);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// The bytes are stored in the visual:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{image, ImageFormat};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// image!(vlogger: capture, "main_surface", [1.0, 2.0], (1, 2), ImageFormat::Gray8, &[10, 20]);
+///
+/// let visuals = capture.0.borrow();
+/// match &visuals[0] {
+///     Visual::Image { width, height, data, .. } => {
+///         assert_eq!((*width, *height), (1, 2));
+///         assert_eq!(data, &[10, 20]);
+///     }
+///     _ => panic!("expected an image"),
+/// }
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! image {
+    // image!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], (16, 16), Rgba8, &data, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__image!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // image!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], (16, 16), Rgba8, &data, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__image!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // image!(target: "my_target", "my_surface", [1.0, 2.0], (16, 16), Rgba8, &data, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__image!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // image!("my_surface", [1.0, 2.0], (16, 16), Rgba8, &data, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__image!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends many points sharing one style to the vlogger in a single record.
+///
+/// The grammar is the same as for [`point!`](crate::point), but with a list of
+/// positions instead of a single one. The whole cloud is a single vlog call instead
+/// of one per point. All points share the size, color and style, so clouds with
+/// different colors need one call per color.
+/// See [`Visual::PointCloud`](crate::Visual::PointCloud).
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use v_log::point_cloud;
+///
+/// let samples: Vec<[f64; 3]> = (0..1000).map(|i| [i as f64, (i % 7) as f64, 0.0]).collect();
+///
+/// // Points are only required to implement IntoIterator, like for `point!`.
+/// point_cloud!("main_surface", samples.iter().copied(), 2.0, Base);
+/// point_cloud!("main_surface", samples, 2.0, Info, "x", "{} samples", 1000);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// Compared to a loop of [`point!`](crate::point), the vlogger is called once
+/// instead of once per point:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// # use std::cell::Cell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # #[derive(Default)]
+/// # struct Count { enabled: Cell<usize>, vlog: Cell<usize> }
+/// # impl VLog for Count {
+/// #     fn enabled(&self, _: &Metadata) -> bool { self.enabled.set(self.enabled.get() + 1); true }
+/// #     fn vlog(&self, _: &Record) { self.vlog.set(self.vlog.get() + 1) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use std::time::Instant;
+/// use v_log::{point, point_cloud};
+///
+/// let points: Vec<[f64; 3]> = (0..10_000).map(|i| [i as f64, 0.0, 0.0]).collect();
+///
+/// let per_point = Count::default();
+/// let start = Instant::now();
+/// for p in &points {
+///     point!(vlogger: per_point, "main_surface", *p, 2.0, Base);
+/// }
+/// let loop_time = start.elapsed();
+///
+/// let cloud = Count::default();
+/// let start = Instant::now();
+/// point_cloud!(vlogger: cloud, "main_surface", points.iter().copied(), 2.0, Base);
+/// let cloud_time = start.elapsed();
+///
+/// // `enabled` is checked before building each record and by the default `enabled_record`.
+/// assert_eq!((per_point.enabled.get(), per_point.vlog.get()), (20_000, 10_000));
+/// assert_eq!((cloud.enabled.get(), cloud.vlog.get()), (2, 1));
+/// println!("loop: {:?}, cloud: {:?}", loop_time, cloud_time);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! point_cloud {
+    // point_cloud!(vlogger: my_vlogger, target: "my_target", "my_surface", points, 5.0, Base, "o", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__point_cloud!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // point_cloud!(vlogger: my_vlogger, "my_surface", points, 5.0, Base, "o", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__point_cloud!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // point_cloud!(target: "my_target", "my_surface", points, 5.0, Base, "o", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__point_cloud!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // point_cloud!("my_surface", points, 5.0, Base, "o", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__point_cloud!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends an indexed triangle mesh to the vlogger.
+///
+/// The mesh is given by a list of vertex positions and a list of indices into them,
+/// three per triangle. With `shaded:` in front of the vertices, the triangles are
+/// drawn with flat shading, otherwise as a wireframe with the given thickness.
+/// The color tints the whole mesh. If the number of indices is not a multiple of
+/// three or an index is out of bounds, nothing is drawn.
+/// See [`Visual::Mesh`](crate::Visual::Mesh).
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use v_log::mesh;
+///
+/// // Vertices must be of the same type for arrays to work,
+/// // but are only required to implement IntoIterator.
+/// // They can be arbitrary dimension, but only the first 3 are used.
+/// let vertices = [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0], [0.0, 0.0, 1.0]];
+/// let indices = [0, 2, 1, 0, 1, 3, 0, 3, 2, 1, 2, 3];
+///
+/// // Draw a tetrahedron as a wireframe with thickness 1.0.
+/// mesh!("main_surface", vertices, &indices, 1.0, Base, "tetrahedron {}", 1);
+/// // Draw it shaded.
+/// mesh!("main_surface", shaded: vertices, &indices, 1.0, Info);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// Invalid meshes are skipped:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::mesh;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// let vertices = [[0.0, 0.0], [1.0, 0.0], [0.0, 1.0]];
+/// mesh!(vlogger: capture, "main_surface", vertices, [0, 1, 2], 1.0, Base);
+/// mesh!(vlogger: capture, "main_surface", vertices, [0, 1, 3], 1.0, Base);
+/// mesh!(vlogger: capture, "main_surface", vertices, [0, 1], 1.0, Base);
+///
+/// let visuals = capture.0.borrow();
+/// assert_eq!(visuals.len(), 1);
+/// assert!(matches!(&visuals[0], Visual::Mesh { vertices, indices, shaded: false }
+///     if vertices.len() == 3 && indices == &[0, 1, 2]));
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! mesh {
+    // mesh!(vlogger: my_vlogger, target: "my_target", "my_surface", &vertices, &indices, 1.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__mesh!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // mesh!(vlogger: my_vlogger, "my_surface", &vertices, &indices, 1.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__mesh!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // mesh!(target: "my_target", "my_surface", &vertices, &indices, 1.0, Base, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__mesh!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // mesh!("my_surface", &vertices, &indices, 1.0, Base, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__mesh!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends many arrows at once to the vlogger, e.g. to show a flow field.
+///
+/// The arrows are given by a list of origins and a list of vectors. Each arrow
+/// starts at its origin and ends at the origin plus the vector multiplied by the
+/// scale. If the lists have different lengths, the longer one is truncated.
+/// The arrowheads are sized like [`LineStyle::Arrow`](crate::LineStyle::Arrow).
+/// See [`Visual::VectorField`](crate::Visual::VectorField).
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use v_log::quiver;
+///
+/// let origins = [[0.0, 0.0], [1.0, 0.0], [2.0, 0.0]];
+/// let velocities = [[0.0, 1.0], [0.5, 1.0], [1.0, 1.0]];
+///
+/// // Draw the velocities at half length with thickness 1.0.
+/// quiver!("main_surface", origins, velocities, 0.5, 1.0, Base, "flow at t={}", 0.1);
+/// // Positions can be any IntoIterator, e.g. from a computation.
+/// quiver!("main_surface", origins, origins.map(|[x, y]| [-y, x]), 1.0, 1.0, Info);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! quiver {
+    // quiver!(vlogger: my_vlogger, target: "my_target", "my_surface", origins, vectors, 1.0, 1.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__quiver!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // quiver!(vlogger: my_vlogger, "my_surface", origins, vectors, 1.0, 1.0, Base, "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__quiver!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // quiver!(target: "my_target", "my_surface", origins, vectors, 1.0, 1.0, Base, "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__quiver!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // quiver!("my_surface", origins, vectors, 1.0, 1.0, Base, "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__quiver!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a closed polygon to the vlogger, optionally with a filled area.
+///
+/// The points are given like for [`polyline!`], either as a list or in parentheses.
+/// With `filled:` in front of the points, the area of the polygon is filled with the
+/// color, while the outline is drawn with the same color at full alpha.
+/// With fewer than three points, nothing is drawn.
+/// See [`Visual::Polygon`](crate::Visual::Polygon).
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// use v_log::polygon;
+///
+/// // Points must be of the same type for arrays to work,
+/// // but are only required to implement IntoIterator.
+/// // They can be arbitrary dimension, but only the first 3 are used.
+/// let pos1 = [3.234, -1.223];
+/// let pos2 = [2.713, 0.577];
+/// let pos3 = [6.283, 0.692];
+///
+/// // Draw the outline of a triangle with thickness 5.0.
+/// polygon!("main_surface", [pos1, pos2, pos3], 5.0, Base, "--", "Triangle of {} points", 3);
+/// polygon!("main_surface", (pos1, pos2, pos3), 5.0, Base);
+/// // Fill a triangle with a transparent color and without an outline.
+/// polygon!("main_surface", filled: [pos1, pos2, pos3], 5.0, 0xff000080, None);
+/// polygon!("main_surface", filled: (pos1, pos2, pos3), 5.0, Warn, "-", opacity: 0.5);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+///
+/// The points are stored in the visual:
+///
+/// ```
+/// # #[cfg(feature = "alloc")]
+/// # fn main() {
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(RefCell<Vec<Visual>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.visual().clone()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::polygon;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// polygon!(vlogger: capture, "main_surface", filled: ([0.0, 0.0], [1.0, 0.0], [0.0, 1.0]), 1.0, Info);
+///
+/// let visuals = capture.0.borrow();
+/// match &visuals[0] {
+///     Visual::Polygon { points, filled, .. } => {
+///         assert_eq!(points, &[[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+///         assert!(*filled);
+///     }
+///     _ => panic!("expected a polygon"),
+/// }
+/// drop(visuals);
+///
+/// polygon!(vlogger: capture, "main_surface", ([0.0, 0.0], [1.0, 0.0]), 1.0, Info);
+/// assert_eq!(capture.0.borrow().len(), 1);
+/// # }
+/// # #[cfg(not(feature = "alloc"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! polygon {
+    // polygon!(vlogger: my_vlogger, target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 3.0], [2.0, 2.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__polygon!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // polygon!(vlogger: my_vlogger, "my_surface", ([1.0, 2.0], [1.0, 3.0], [2.0, 2.0]), 5.0, Base, "-", "a {} event", "log")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__polygon!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // polygon!(target: "my_target", "my_surface", ([1.0, 2.0], [1.0, 3.0], [2.0, 2.0]), 5.0, Base, "-", "a {} event", "log")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__polygon!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // polygon!("my_surface", ([1.0, 2.0], [1.0, 3.0], [2.0, 2.0]), 5.0, Base, "-", "a {} event", "log")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__polygon!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Sends a polyline with a color gradient along its length to the vlogger.
+///
+/// The colors of the segments are interpolated with [`Color::lerp`](crate::Color::lerp)
+/// from the start color to the end color by the arc length at their centers.
+/// With fewer than two points, nothing is drawn.
+/// The default line style is [`default_line_style`](crate::default_line_style), initially `"-"`.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
 /// # #[cfg(feature = "std")]
-/// arrow!("main_surface", pos, dir, (50.0), 5.0, Base, "Position is: x: {}, y: {}", pos[0], pos[1]);
+/// # fn main() {
+/// use v_log::polyline_gradient;
+///
+/// let residuals = vec![[0.0, 1.0], [1.0, 0.5], [2.0, 0.2], [3.0, 0.1]];
+///
+/// polyline_gradient!("main_surface", &residuals, 2.0, (Error, Healthy));
+/// polyline_gradient!("main_surface", &residuals, 2.0, (0x000000ff, 0xffffffff), "--");
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+///
+/// The segments of a path get intermediate colors:
+///
+/// ```
 /// # #[cfg(feature = "std")]
-/// arrow!("main_surface", pos, dir, (50.0), 5.0, Base);
-/// // Draw an arrow from `pos` pointing in direction `dir` with the length of `dir` and thickness 5.0.
-/// arrow!("main_surface", pos, dir, 5.0, Base, "Position is: x: {}, y: {}", pos[0], pos[1]);
-/// arrow!("main_surface", pos, dir, 5.0, Base);
-/// // Draw multiple arrows from `pos` and scale them with 50.0.
-/// // The colors are X, Y, Z and then "Missing" for all others.
-/// arrow!("main_surface", pos, space, (50.0), 5.0);
+/// # fn main() {
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<Color>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(*record.color()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{polyline_gradient, Color};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// let points = [[0.0, 0.0], [1.0, 0.0], [1.0, 1.0]];
+/// polyline_gradient!(vlogger: capture, "main_surface", &points, 2.0, (0x000000ff, 0xffffffff));
+///
+/// let colors = capture.0.borrow();
+/// assert_eq!(colors.len(), 2);
+/// assert!(colors[0].approx_eq(&Color::Hex(0x404040ff), 1));
+/// assert!(colors[1].approx_eq(&Color::Hex(0xbfbfbfff), 1));
+/// drop(colors);
+///
+/// polyline_gradient!(vlogger: capture, "main_surface", &points[..1], 2.0, (Error, Healthy));
+/// assert_eq!(capture.0.borrow().len(), 2);
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
+/// ```
+#[macro_export]
+macro_rules! polyline_gradient {
+    // polyline_gradient!(vlogger: my_vlogger, target: "my_target", "my_surface", &points, 2.0, (Error, Healthy), "-")
+    (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__polyline_gradient!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // polyline_gradient!(vlogger: my_vlogger, "my_surface", &points, 2.0, (Error, Healthy), "-")
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__polyline_gradient!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // polyline_gradient!(target: "my_target", "my_surface", &points, 2.0, (Error, Healthy), "-")
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__polyline_gradient!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // polyline_gradient!("my_surface", &points, 2.0, (Error, Healthy), "-")
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__polyline_gradient!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+/// Times a block and sends the duration as a label to the vlogger.
+///
+/// The label shows the stage name and the wall time of the block in milliseconds.
+/// It is drawn with the default label size, color and alignment, see [`label!`].
+/// The value of the block is returned.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "std")]
+/// # fn main() {
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog, Visual};
+/// # struct Capture(RefCell<Vec<(Visual, String)>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) {
+/// #         self.0.borrow_mut().push((record.visual().clone(), record.args().to_string()))
+/// #     }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::timed_label;
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// let sum = timed_label!(vlogger: capture, "main_surface", [1.0, 2.0], "broadphase", {
+///     (0..1000).sum::<u64>()
+/// });
+/// assert_eq!(sum, 499500);
+///
+/// let labels = capture.0.borrow();
+/// assert!(matches!(labels[0].0, Visual::Label { x, y, .. } if x == 1.0 && y == 2.0));
+/// assert!(labels[0].1.starts_with("broadphase: "));
+/// assert!(labels[0].1.ends_with(" ms"));
+/// # }
+/// # #[cfg(not(feature = "std"))]
+/// # fn main() {}
 /// ```
 #[macro_export]
-macro_rules! arrow {
-    // arrow!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
+macro_rules! timed_label {
+    // timed_label!(vlogger: my_vlogger, target: "my_target", "my_surface", [1.0, 2.0], "stage", { work() })
     (vlogger: $vlogger:expr, target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__arrow!(
+        $crate::__timed_label!(
+            $crate::__vlog_vlogger!($vlogger),
+            $surface,
+            &$crate::__abs_module_path!($target),
+            $($arg)+
+        )
+    });
+
+    // timed_label!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], "stage", { work() })
+    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__timed_label!(
             $crate::__vlog_vlogger!($vlogger),
             $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    });
+
+    // timed_label!(target: "my_target", "my_surface", [1.0, 2.0], "stage", { work() })
+    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
+        $crate::__timed_label!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
             &$crate::__abs_module_path!($target),
             $($arg)+
         )
     });
 
-    // arrow!(vlogger: my_vlogger, "my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
-    (vlogger: $vlogger:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__arrow!(
-            $crate::__vlog_vlogger!($vlogger),
+    // timed_label!("my_surface", [1.0, 2.0], "stage", { work() })
+    ($surface:expr, $($arg:tt)+) => (
+        $crate::__timed_label!(
+            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+            $surface,
+            &$crate::__abs_module_path!(),
+            $($arg)+
+        )
+    )
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __polyline_gradient {
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, ($color1:tt, $color2:tt), $style:tt) => {
+        $crate::__std_only!($crate::__private_api::vlog_gradient_line(
+            $vlogger,
+            $point_list,
+            $size,
+            ($crate::__color!($color1), $crate::__color!($color2)),
+            $crate::__line_style!($style),
+            $surface,
+            $loc,
+        ))
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, ($color1:tt, $color2:tt)) => {
+        $crate::__polyline_gradient!(
+            $vlogger,
+            $surface,
+            $loc,
+            $point_list,
+            $size,
+            ($color1, $color2),
+            ($crate::default_line_style())
+        )
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __timed_label {
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $stage:expr, $block:block) => {
+        $crate::__std_only!({
+            let start = $crate::__private_api::Instant::now();
+            let value = $block;
+            let millis = start.elapsed().as_secs_f64() * 1000.0;
+            $crate::__label!(
+                $vlogger,
+                $surface,
+                $loc,
+                $pos,
+                "{}: {:.3} ms",
+                $stage,
+                millis
+            );
+            value
+        })
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __format_args {
+    // split off the key-value pairs after `;`, they are handled by `__extra!`
+    (@split [$($arg:tt)*]) => {
+        $crate::__format_args!(@args $($arg)*)
+    };
+    (@split [$($arg:tt)*] ; $($kv:tt)*) => {
+        $crate::__format_args!(@args $($arg)*)
+    };
+    (@split [$($arg:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__format_args!(@split [$($arg)* $next] $($rest)*)
+    };
+    // skip keyword arguments, they are handled by `__extra!`
+    (@args) => {
+        $crate::__private_api::format_args!("")
+    };
+    (@args msg: $msg:expr $(, $($rest:tt)*)?) => {
+        $crate::__private_api::format_args!("{}", $msg)
+    };
+    (@args show_pos $(, $($rest:tt)*)?) => {
+        $crate::__format_args!(@args $($($rest)*)?)
+    };
+    (@args $key:ident: $val:expr $(, $($rest:tt)*)?) => {
+        $crate::__format_args!(@args $($($rest)*)?)
+    };
+    (@args $($arg:tt)+) => {
+        $crate::__private_api::format_args!($($arg)+)
+    };
+    ($($arg:tt)*) => {
+        $crate::__format_args!(@split [] $($arg)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __extra {
+    // split off the key-value pairs after `;`
+    (@split [$($arg:tt)*]) => {
+        $crate::__extra!(@ $crate::__private_api::Extra::new(); $($arg)*)
+    };
+    (@split [$($arg:tt)*] ; $($kv:tt)*) => {
+        $crate::__extra!(@ $crate::__private_api::Extra::new().key_values(&$crate::__key_values!($($kv)*)); $($arg)*)
+    };
+    (@split [$($arg:tt)*] $next:tt $($rest:tt)*) => {
+        $crate::__extra!(@split [$($arg)* $next] $($rest)*)
+    };
+    // collect keyword arguments into an `Extra`, ignoring the format arguments
+    (@ $extra:expr; msg: $val:expr $(, $($rest:tt)*)?) => {
+        $crate::__extra!(@ $extra; $($($rest)*)?)
+    };
+    (@ $extra:expr; show_pos $(, $($rest:tt)*)?) => {
+        $crate::__extra!(@ $extra.show_pos(); $($($rest)*)?)
+    };
+    (@ $extra:expr; $key:ident: $val:expr $(, $($rest:tt)*)?) => {
+        $crate::__extra!(@ $extra.$key($val); $($($rest)*)?)
+    };
+    (@ $extra:expr; $($arg:tt)*) => {
+        $extra
+    };
+    ($($arg:tt)*) => {
+        $crate::__extra!(@split [] $($arg)*)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "kv")]
+macro_rules! __key_values {
+    ($($key:ident = $val:expr),* $(,)?) => {
+        [$(($crate::__private_api::stringify!($key), $crate::kv::Value::from($val))),*]
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "kv"))]
+macro_rules! __key_values {
+    ($($kv:tt)*) => {
+        $crate::__private_api::compile_error!("key-value pairs require the `kv` feature of v-log")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __message {
+    ($vlogger:expr, $surface:expr, $loc:expr, color: $color:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_message(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__color!($color),
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $($arg:tt)+) => {
+        $crate::__message!($vlogger, $surface, $loc, color: Base, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __point {
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__point!($vlogger, $surface, $loc, $pos, $size, $color, ($crate::default_point_style()), $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt, show_pos $(, $($arg:tt)+)?) => {
+        $crate::__point!($vlogger, $surface, $loc, $pos, $size, $color, ($crate::default_point_style()), show_pos $(, $($arg)+)?)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_point(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos),
+            $size,
+            $crate::__color!($color),
+            $crate::__point_style!($style),
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt, $style:tt) => {
+        $crate::__point!($vlogger, $surface, $loc, $pos, $size, $color, $style, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt) => {
+        $crate::__point!($vlogger, $surface, $loc, $pos, $size, $color, ($crate::default_point_style()), "")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __label {
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($size:expr, $color:tt, $align:tt, $max_width:expr, $style:ident), $($arg:tt)+) => {
+        $crate::__private_api::vlog_label(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos),
+            $size,
+            $crate::__color!($color),
+            $crate::__alignment!($align),
+            Some($max_width),
+            $crate::LabelStyle::$style,
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($size:expr, $color:tt, $align:tt, $max_width:expr), $($arg:tt)+) => {
+        $crate::__label!($vlogger, $surface, $loc, $pos, ($size, $color, $align, $max_width, WordWrap), $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($size:expr, $color:tt, $align:tt), $($arg:tt)+) => {
+        $crate::__private_api::vlog_label(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos),
+            $size,
+            $crate::__color!($color),
+            $crate::__alignment!($align),
+            None,
+            $crate::LabelStyle::WordWrap,
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $($arg:tt)+) => {
+        // default size of 12 pixels
+        $crate::__label!($vlogger, $surface, $loc, $pos, (12.0, Base, "x"), $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __line {
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__line!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, ($crate::default_line_style()), $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_line(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos1),
+            $crate::__pos!($pos2),
+            $size,
+            $crate::__color!($color),
+            $crate::__line_style!($style),
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $style:tt) => {
+        $crate::__line!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, $style, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt) => {
+        $crate::__line!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, ($crate::default_line_style()), "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__line!($vlogger, $surface, $loc, closed: $point_list, $size, $color, ($crate::default_line_style()), 0.0, $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt, $style:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__line!($vlogger, $surface, $loc, closed: $point_list, $size, $color, $style, 0.0, $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt, $style:tt, $textsize:expr, $($arg:tt)+) => {
+        $crate::__private_api::vlog_closed_line(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $point_list,
+            $size,
+            $textsize,
+            $crate::__color!($color),
+            $crate::__line_style!($style),
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt, $style:tt) => {
+        $crate::__line!($vlogger, $surface, $loc, closed: $point_list, $size, $color, $style, 0.0, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt) => {
+        $crate::__line!($vlogger, $surface, $loc, closed: $point_list, $size, $color, ($crate::default_line_style()), 0.0, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__line!($vlogger, $surface, $loc, $point_list, $size, $color, ($crate::default_line_style()), $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt, $style:tt $(, $key:ident: $val:expr)*) => {{
+        let mut last = None;
+        let col = $crate::__color!($color);
+        let line_style = $crate::__line_style!($style);
+        let extra = $crate::__extra!($($key: $val),*).path_id($crate::__private_api::next_path_id());
+        let mut count = 0;
+        for p in $point_list {
+            if let Some(f) = last {
+                $crate::__private_api::vlog_line(
+                    $vlogger,
+                    $crate::__private_api::format_args!(""),
+                    f,
+                    p,
+                    $size,
+                    col,
+                    line_style,
+                    $surface,
+                    $loc,
+                    &extra
+                );
+            }
+            last = Some(p);
+            count += 1;
+        }
+        assert!(count >= 2);
+    }};
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt) => {
+        $crate::__line!($vlogger, $surface, $loc, $point_list, $size, $color, ($crate::default_line_style()))
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __path {
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt, $style:tt) => {
+        $crate::__private_api::vlog_path(
+            $vlogger,
+            $point_list,
+            $size,
+            $crate::__color!($color),
+            $crate::__line_style!($style),
+            $surface,
+            $loc,
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt) => {
+        $crate::__path!($vlogger, $surface, $loc, $point_list, $size, $color, "->")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "std")]
+macro_rules! __std_only {
+    ($($t:tt)*) => {
+        $($t)*
+    };
+}
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "std"))]
+macro_rules! __std_only {
+    ($($t:tt)*) => {
+        compile_error!("std required for this macro, e.g. arrow with fixed length")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[cfg(feature = "alloc")]
+macro_rules! __alloc_only {
+    ($($t:tt)*) => {
+        $($t)*
+    };
+}
+#[doc(hidden)]
+#[macro_export]
+#[cfg(not(feature = "alloc"))]
+macro_rules! __alloc_only {
+    ($($t:tt)*) => {
+        compile_error!("alloc required for this macro")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __spline {
+    (@ $closed:expr, $vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $tension:expr, $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__alloc_only!($crate::__private_api::vlog_spline(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $point_list,
+            $tension,
+            $closed,
+            $size,
+            $crate::__color!($color),
+            $crate::__line_style!($style),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
-        )
-    });
+            $loc,
+            &$crate::__extra!($($arg)+)
+        ))
+    };
+    (@ $closed:expr, $vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $tension:expr, $size:expr, $color:tt, $style:tt) => {
+        $crate::__spline!(@ $closed, $vlogger, $surface, $loc, $point_list, $tension, $size, $color, $style, "")
+    };
+    (@ $closed:expr, $vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $tension:expr, $size:expr, $color:tt) => {
+        $crate::__spline!(@ $closed, $vlogger, $surface, $loc, $point_list, $tension, $size, $color, ($crate::default_line_style()), "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($($pos:expr,)+), $($arg:tt)+) => {
+        $crate::__spline!(@ true, $vlogger, $surface, $loc, [$($pos),+], $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($($pos:expr),+), $($arg:tt)+) => {
+        $crate::__spline!(@ false, $vlogger, $surface, $loc, [$($pos),+], $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $($arg:tt)+) => {
+        $crate::__spline!(@ true, $vlogger, $surface, $loc, $point_list, $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $($arg:tt)+) => {
+        $crate::__spline!(@ false, $vlogger, $surface, $loc, $point_list, $($arg)+)
+    };
+}
 
-    // arrow!(target: "my_target", "my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
-    (target: $target:expr, $surface:expr, $($arg:tt)+) => ({
-        $crate::__arrow!(
-            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __image {
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($width:expr, $height:expr), $format:expr, $data:expr, $($arg:tt)+) => {
+        $crate::__alloc_only!($crate::__private_api::vlog_image(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $pos,
+            ($width, $height),
+            {
+                use $crate::ImageFormat::*;
+                $format
+            },
+            $data,
             $surface,
-            &$crate::__abs_module_path!($target),
-            $($arg)+
-        )
-    });
+            $loc,
+            &$crate::__extra!($($arg)+)
+        ))
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($width:expr, $height:expr), $format:expr, $data:expr) => {
+        $crate::__image!($vlogger, $surface, $loc, $pos, ($width, $height), $format, $data, "")
+    };
+}
 
-    // arrow!("my_surface", [1.0, 2.0], [1.0, 3.0], 5.0, Base, "a {} event", "log")
-    ($surface:expr, $($arg:tt)+) => (
-        $crate::__arrow!(
-            $crate::__vlog_vlogger!(__vlog_global_vlogger),
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __point_cloud {
+    ($vlogger:expr, $surface:expr, $loc:expr, $points:expr, $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__point_cloud!($vlogger, $surface, $loc, $points, $size, $color, ($crate::default_point_style()), $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $points:expr, $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__alloc_only!($crate::__private_api::vlog_point_cloud(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $points,
+            $size,
+            $crate::__color!($color),
+            $crate::__point_style!($style),
             $surface,
-            &$crate::__abs_module_path!(),
-            $($arg)+
-        )
-    )
+            $loc,
+            &$crate::__extra!($($arg)+)
+        ))
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $points:expr, $size:expr, $color:tt, $style:tt) => {
+        $crate::__point_cloud!($vlogger, $surface, $loc, $points, $size, $color, $style, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $points:expr, $size:expr, $color:tt) => {
+        $crate::__point_cloud!($vlogger, $surface, $loc, $points, $size, $color, ($crate::default_point_style()), "")
+    };
 }
 
 #[doc(hidden)]
 #[macro_export]
 #[clippy::format_args]
-macro_rules! __message {
-    ($vlogger:expr, $surface:expr, $loc:expr, color: $color:tt, $($arg:tt)+) => {
-        $crate::__private_api::vlog_message(
+macro_rules! __mesh {
+    (@ $shaded:expr, $vlogger:expr, $surface:expr, $loc:expr, $vertices:expr, $indices:expr, $size:expr, $color:tt, $($arg:tt)+) => {
+        $crate::__alloc_only!($crate::__private_api::vlog_mesh(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
+            $crate::__format_args!($($arg)+),
+            $vertices,
+            $indices,
+            $shaded,
+            $size,
             $crate::__color!($color),
             $surface,
-            $loc
-        )
+            $loc,
+            &$crate::__extra!($($arg)+)
+        ))
+    };
+    (@ $shaded:expr, $vlogger:expr, $surface:expr, $loc:expr, $vertices:expr, $indices:expr, $size:expr, $color:tt) => {
+        $crate::__mesh!(@ $shaded, $vlogger, $surface, $loc, $vertices, $indices, $size, $color, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, shaded: $($arg:tt)+) => {
+        $crate::__mesh!(@ true, $vlogger, $surface, $loc, $($arg)+)
     };
     ($vlogger:expr, $surface:expr, $loc:expr, $($arg:tt)+) => {
-        $crate::__private_api::vlog_message(
+        $crate::__mesh!(@ false, $vlogger, $surface, $loc, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __quiver {
+    ($vlogger:expr, $surface:expr, $loc:expr, $origins:expr, $vectors:expr, $scale:expr, $size:expr, $color:tt, $($arg:tt)+) => {
+        $crate::__alloc_only!($crate::__private_api::vlog_vector_field(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $crate::__color!(Base),
+            $crate::__format_args!($($arg)+),
+            $origins,
+            $vectors,
+            $scale,
+            $size,
+            $crate::__color!($color),
             $surface,
-            $loc
-        )
+            $loc,
+            &$crate::__extra!($($arg)+)
+        ))
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $origins:expr, $vectors:expr, $scale:expr, $size:expr, $color:tt) => {
+        $crate::__quiver!($vlogger, $surface, $loc, $origins, $vectors, $scale, $size, $color, "")
     };
 }
 
 #[doc(hidden)]
 #[macro_export]
 #[clippy::format_args]
-macro_rules! __point {
-    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
-        $crate::__private_api::vlog_point(
+macro_rules! __polygon {
+    (@ $filled:expr, $vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__polygon!(@ $filled, $vlogger, $surface, $loc, $point_list, $size, $color, ($crate::default_line_style()), $key: $($arg)+)
+    };
+    (@ $filled:expr, $vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__alloc_only!($crate::__private_api::vlog_polygon(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $pos,
+            $crate::__format_args!($($arg)+),
+            $point_list,
+            $filled,
             $size,
             $crate::__color!($color),
-            $crate::__point_style!($style),
+            $crate::__line_style!($style),
             $surface,
-            $loc
-        )
+            $loc,
+            &$crate::__extra!($($arg)+)
+        ))
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt, $style:tt) => {
-        $crate::__private_api::vlog_point(
+    (@ $filled:expr, $vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt, $style:tt) => {
+        $crate::__polygon!(@ $filled, $vlogger, $surface, $loc, $point_list, $size, $color, $style, "")
+    };
+    (@ $filled:expr, $vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt) => {
+        $crate::__polygon!(@ $filled, $vlogger, $surface, $loc, $point_list, $size, $color, ($crate::default_line_style()), "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, filled: ($($pos:expr),+ $(,)?), $($arg:tt)+) => {
+        $crate::__polygon!(@ true, $vlogger, $surface, $loc, [$($pos),+], $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, filled: $point_list:expr, $($arg:tt)+) => {
+        $crate::__polygon!(@ true, $vlogger, $surface, $loc, $point_list, $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($($pos:expr),+ $(,)?), $($arg:tt)+) => {
+        $crate::__polygon!(@ false, $vlogger, $surface, $loc, [$($pos),+], $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $($arg:tt)+) => {
+        $crate::__polygon!(@ false, $vlogger, $surface, $loc, $point_list, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __aabb {
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__aabb!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, ($crate::default_line_style()), $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_aabb(
             $vlogger,
-            $crate::__private_api::format_args!(""),
-            $pos,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos1),
+            $crate::__pos!($pos2),
             $size,
             $crate::__color!($color),
-            $crate::__point_style!($style),
+            $crate::__line_style!($style),
             $surface,
-            $loc
+            $loc,
+            &$crate::__extra!($($arg)+)
         )
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $size:expr, $color:tt) => {
-        $crate::__private_api::vlog_point(
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $style:tt) => {
+        $crate::__aabb!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, $style, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt) => {
+        $crate::__aabb!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, ($crate::default_line_style()), "")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __axes {
+    ($vlogger:expr, $surface:expr, $loc:expr, $origin:expr, rotation: $rotation:expr, $length:expr, $size:expr, $($arg:tt)+) => {
+        $crate::__private_api::vlog_axes(
             $vlogger,
-            $crate::__private_api::format_args!(""),
-            $pos,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($origin),
+            $rotation,
+            $length,
             $size,
-            $crate::__color!($color),
-            $crate::__point_style!("o"),
             $surface,
-            $loc
+            $loc,
+            &$crate::__extra!($($arg)+)
         )
     };
+    ($vlogger:expr, $surface:expr, $loc:expr, $origin:expr, rotation: $rotation:expr, $length:expr, $size:expr) => {
+        $crate::__axes!($vlogger, $surface, $loc, $origin, rotation: $rotation, $length, $size, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $origin:expr, $length:expr, $size:expr $(, $($arg:tt)+)?) => {
+        $crate::__axes!($vlogger, $surface, $loc, $origin, rotation: [0.0, 0.0, 0.0, 1.0], $length, $size $(, $($arg)+)?)
+    };
 }
 
 #[doc(hidden)]
 #[macro_export]
 #[clippy::format_args]
-macro_rules! __label {
-    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($size:expr, $color:tt, $align:tt), $($arg:tt)+) => {
-        $crate::__private_api::vlog_label(
+macro_rules! __obb {
+    ($vlogger:expr, $surface:expr, $loc:expr, $center:expr, $half_extents:expr, $rotation:expr, $size:expr, $color:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_obb(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $pos,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($center),
+            $crate::__pos!($half_extents),
+            $rotation,
             $size,
             $crate::__color!($color),
-            $crate::__alignment!($align),
             $surface,
-            $loc
+            $loc,
+            &$crate::__extra!($($arg)+)
         )
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $($arg:tt)+) => {
-        $crate::__private_api::vlog_label(
+    ($vlogger:expr, $surface:expr, $loc:expr, $center:expr, $half_extents:expr, $rotation:expr, $size:expr, $color:tt) => {
+        $crate::__obb!($vlogger, $surface, $loc, $center, $half_extents, $rotation, $size, $color, "")
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __arc {
+    (@ $sector:expr, $vlogger:expr, $surface:expr, $loc:expr, $center:expr, $radius:expr, $angles:expr, $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__arc!(@ $sector, $vlogger, $surface, $loc, $center, $radius, $angles, $size, $color, ($crate::default_line_style()), $key: $($arg)+)
+    };
+    (@ $sector:expr, $vlogger:expr, $surface:expr, $loc:expr, $center:expr, $radius:expr, $angles:expr, $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_arc(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $pos,
-            12.0, // default size of 12 pixels
-            $crate::__color!(Base),
-            $crate::__alignment!("x"),
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($center),
+            $radius,
+            $angles,
+            $sector,
+            $size,
+            $crate::__color!($color),
+            $crate::__line_style!($style),
             $surface,
-            $loc
+            $loc,
+            &$crate::__extra!($($arg)+)
         )
     };
+    (@ $sector:expr, $vlogger:expr, $surface:expr, $loc:expr, $center:expr, $radius:expr, $angles:expr, $size:expr, $color:tt, $style:tt) => {
+        $crate::__arc!(@ $sector, $vlogger, $surface, $loc, $center, $radius, $angles, $size, $color, $style, "")
+    };
+    (@ $sector:expr, $vlogger:expr, $surface:expr, $loc:expr, $center:expr, $radius:expr, $angles:expr, $size:expr, $color:tt) => {
+        $crate::__arc!(@ $sector, $vlogger, $surface, $loc, $center, $radius, $angles, $size, $color, ($crate::default_line_style()), "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $center:expr, $radius:expr, ($start:expr, $end:expr,), $($arg:tt)+) => {
+        $crate::__arc!(@ true, $vlogger, $surface, $loc, $center, $radius, ($start, $end), $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $center:expr, $radius:expr, ($start:expr, $end:expr), $($arg:tt)+) => {
+        $crate::__arc!(@ false, $vlogger, $surface, $loc, $center, $radius, ($start, $end), $($arg)+)
+    };
 }
 
 #[doc(hidden)]
 #[macro_export]
 #[clippy::format_args]
-macro_rules! __line {
-    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
-        $crate::__private_api::vlog_line(
+macro_rules! __bezier {
+    ($vlogger:expr, $surface:expr, $loc:expr, ($p0:expr, $p1:expr, $p2:expr, $p3:expr $(,)?), $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__bezier!($vlogger, $surface, $loc, ($p0, $p1, $p2, $p3), $size, $color, ($crate::default_line_style()), $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($p0:expr, $p1:expr, $p2:expr, $p3:expr $(,)?), $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_bezier(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $pos1,
-            $pos2,
+            $crate::__format_args!($($arg)+),
+            [$crate::__pos!($p0), $crate::__pos!($p1), $crate::__pos!($p2), $crate::__pos!($p3)],
             $size,
             $crate::__color!($color),
             $crate::__line_style!($style),
             $surface,
-            $loc
+            $loc,
+            &$crate::__extra!($($arg)+)
         )
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt, $style:tt) => {
-        $crate::__line!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, $style, "");
+    ($vlogger:expr, $surface:expr, $loc:expr, ($p0:expr, $p1:expr, $p2:expr, $p3:expr $(,)?), $size:expr, $color:tt, $style:tt) => {
+        $crate::__bezier!($vlogger, $surface, $loc, ($p0, $p1, $p2, $p3), $size, $color, $style, "")
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, ($pos1:expr, $pos2:expr), $size:expr, $color:tt) => {
-        $crate::__line!($vlogger, $surface, $loc, ($pos1, $pos2), $size, $color, "-", "");
+    ($vlogger:expr, $surface:expr, $loc:expr, ($p0:expr, $p1:expr, $p2:expr, $p3:expr $(,)?), $size:expr, $color:tt) => {
+        $crate::__bezier!($vlogger, $surface, $loc, ($p0, $p1, $p2, $p3), $size, $color, ($crate::default_line_style()), "")
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt, $style:tt, $textsize:expr, $($arg:tt)+) => {
-        $crate::__private_api::vlog_closed_line(
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __grid {
+    (@ $vlogger:expr, $surface:expr, $loc:expr, $spacing:expr, $extent:expr, $plane:expr, $color:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_grid(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $point_list,
-            $size,
-            $textsize,
+            $crate::__format_args!($($arg)+),
+            $spacing,
+            $extent,
+            {
+                use $crate::GridPlane::*;
+                $plane
+            },
             $crate::__color!($color),
-            $crate::__line_style!($style),
             $surface,
-            $loc
-        );
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt, $style:tt) => {
-        $crate::__line!($vlogger, $surface, $loc, closed: $point_list, $size, $color, $style, 0.0, "");
+    (@ $vlogger:expr, $surface:expr, $loc:expr, $spacing:expr, $extent:expr, $plane:expr, $color:tt) => {
+        $crate::__grid!(@ $vlogger, $surface, $loc, $spacing, $extent, $plane, $color, "")
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, closed: $point_list:expr, $size:expr, $color:tt) => {
-        $crate::__line!($vlogger, $surface, $loc, closed: $point_list, $size, $color, "-", 0.0, "");
+    (@ $vlogger:expr, $surface:expr, $loc:expr, $spacing:expr, $extent:expr, $plane:expr) => {
+        $crate::__grid!(@ $vlogger, $surface, $loc, $spacing, $extent, $plane, Base, "")
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt, $style:tt) => {
-        let mut last = None;
-        let col = $crate::__color!($color);
-        let line_style = $crate::__line_style!($style);
-        let mut count = 0;
-        for p in $point_list {
-            if let Some(f) = last {
-                $crate::__private_api::vlog_line(
-                    $vlogger,
-                    $crate::__private_api::format_args!(""),
-                    f,
-                    p,
-                    $size,
-                    col,
-                    line_style,
-                    $surface,
-                    $loc
-                );
-            }
-            last = Some(p);
-            count += 1;
-        }
-        assert!(count >= 2);
+    ($vlogger:expr, $surface:expr, $loc:expr, spacing: $spacing:expr, extent: $extent:expr, plane: $plane:expr $(, $($arg:tt)+)?) => {
+        $crate::__grid!(@ $vlogger, $surface, $loc, $spacing, $extent, $plane $(, $($arg)+)?)
     };
-    ($vlogger:expr, $surface:expr, $loc:expr, $point_list:expr, $size:expr, $color:tt) => {
-        $crate::__line!($vlogger, $surface, $loc, $point_list, $size, $color, "-");
+    ($vlogger:expr, $surface:expr, $loc:expr, spacing: $spacing:expr, extent: $extent:expr $(, $($arg:tt)+)?) => {
+        $crate::__grid!(@ $vlogger, $surface, $loc, $spacing, $extent, XY $(, $($arg)+)?)
     };
 }
 
 #[doc(hidden)]
 #[macro_export]
-#[cfg(feature = "std")]
-macro_rules! __std_only {
-    ($($t:tt)*) => {
-        $($t)*
+#[clippy::format_args]
+macro_rules! __ellipse {
+    (@ $filled:expr, $vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($rx:expr, $ry:expr), $rotation:expr, $color:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_ellipse(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos),
+            ($rx, $ry),
+            $rotation,
+            $filled,
+            0.0,
+            $crate::__color!($color),
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    (@ $filled:expr, $vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($rx:expr, $ry:expr), $rotation:expr, $color:tt) => {
+        $crate::__ellipse!(@ $filled, $vlogger, $surface, $loc, $pos, ($rx, $ry), $rotation, $color, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, filled: $($arg:tt)+) => {
+        $crate::__ellipse!(@ true, $vlogger, $surface, $loc, $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $($arg:tt)+) => {
+        $crate::__ellipse!(@ false, $vlogger, $surface, $loc, $($arg)+)
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+#[clippy::format_args]
+macro_rules! __errorbar {
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($ex:expr, $ey:expr $(, $ez:expr)?), $size:expr, $color:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_errorbar(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos),
+            [$ex, $ey $(, $ez)?],
+            $size,
+            $crate::__color!($color),
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, ($($extent:expr),+), $size:expr, $color:tt) => {
+        $crate::__errorbar!($vlogger, $surface, $loc, $pos, ($($extent),+), $size, $color, "")
     };
 }
+
 #[doc(hidden)]
 #[macro_export]
-#[cfg(not(feature = "std"))]
-macro_rules! __std_only {
-    ($($t:tt)*) => {
-        compile_error!("std required for arrow macro with fixed length")
+#[clippy::format_args]
+macro_rules! __ray {
+    ($vlogger:expr, $surface:expr, $loc:expr, ($origin:expr, $direction:expr), $size:expr, $color:tt, $key:ident: $($arg:tt)+) => {
+        $crate::__ray!($vlogger, $surface, $loc, ($origin, $direction), $size, $color, ($crate::default_line_style()), $key: $($arg)+)
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($origin:expr, $direction:expr), $size:expr, $color:tt, $style:tt, $($arg:tt)+) => {
+        $crate::__private_api::vlog_ray(
+            $vlogger,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($origin),
+            $crate::__pos!($direction),
+            $size,
+            $crate::__color!($color),
+            $crate::__line_style!($style),
+            $surface,
+            $loc,
+            &$crate::__extra!($($arg)+)
+        )
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($origin:expr, $direction:expr), $size:expr, $color:tt, $style:tt) => {
+        $crate::__ray!($vlogger, $surface, $loc, ($origin, $direction), $size, $color, $style, "")
+    };
+    ($vlogger:expr, $surface:expr, $loc:expr, ($origin:expr, $direction:expr), $size:expr, $color:tt) => {
+        $crate::__ray!($vlogger, $surface, $loc, ($origin, $direction), $size, $color, ($crate::default_line_style()), "")
     };
 }
 
@@ -560,14 +3703,15 @@ macro_rules! __arrow {
     ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $dir:expr, ($len:expr), $size:expr, $color:tt, $($arg:tt)+) => {
         $crate::__std_only!($crate::__private_api::vlog_arrow(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $pos,
-            $dir,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos),
+            $crate::__pos!($dir),
             Some($len),
             $size,
             $crate::__color!($color),
             $surface,
-            $loc
+            $loc,
+            &$crate::__extra!($($arg)+)
         ))
     };
     ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $dir:expr, ($len:expr), $size:expr, $color:tt) => {
@@ -576,14 +3720,15 @@ macro_rules! __arrow {
     ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $dir:expr, $size:expr, $color:tt, $($arg:tt)+) => {
         $crate::__private_api::vlog_arrow(
             $vlogger,
-            $crate::__private_api::format_args!($($arg)+),
-            $pos,
-            $dir,
+            $crate::__format_args!($($arg)+),
+            $crate::__pos!($pos),
+            $crate::__pos!($dir),
             None,
             $size,
             $crate::__color!($color),
             $surface,
-            $loc
+            $loc,
+            &$crate::__extra!($($arg)+)
         )
     };
     ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $dirs:expr, ($scale:expr), $size:expr) => {
@@ -594,7 +3739,8 @@ macro_rules! __arrow {
             $scale,
             $size,
             $surface,
-            $loc
+            $loc,
+            &$crate::__private_api::Extra::new()
         )
     };
     ($vlogger:expr, $surface:expr, $loc:expr, $pos:expr, $dir:expr, $size:expr, $color:tt) => {
@@ -642,7 +3788,7 @@ macro_rules! vlog_enabled {
         $crate::__private_api::enabled(
             $crate::__vlog_vlogger!($vlogger),
             $surface,
-            $crate::__private_api::module_path!(),
+            $crate::__default_target!(),
         )
     }};
 
@@ -660,11 +3806,47 @@ macro_rules! vlog_enabled {
         $crate::__private_api::enabled(
             $crate::__vlog_vlogger!(__vlog_global_vlogger),
             $surface,
-            $crate::__private_api::module_path!(),
+            $crate::__default_target!(),
         )
     }};
 }
 
+/// Vlogs only on every `n`th invocation of this call site, e.g. to reduce clutter in loops.
+///
+/// The wrapped draw is evaluated on the 1st, `n + 1`th, `2n + 1`th, ... invocation,
+/// so the first iteration of a loop is always drawn. Each call site has its own
+/// counter, which is shared between all threads. An `n` of `0` is treated as `1`.
+///
+/// # Examples
+///
+/// ```
+/// # use std::cell::RefCell;
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct Capture(RefCell<Vec<String>>);
+/// # impl VLog for Capture {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, record: &Record) { self.0.borrow_mut().push(record.args().to_string()) }
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::{point, vlog_every};
+///
+/// let capture = Capture(RefCell::new(Vec::new()));
+/// for i in 1..=25 {
+///     vlog_every!(10, point!(vlogger: capture, "main_surface", [i as f64, 0.0], 5.0, Base, "o", "{}", i));
+/// }
+/// assert_eq!(*capture.0.borrow(), ["1", "11", "21"]);
+/// ```
+#[macro_export]
+macro_rules! vlog_every {
+    ($n:expr, $draw:expr $(,)?) => {{
+        static EVERY: $crate::__private_api::Every = $crate::__private_api::Every::new();
+        if EVERY.tick($n) {
+            $draw;
+        }
+    }};
+}
+
 // Determine the vlogger to use, and whether to take it by-value or by reference
 
 #[doc(hidden)]
@@ -724,7 +3906,7 @@ macro_rules! __point_style {
     ($s:literal) => {
         compile_error!(concat!("unknown point style ", $s))
     };
-    ($s:ident) => {{
+    ($s:expr) => {{
         use $crate::PointStyle::*;
         $s
     }};
@@ -781,12 +3963,29 @@ macro_rules! __alignment {
     }};
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __pos {
+    // a position which is `IntoIterator<Item = f64>` or `Position`, see `PosArg`.
+    ($pos:expr) => {{
+        #[allow(unused_imports)]
+        use $crate::__private_api::{ViaIntoIter as _, ViaPosition as _};
+        $crate::__private_api::PosArg($pos).into_pos()
+    }};
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! __color {
     ($hex:literal) => {
         $crate::Color::Hex($hex)
     };
+    ([$r:expr, $g:expr, $b:expr $(,)?]) => {
+        $crate::Color::rgba($r, $g, $b, 1.0)
+    };
+    ([$r:expr, $g:expr, $b:expr, $a:expr $(,)?]) => {
+        $crate::Color::rgba($r, $g, $b, $a)
+    };
     ($name:expr) => {{
         use $crate::Color::*;
         $name
@@ -0,0 +1,343 @@
+//! Owned records, which can be stored and submitted later.
+
+use crate::{Color, Record, Visual, VisualKind};
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+/// An owned version of a [`Record`].
+///
+/// `Record`s borrow their text and metadata, so they can't outlive the
+/// vlog directive. A `RecordOwned` copies everything, so it can be buffered
+/// and submitted later, e.g. with [`VLog::vlog_batch`](crate::VLog::vlog_batch).
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{Record, RecordOwned, Visual};
+///
+/// let owned = RecordOwned::from(
+///     &Record::builder()
+///         .args(format_args!("{} steps", 3))
+///         .surface("main_surface")
+///         .visual(Visual::Message)
+///         .build(),
+/// );
+/// assert_eq!(owned.args(), "3 steps");
+/// owned.with_record(|record| {
+///     assert_eq!(record.surface(), "main_surface");
+///     assert_eq!(record.args().to_string(), "3 steps");
+/// });
+/// ```
+///
+/// With the `serde` feature, `RecordOwned` can be serialized, e.g. to record
+/// vlogs to disk and replay them later:
+///
+/// ```
+/// # #[cfg(feature = "serde")]
+/// # fn main() {
+/// use v_log::{Color, PointStyle, Record, RecordOwned, Visual};
+///
+/// let owned = RecordOwned::from(
+///     &Record::builder()
+///         .args(format_args!("hit {}", 7))
+///         .surface("main_surface")
+///         .target("physics")
+///         .visual(Visual::Point { x: 1.0, y: 2.0, z: 0.0, style: PointStyle::Circle })
+///         .color(Color::rgba(1.0, 0.5, 0.0, 1.0))
+///         .line(Some(42))
+///         .build(),
+/// );
+/// let json = serde_json::to_string(&owned).unwrap();
+/// let back: RecordOwned = serde_json::from_str(&json).unwrap();
+/// assert_eq!(back.args(), "hit 7");
+/// assert_eq!((back.surface(), back.target(), back.line()), ("main_surface", "physics", Some(42)));
+/// back.with_record(|back| owned.with_record(|owned| assert!(back.approx_eq(owned, 0.0, 0))));
+/// # }
+/// # #[cfg(not(feature = "serde"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RecordOwned {
+    surface: String,
+    target: String,
+    visual: Visual,
+    color: Color,
+    opacity: f32,
+    size: f64,
+    args: String,
+    module_path: Option<String>,
+    file: Option<String>,
+    line: Option<u32>,
+    call_site_id: u64,
+    path_id: u64,
+    tooltip: Option<String>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    z_order: i32,
+    #[cfg(feature = "std")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    ttl: Option<std::time::Duration>,
+    #[cfg(feature = "kv")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty")
+    )]
+    key_values: Vec<(String, crate::kv::ValueOwned)>,
+}
+
+impl RecordOwned {
+    /// The formatted message/label text.
+    #[inline]
+    pub fn args(&self) -> &str {
+        &self.args
+    }
+
+    /// The visual element to draw.
+    #[inline]
+    pub fn visual(&self) -> &Visual {
+        &self.visual
+    }
+
+    /// The color of the visual element.
+    #[inline]
+    pub fn color(&self) -> &Color {
+        &self.color
+    }
+
+    /// The opacity of the visual element in `[0, 1]`.
+    #[inline]
+    pub fn opacity(&self) -> f32 {
+        self.opacity
+    }
+
+    /// The size of the visual element.
+    #[inline]
+    pub fn size(&self) -> f64 {
+        self.size
+    }
+
+    /// The size of the visual element, or `default` if the size is `<= 0.0`.
+    ///
+    /// See [`Record::size_or_default`].
+    #[inline]
+    pub fn size_or_default(&self, default: f64) -> f64 {
+        if self.size <= 0.0 {
+            default
+        } else {
+            self.size
+        }
+    }
+
+    /// The name of the target of the directive.
+    #[inline]
+    pub fn target(&self) -> &str {
+        &self.target
+    }
+
+    /// The name of the surface of the directive.
+    #[inline]
+    pub fn surface(&self) -> &str {
+        &self.surface
+    }
+
+    /// The module path of the message.
+    #[inline]
+    pub fn module_path(&self) -> Option<&str> {
+        self.module_path.as_deref()
+    }
+
+    /// The source file containing the message.
+    #[inline]
+    pub fn file(&self) -> Option<&str> {
+        self.file.as_deref()
+    }
+
+    /// The line containing the message.
+    #[inline]
+    pub fn line(&self) -> Option<u32> {
+        self.line
+    }
+
+    /// A stable identifier of the source location of the directive.
+    ///
+    /// See [`Record::call_site_id`].
+    #[inline]
+    pub fn call_site_id(&self) -> u64 {
+        self.call_site_id
+    }
+
+    /// Identifies the records which belong to the same path.
+    ///
+    /// See [`Record::path_id`].
+    #[inline]
+    pub fn path_id(&self) -> u64 {
+        self.path_id
+    }
+
+    /// Additional text, which interactive vloggers show on demand.
+    ///
+    /// See [`Record::tooltip`].
+    #[inline]
+    pub fn tooltip(&self) -> Option<&str> {
+        self.tooltip.as_deref()
+    }
+
+    /// The draw order of the visual element on 2D surfaces.
+    ///
+    /// See [`Record::z_order`].
+    #[inline]
+    pub fn z_order(&self) -> i32 {
+        self.z_order
+    }
+
+    /// How long the visual element should stay visible.
+    ///
+    /// See [`Record::ttl`].
+    ///
+    /// Requires the `std` feature.
+    #[cfg(feature = "std")]
+    #[inline]
+    pub fn ttl(&self) -> Option<std::time::Duration> {
+        self.ttl
+    }
+
+    /// The structured key-value pairs of the directive.
+    ///
+    /// See [`Record::key_values`].
+    ///
+    /// Requires the `kv` feature.
+    #[cfg(feature = "kv")]
+    #[inline]
+    pub fn key_values(&self) -> &[(String, crate::kv::ValueOwned)] {
+        &self.key_values
+    }
+
+    /// Calls `f` with a borrowed [`Record`] of this record.
+    ///
+    /// A closure is needed, as the [`fmt::Arguments`](core::fmt::Arguments)
+    /// of the record can't outlive the statement it is created in.
+    pub fn with_record<R>(&self, f: impl FnOnce(&Record) -> R) -> R {
+        #[cfg(feature = "kv")]
+        let key_values: Vec<_> = self
+            .key_values
+            .iter()
+            .map(|(key, value)| (key.as_str(), value.as_value()))
+            .collect();
+        let mut builder = Record::builder();
+        #[cfg(feature = "std")]
+        builder.ttl(self.ttl);
+        #[cfg(feature = "kv")]
+        builder.key_values(&key_values);
+        f(&builder
+            .args(format_args!("{}", self.args))
+            .surface(&self.surface)
+            .target(&self.target)
+            .visual(self.visual.clone())
+            .color(self.color)
+            .opacity(self.opacity)
+            .size(self.size)
+            .module_path(self.module_path.as_deref())
+            .file(self.file.as_deref())
+            .line(self.line)
+            .call_site_id(self.call_site_id)
+            .path_id(self.path_id)
+            .tooltip(self.tooltip.as_deref())
+            .z_order(self.z_order)
+            .build())
+    }
+}
+
+impl<'a> From<&Record<'a>> for RecordOwned {
+    fn from(record: &Record<'a>) -> Self {
+        RecordOwned {
+            surface: record.surface().into(),
+            target: record.target().into(),
+            visual: record.visual().clone(),
+            color: *record.color(),
+            opacity: record.opacity(),
+            size: record.size(),
+            args: record.args().to_string(),
+            module_path: record.module_path().map(Into::into),
+            file: record.file().map(Into::into),
+            line: record.line(),
+            call_site_id: record.call_site_id(),
+            path_id: record.path_id(),
+            tooltip: record.tooltip().map(Into::into),
+            z_order: record.z_order(),
+            #[cfg(feature = "std")]
+            ttl: record.ttl(),
+            #[cfg(feature = "kv")]
+            key_values: record
+                .key_values()
+                .iter()
+                .map(|(key, value)| (key.to_string(), (*value).into()))
+                .collect(),
+        }
+    }
+}
+
+/// Group a batch of records by surface and then by [`VisualKind`].
+///
+/// Returns the indices of the records in each group. The groups are ordered by the
+/// first appearance of their surface and then of their kind on that surface, and the
+/// records keep their order within a group. Vloggers can call this in
+/// [`VLog::vlog_batch`](crate::VLog::vlog_batch) to draw all records of a group
+/// at once, which minimizes state changes e.g. in GPU backends.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{group_by_surface_and_kind, PointStyle, Record, RecordOwned, Visual, VisualKind};
+///
+/// let point = Visual::Point { x: 0.0, y: 0.0, z: 0.0, style: PointStyle::Point };
+/// let records: Vec<RecordOwned> = [
+///     ("a", point.clone()),
+///     ("b", Visual::Message),
+///     ("a", Visual::Message),
+///     ("a", point.clone()),
+///     ("b", point),
+///     ("a", Visual::Message),
+/// ]
+/// .into_iter()
+/// .map(|(surface, visual)| (&Record::builder().surface(surface).visual(visual).build()).into())
+/// .collect();
+///
+/// assert_eq!(
+///     group_by_surface_and_kind(&records),
+///     [
+///         ("a", VisualKind::Point, vec![0, 3]),
+///         ("a", VisualKind::Message, vec![2, 5]),
+///         ("b", VisualKind::Message, vec![1]),
+///         ("b", VisualKind::Point, vec![4]),
+///     ]
+/// );
+/// ```
+pub fn group_by_surface_and_kind(records: &[RecordOwned]) -> Vec<(&str, VisualKind, Vec<usize>)> {
+    let mut surfaces = Vec::new();
+    let mut groups: Vec<(&str, VisualKind, Vec<usize>)> = Vec::new();
+    for (i, record) in records.iter().enumerate() {
+        let (surface, kind) = (record.surface(), record.visual().kind());
+        match groups
+            .iter_mut()
+            .find(|(s, k, _)| *s == surface && *k == kind)
+        {
+            Some((_, _, indices)) => indices.push(i),
+            None => {
+                if !surfaces.contains(&surface) {
+                    surfaces.push(surface);
+                }
+                groups.push((surface, kind, alloc::vec![i]));
+            }
+        }
+    }
+    // the groups are ordered by their first record, so a stable sort by surface is enough.
+    groups.sort_by_key(|(s, _, _)| surfaces.iter().position(|surface| surface == s));
+    groups
+}
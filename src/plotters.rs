@@ -0,0 +1,557 @@
+//! A vlogger which renders 2D surfaces with the `plotters` crate.
+
+use crate::{
+    Color, GridPlane, LineStyle, Metadata, PointStyle, Record, RecordOwned, TextAlignment, VLog,
+    Visual,
+};
+use plotters::coord::CoordTranslate;
+use plotters::drawing::{DrawingArea, DrawingAreaErrorKind};
+use plotters::element::{
+    Circle, Cross, DashedPathElement, EmptyElement, PathElement, Polygon, Rectangle, Text,
+};
+use plotters::prelude::DrawingBackend;
+use plotters::style::text_anchor::{HPos, Pos, VPos};
+use plotters::style::{IntoFont, RGBAColor, ShapeStyle};
+use std::collections::BTreeMap;
+use std::sync::Mutex;
+
+/// Resolves the color to its RGBA bytes, see [`Color::to_rgba8`].
+///
+/// Requires the `plotters` feature.
+///
+/// # Examples
+///
+/// ```
+/// use plotters::style::RGBAColor;
+/// use v_log::Color;
+///
+/// let RGBAColor(r, g, b, a) = Color::Error.into();
+/// assert_eq!((r, g, b, a), (0xf4, 0x43, 0x36, 1.0));
+/// let RGBAColor(r, g, b, a) = Color::Hex(0x11223380).into();
+/// assert_eq!((r, g, b), (0x11, 0x22, 0x33));
+/// assert!((a - 128.0 / 255.0).abs() < 1e-9);
+/// ```
+impl From<Color> for RGBAColor {
+    fn from(color: Color) -> Self {
+        let [r, g, b, a] = color.to_rgba8();
+        RGBAColor(r, g, b, a as f64 / 255.0)
+    }
+}
+
+/// A vlogger which collects the records of 2D surfaces and draws them onto
+/// a [`DrawingArea`] of the [`plotters`] crate, e.g. the plotting area of a chart.
+///
+/// Points, point clouds, lines, rays, arcs, boxes, axes, ellipses, curves, polygons, meshes, vector fields,
+/// grids in the xy-plane and labels are drawn, the z-coordinate and messages are
+/// ignored. Records with a higher [`z_order`](crate::Record::z_order) are drawn on top,
+/// otherwise the records are drawn in order. Marker sizes, line thicknesses and text sizes are in pixels, while the
+/// sizes of the absolute point styles (e.g. [`PointStyle::Circle`]) are mapped
+/// from world units. Dashed outlines are drawn solid, arcs and Bézier curves have no arrow heads and
+/// label rotation is ignored.
+/// Drawing labels with a rasterizing backend requires a font feature of `plotters`,
+/// e.g. `ttf`.
+/// [`clear`](VLog::clear) removes the collected records of a surface.
+///
+/// Requires the `plotters` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use plotters::prelude::*;
+/// # use plotters_backend::{BackendColor, BackendCoord, DrawingErrorKind};
+/// # use std::cell::RefCell;
+/// # use std::rc::Rc;
+/// # // a backend which collects the drawn pixels.
+/// # struct Pixels(Rc<RefCell<Vec<BackendCoord>>>);
+/// # impl DrawingBackend for Pixels {
+/// #     type ErrorType = std::io::Error;
+/// #     fn get_size(&self) -> (u32, u32) { (100, 100) }
+/// #     fn ensure_prepared(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> { Ok(()) }
+/// #     fn present(&mut self) -> Result<(), DrawingErrorKind<Self::ErrorType>> { Ok(()) }
+/// #     fn draw_pixel(&mut self, p: BackendCoord, _: BackendColor) -> Result<(), DrawingErrorKind<Self::ErrorType>> {
+/// #         Ok(self.0.borrow_mut().push(p))
+/// #     }
+/// # }
+/// use v_log::{point, polyline, LineStyle, PlottersVLogger, Record, VLog, Visual};
+///
+/// let vlogger = PlottersVLogger::new();
+/// point!(vlogger: vlogger, "main_surface", [2.0, 8.0], 5.0, Info);
+/// polyline!(vlogger: vlogger, "main_surface", ([5.0, 5.0], [9.0, 5.0]), 1.0, Base, "->");
+/// assert_eq!(vlogger.len("main_surface"), 2);
+///
+/// // malformed records, e.g. deserialized ones, are skipped when drawing.
+/// let polygon = Visual::Polygon {
+///     points: Vec::new(),
+///     filled: true,
+///     style: LineStyle::Simple,
+/// };
+/// let mesh = Visual::Mesh {
+///     vertices: vec![[0.0, 0.0, 0.0]],
+///     indices: vec![0, 1, 2],
+///     shaded: false,
+/// };
+/// for visual in [polygon, mesh] {
+///     vlogger.vlog(&Record::builder().surface("main_surface").visual(visual).build());
+/// }
+///
+/// # let pixels = Rc::new(RefCell::new(Vec::new()));
+/// # let backend = Pixels(pixels.clone());
+/// let root = backend.into_drawing_area();
+/// let chart = ChartBuilder::on(&root).build_cartesian_2d(0.0..10.0, 0.0..10.0).unwrap();
+/// vlogger.draw_to("main_surface", chart.plotting_area()).unwrap();
+/// let pixels = pixels.borrow();
+/// // the y-axis points up, so the point is in the upper left.
+/// assert!(pixels.contains(&(20, 20)));
+/// assert!(pixels.contains(&(70, 50)));
+/// assert!(!pixels.contains(&(20, 80)));
+/// ```
+#[derive(Debug, Default)]
+pub struct PlottersVLogger {
+    surfaces: Mutex<BTreeMap<String, Vec<RecordOwned>>>,
+}
+
+impl PlottersVLogger {
+    /// Create a new `PlottersVLogger` without any records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of collected records of a surface.
+    pub fn len(&self, surface: &str) -> usize {
+        self.lock().get(surface).map_or(0, Vec::len)
+    }
+
+    /// Draw the collected records of a surface onto the drawing area.
+    ///
+    /// The coordinates of the records are mapped with the coordinate system of the area,
+    /// e.g. the ranges of a chart. The area isn't cleared before drawing.
+    ///
+    /// # Errors
+    ///
+    /// Returns the first error of the drawing backend.
+    pub fn draw_to<DB, CT>(
+        &self,
+        surface: &str,
+        area: &DrawingArea<DB, CT>,
+    ) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+    where
+        DB: DrawingBackend,
+        CT: CoordTranslate<From = (f64, f64)>,
+    {
+        let surfaces = self.lock();
+        let mut records: Vec<&RecordOwned> = surfaces.get(surface).into_iter().flatten().collect();
+        // stable, so records with the same z order keep their order.
+        records.sort_by_key(|record| record.z_order());
+        for record in records {
+            draw_record(area, record)?;
+        }
+        Ok(())
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, BTreeMap<String, Vec<RecordOwned>>> {
+        // the records are always in a valid state, so poisoning can be ignored.
+        self.surfaces.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+fn draw_record<DB, CT>(
+    area: &DrawingArea<DB, CT>,
+    record: &RecordOwned,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate<From = (f64, f64)>,
+{
+    let RGBAColor(r, g, b, a) = (*record.color()).into();
+    let color = RGBAColor(r, g, b, a * record.opacity().clamp(0.0, 1.0) as f64);
+    let stroke = ShapeStyle::from(color);
+    let fill = stroke.filled();
+    match *record.visual() {
+        Visual::Point { x, y, style, .. } => {
+            draw_point(area, (x, y), record.size_or_default(5.0), style, stroke)
+        }
+        Visual::PointCloud { ref points, style } => {
+            let size = record.size_or_default(5.0);
+            for p in points {
+                draw_point(area, (p[0], p[1]), size, style, stroke)?;
+            }
+            Ok(())
+        }
+        Visual::Line {
+            x1,
+            y1,
+            x2,
+            y2,
+            style,
+            world_thickness,
+            ..
+        } => {
+            let width = match world_thickness {
+                Some(thickness) => {
+                    let (p, q) = (
+                        area.map_coordinate(&(x1, y1)),
+                        area.map_coordinate(&(x1 + thickness, y1)),
+                    );
+                    (q.0 - p.0).unsigned_abs()
+                }
+                None => record.size_or_default(1.0).round() as u32,
+            }
+            .max(1);
+            let stroke = stroke.stroke_width(width);
+            let points = [(x1, y1), (x2, y2)];
+            if let LineStyle::Dashed = style {
+                let dash = 4 * width as i32;
+                return area.draw(&DashedPathElement::new(points, dash, dash, stroke));
+            }
+            area.draw(&PathElement::new(points, stroke))?;
+            draw_head(area, (x1, y1), (x2, y2), width, style, stroke)
+        }
+        Visual::Ray {
+            origin,
+            direction,
+            style,
+        } => {
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let stroke = stroke.stroke_width(width);
+            // extend the ray in pixels beyond the drawing area, as its bounds in
+            // world units are not known.
+            let from = (origin[0], origin[1]);
+            let (p, q) = (
+                area.map_coordinate(&from),
+                area.map_coordinate(&(origin[0] + direction[0], origin[1] + direction[1])),
+            );
+            let (dx, dy) = ((q.0 - p.0) as f64, (q.1 - p.1) as f64);
+            let len = dx.hypot(dy);
+            if len == 0.0 {
+                return Ok(());
+            }
+            let (w, h) = area.dim_in_pixel();
+            let far = (p.0.unsigned_abs() + p.1.unsigned_abs() + 2 * (w + h)) as f64 / len;
+            let points = vec![(0, 0), ((dx * far) as i32, (dy * far) as i32)];
+            let at = EmptyElement::at(from);
+            if let LineStyle::Dashed = style {
+                let dash = 4 * width as i32;
+                return area.draw(&(at + DashedPathElement::new(points, dash, dash, stroke)));
+            }
+            area.draw(&(at + PathElement::new(points, stroke)))
+        }
+        Visual::VectorField {
+            ref origins,
+            ref vectors,
+            scale,
+        } => {
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let stroke = stroke.stroke_width(width);
+            for (o, v) in origins.iter().zip(vectors) {
+                let (from, to) = ((o[0], o[1]), (o[0] + scale * v[0], o[1] + scale * v[1]));
+                area.draw(&PathElement::new([from, to], stroke))?;
+                draw_head(area, from, to, width, LineStyle::Arrow, stroke)?;
+            }
+            Ok(())
+        }
+        Visual::Arc { style, .. } | Visual::Bezier { style, .. } => {
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let stroke = stroke.stroke_width(width);
+            let visual = record.visual();
+            let points = visual
+                .arc_points(64)
+                .or_else(|| visual.bezier_points(64))
+                .unwrap_or_default();
+            let points: Vec<_> = points.iter().map(|p| (p[0], p[1])).collect();
+            if let LineStyle::Dashed = style {
+                let dash = 4 * width as i32;
+                return area.draw(&DashedPathElement::new(points, dash, dash, stroke));
+            }
+            area.draw(&PathElement::new(points, stroke))
+        }
+        Visual::Grid {
+            plane: GridPlane::XY,
+            ..
+        } => {
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let stroke = stroke.stroke_width(width);
+            for [a, b] in record.visual().grid_lines().unwrap_or_default() {
+                area.draw(&PathElement::new([(a[0], a[1]), (b[0], b[1])], stroke))?;
+            }
+            Ok(())
+        }
+        Visual::Spline {
+            ref points, closed, ..
+        } => {
+            if points.is_empty() {
+                return Ok(());
+            }
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let mut points: Vec<_> = points.iter().map(|p| (p[0], p[1])).collect();
+            if closed {
+                points.push(points[0]);
+            }
+            area.draw(&PathElement::new(points, stroke.stroke_width(width)))
+        }
+        Visual::Aabb { min, max, style } => {
+            if let LineStyle::None = style {
+                return Ok(());
+            }
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let stroke = stroke.stroke_width(width);
+            let outline = vec![
+                (min[0], min[1]),
+                (max[0], min[1]),
+                (max[0], max[1]),
+                (min[0], max[1]),
+                (min[0], min[1]),
+            ];
+            if let LineStyle::Dashed = style {
+                let dash = 4 * width as i32;
+                return area.draw(&DashedPathElement::new(outline, dash, dash, stroke));
+            }
+            area.draw(&PathElement::new(outline, stroke))
+        }
+        Visual::Axes { origin, .. } => {
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let tips = record.visual().axes_tips().unwrap_or_default();
+            for (tip, color) in tips.iter().zip([Color::X, Color::Y, Color::Z]) {
+                let RGBAColor(r, g, b, a) = color.into();
+                let color = RGBAColor(r, g, b, a * record.opacity().clamp(0.0, 1.0) as f64);
+                let stroke = ShapeStyle::from(color).stroke_width(width);
+                let (from, to) = ((origin[0], origin[1]), (tip[0], tip[1]));
+                area.draw(&PathElement::new([from, to], stroke))?;
+                draw_head(area, from, to, width, LineStyle::Arrow, stroke)?;
+            }
+            Ok(())
+        }
+        Visual::Obb { .. } => {
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let stroke = stroke.stroke_width(width);
+            let corners = record.visual().obb_corners().unwrap_or_default();
+            for (i, a) in corners.iter().enumerate() {
+                for bit in [1, 2, 4] {
+                    if i & bit == 0 {
+                        let b = corners[i | bit];
+                        area.draw(&PathElement::new([(a[0], a[1]), (b[0], b[1])], stroke))?;
+                    }
+                }
+            }
+            Ok(())
+        }
+        Visual::Ellipse { filled, .. } => {
+            let points = record.visual().ellipse_points(64).unwrap_or_default();
+            let points: Vec<_> = points.iter().map(|p| (p[0], p[1])).collect();
+            if filled {
+                return area.draw(&Polygon::new(points, fill));
+            }
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            area.draw(&PathElement::new(points, stroke.stroke_width(width)))
+        }
+        Visual::Mesh {
+            ref vertices,
+            ref indices,
+            shaded,
+        } => {
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            let stroke = stroke.stroke_width(width);
+            for triangle in indices.chunks_exact(3) {
+                // triangles with indices out of range are skipped.
+                let Some(mut points) = triangle
+                    .iter()
+                    .map(|&i| vertices.get(i as usize).map(|v| (v[0], v[1])))
+                    .collect::<Option<Vec<_>>>()
+                else {
+                    continue;
+                };
+                if shaded {
+                    area.draw(&Polygon::new(points, fill))?;
+                } else {
+                    points.push(points[0]);
+                    area.draw(&PathElement::new(points, stroke))?;
+                }
+            }
+            Ok(())
+        }
+        Visual::Polygon {
+            ref points,
+            filled,
+            style,
+        } => {
+            if points.is_empty() {
+                return Ok(());
+            }
+            let points: Vec<_> = points.iter().map(|p| (p[0], p[1])).collect();
+            if filled {
+                area.draw(&Polygon::new(points.clone(), fill))?;
+            }
+            if let LineStyle::None = style {
+                return Ok(());
+            }
+            let width = record.size_or_default(1.0).round().max(1.0) as u32;
+            // the outline uses the color at full alpha.
+            let stroke = ShapeStyle::from(RGBAColor(r, g, b, 1.0)).stroke_width(width);
+            let mut outline = points;
+            outline.push(outline[0]);
+            if let LineStyle::Dashed = style {
+                let dash = 4 * width as i32;
+                return area.draw(&DashedPathElement::new(outline, dash, dash, stroke));
+            }
+            area.draw(&PathElement::new(outline, stroke))
+        }
+        Visual::Label {
+            x,
+            y,
+            alignment,
+            max_width,
+            style,
+            ..
+        } => {
+            let h = match alignment {
+                TextAlignment::Left => HPos::Left,
+                TextAlignment::Right => HPos::Right,
+                TextAlignment::Center | TextAlignment::Flexible => HPos::Center,
+            };
+            let size = record.size_or_default(12.0);
+            let font = ("sans-serif", size)
+                .into_font()
+                .color(&color)
+                .pos(Pos::new(h, VPos::Center));
+            // the width in pixels, assuming an average glyph width of 0.6 times the size.
+            let max_chars = max_width.map(|w| {
+                let (p, q) = (
+                    area.map_coordinate(&(x, y)),
+                    area.map_coordinate(&(x + w, y)),
+                );
+                ((q.0 - p.0).unsigned_abs() as f64 / (0.6 * size)) as usize
+            });
+            let lines = style.wrap(record.args(), max_chars);
+            let line_height = 1.2 * size;
+            let top = -line_height * (lines.len() - 1) as f64 * 0.5;
+            for (i, line) in lines.into_iter().enumerate() {
+                let offset = (0, (top + line_height * i as f64).round() as i32);
+                area.draw(&(EmptyElement::at((x, y)) + Text::new(line, offset, font.clone())))?;
+            }
+            Ok(())
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Draws a point marker of the given style at `(x, y)`.
+fn draw_point<DB, CT>(
+    area: &DrawingArea<DB, CT>,
+    (x, y): (f64, f64),
+    size: f64,
+    style: PointStyle,
+    stroke: ShapeStyle,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate<From = (f64, f64)>,
+{
+    let fill = stroke.filled();
+    let at = EmptyElement::at((x, y));
+    let radius = match style {
+        PointStyle::FilledCircle
+        | PointStyle::Circle
+        | PointStyle::DashedCircle
+        | PointStyle::FilledSquare
+        | PointStyle::Square
+        | PointStyle::DashedSquare => {
+            // sizes in world units, assuming a uniform scale.
+            let (p, q) = (
+                area.map_coordinate(&(x, y)),
+                area.map_coordinate(&(x + size * 0.5, y)),
+            );
+            (q.0 - p.0).abs().max((q.1 - p.1).abs())
+        }
+        _ => (size * 0.5).round() as i32,
+    }
+    .max(1);
+    let square = [(-radius, -radius), (radius, radius)];
+    let diamond = vec![(0, -radius), (radius, 0), (0, radius), (-radius, 0)];
+    match style {
+        PointStyle::FilledCircle | PointStyle::Point => {
+            area.draw(&(at + Circle::new((0, 0), radius, fill)))
+        }
+        PointStyle::Circle | PointStyle::DashedCircle | PointStyle::PointOutline => {
+            area.draw(&(at + Circle::new((0, 0), radius, stroke)))
+        }
+        PointStyle::FilledSquare | PointStyle::PointSquare => {
+            area.draw(&(at + Rectangle::new(square, fill)))
+        }
+        PointStyle::Square | PointStyle::DashedSquare | PointStyle::PointSquareOutline => {
+            area.draw(&(at + Rectangle::new(square, stroke)))
+        }
+        PointStyle::PointCross => area.draw(&(at + Cross::new((0, 0), radius, stroke))),
+        PointStyle::PointDiamond => area.draw(&(at + Polygon::new(diamond, fill))),
+        PointStyle::PointDiamondOutline => {
+            let mut outline = diamond;
+            outline.push(outline[0]);
+            area.draw(&(at + PathElement::new(outline, stroke)))
+        }
+        _ => Ok(()),
+    }
+}
+
+/// Draws the head of a line with the given style at `to`.
+fn draw_head<DB, CT>(
+    area: &DrawingArea<DB, CT>,
+    from: (f64, f64),
+    to: (f64, f64),
+    width: u32,
+    style: LineStyle,
+    stroke: ShapeStyle,
+) -> Result<(), DrawingAreaErrorKind<DB::ErrorType>>
+where
+    DB: DrawingBackend,
+    CT: CoordTranslate<From = (f64, f64)>,
+{
+    // the arrow head in pixels, as the axes may be scaled differently.
+    let (p, q) = (area.map_coordinate(&from), area.map_coordinate(&to));
+    let (dx, dy) = ((q.0 - p.0) as f64, (q.1 - p.1) as f64);
+    let len = dx.hypot(dy);
+    if len == 0.0 {
+        return Ok(());
+    }
+    let head = 3.0 * width as f64 + 6.0;
+    let (ux, uy) = (dx / len * head, dy / len * head);
+    let back = |side: f64| {
+        (
+            (-ux + uy * side * 0.5) as i32,
+            (-uy - ux * side * 0.5) as i32,
+        )
+    };
+    let at = EmptyElement::at(to);
+    // the y-axis of the pixels points down, so positive sides are on the right.
+    match style {
+        LineStyle::Arrow => {
+            area.draw(&(at + PathElement::new(vec![back(1.0), (0, 0), back(-1.0)], stroke)))
+        }
+        LineStyle::InsideHarpoonCCW => {
+            area.draw(&(at + PathElement::new(vec![back(-1.0), (0, 0)], stroke)))
+        }
+        LineStyle::InsideHarpoonCW => {
+            area.draw(&(at + PathElement::new(vec![back(1.0), (0, 0)], stroke)))
+        }
+        _ => Ok(()),
+    }
+}
+
+impl VLog for PlottersVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        let mut surfaces = self.lock();
+        let records = surfaces.entry(record.surface().into()).or_default();
+        records.push(record.into());
+        if let Some(cap) = self.max_records(record.surface()) {
+            let excess = records.len().saturating_sub(cap);
+            records.drain(..excess);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.lock().remove(surface);
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,263 @@
+//! Positions of the vlog macros.
+
+/// A 2D position `[x, y]`, e.g. for [`RecordBuilder::visual`](crate::RecordBuilder::visual).
+///
+/// The macros accept the array as a position, just like any other `IntoIterator<Item = f64>`.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{point, pos2};
+///
+/// assert_eq!(pos2(1.0, 2.0), [1.0, 2.0]);
+/// point!("main_surface", pos2(1.0, 2.0), 5.0, Base);
+/// ```
+#[inline]
+pub const fn pos2(x: f64, y: f64) -> [f64; 2] {
+    [x, y]
+}
+
+/// A 3D position `[x, y, z]`, e.g. for [`RecordBuilder::visual`](crate::RecordBuilder::visual).
+///
+/// The macros accept the array as a position, just like any other `IntoIterator<Item = f64>`.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{point, pos3};
+///
+/// assert_eq!(pos3(1.0, 2.0, 3.0), [1.0, 2.0, 3.0]);
+/// point!("main_surface", pos3(1.0, 2.0, 3.0), 5.0, Base);
+/// ```
+#[inline]
+pub const fn pos3(x: f64, y: f64, z: f64) -> [f64; 3] {
+    [x, y, z]
+}
+
+/// A 3D position, which can be passed wherever the macros or functions take `IntoIterator<Item = f64>`.
+///
+/// It converts from `[f64; 2]`, `[f64; 3]` and with the `glam` feature from the vectors
+/// `Vec2`, `Vec3`, `DVec2` and `DVec3` of `glam`. 2D positions get a z of `0.0`, and the
+/// `f32` components of `Vec2` and `Vec3` are widened to `f64`, which is exact, so e.g.
+/// `0.1_f32` becomes `0.10000000149011612`, not `0.1`.
+///
+/// The macros also take the vectors directly, see [`Position`]. `Point` is useful
+/// in code which is generic over `IntoIterator<Item = f64>` itself.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{point, Point};
+///
+/// let p = Point::from([1.0, 2.0]);
+/// assert_eq!(p, Point { x: 1.0, y: 2.0, z: 0.0 });
+/// assert_eq!(p.into_iter().collect::<Vec<_>>(), [1.0, 2.0, 0.0]);
+/// point!("main_surface", p, 5.0, Base);
+/// ```
+///
+/// With the `glam` feature:
+///
+/// ```
+/// # #[cfg(feature = "glam")]
+/// # fn main() {
+/// use glam::{DVec3, Vec2, Vec3};
+/// use v_log::{point, Point};
+///
+/// assert_eq!(Point::from(Vec2::new(1.0, 2.0)), Point { x: 1.0, y: 2.0, z: 0.0 });
+/// assert_eq!(Point::from(Vec3::new(1.0, 2.0, 0.5)), Point { x: 1.0, y: 2.0, z: 0.5 });
+/// assert_eq!(Point::from(DVec3::new(0.1, 0.2, 0.3)), Point { x: 0.1, y: 0.2, z: 0.3 });
+/// // the widening keeps the value of the `f32`, which isn't the closest `f64` to 0.1.
+/// assert_eq!(Point::from(Vec2::new(0.1, 0.0)).x, 0.1_f32 as f64);
+///
+/// fn draw(p: impl IntoIterator<Item = f64>) {
+///     point!("main_surface", p, 5.0, Base);
+/// }
+/// draw(Point::from(Vec3::ONE));
+/// point!("main_surface", Vec3::ONE, 5.0, Base);
+/// point!("main_surface", DVec3::ONE, 5.0, Base);
+/// # }
+/// # #[cfg(not(feature = "glam"))]
+/// # fn main() {}
+/// ```
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Point {
+    /// The x-coordinate.
+    pub x: f64,
+    /// The y-coordinate.
+    pub y: f64,
+    /// The z-coordinate.
+    pub z: f64,
+}
+
+impl IntoIterator for Point {
+    type Item = f64;
+    type IntoIter = core::array::IntoIter<f64, 3>;
+
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        [self.x, self.y, self.z].into_iter()
+    }
+}
+
+impl From<[f64; 2]> for Point {
+    #[inline]
+    fn from([x, y]: [f64; 2]) -> Self {
+        Point { x, y, z: 0.0 }
+    }
+}
+
+impl From<[f64; 3]> for Point {
+    #[inline]
+    fn from([x, y, z]: [f64; 3]) -> Self {
+        Point { x, y, z }
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Vec2> for Point {
+    #[inline]
+    fn from(v: glam::Vec2) -> Self {
+        v.as_dvec2().into()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::Vec3> for Point {
+    #[inline]
+    fn from(v: glam::Vec3) -> Self {
+        v.as_dvec3().into()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::DVec2> for Point {
+    #[inline]
+    fn from(v: glam::DVec2) -> Self {
+        v.to_array().into()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl From<glam::DVec3> for Point {
+    #[inline]
+    fn from(v: glam::DVec3) -> Self {
+        v.to_array().into()
+    }
+}
+
+/// A type which the macros accept as a position, in addition to `IntoIterator<Item = f64>`.
+///
+/// The macros take positions as anything which yields the coordinates `x, y` and
+/// optionally `z`, e.g. `[f64; 2]`, `[f64; 3]` or `Vec<f64>`. Vector types of math crates
+/// usually don't implement `IntoIterator`, and due to the orphan rules neither this
+/// crate nor its users can add that, so this trait is the extension point for them.
+/// It's implemented for the vectors `Vec2`, `Vec3`, `DVec2` and `DVec3` of `glam` with the `glam` feature.
+///
+/// Types of other crates can be converted at the call site, e.g. with `DVec3::to_array`
+/// of `glam` or `<[f64; 3]>::from(point)` for a `Point3<f64>` of `nalgebra`.
+///
+/// `Position` is accepted for the single positions and directions of [`point!`], [`label!`],
+/// [`polyline!`] with two points, [`ray!`], [`arrow!`], [`aabb!`], [`axes!`], [`obb!`],
+/// [`arc!`], [`bezier!`], [`ellipse!`] and [`errorbar!`]. Lists of positions, e.g. of
+/// [`polyline!`] or [`point_cloud!`], still need to be `IntoIterator<Item = f64>`.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{point, Position};
+///
+/// struct Body {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// impl Position for Body {
+///     type Coords = [f64; 2];
+///
+///     fn coords(&self) -> [f64; 2] {
+///         [self.x as f64, self.y as f64]
+///     }
+/// }
+///
+/// let body = Body { x: 1.0, y: 2.0 };
+/// point!("main_surface", body, 5.0, Base);
+/// ```
+pub trait Position {
+    /// The coordinates, e.g. `[f64; 3]`.
+    type Coords: IntoIterator<Item = f64>;
+
+    /// The coordinates `x, y` and optionally `z`.
+    fn coords(&self) -> Self::Coords;
+}
+
+impl<T: Position + ?Sized> Position for &T {
+    type Coords = T::Coords;
+
+    #[inline]
+    fn coords(&self) -> Self::Coords {
+        (**self).coords()
+    }
+}
+
+/// The `f32` vectors of `glam` are widened to `f64`, see [`Point`].
+///
+/// Requires the `glam` feature.
+///
+/// # Examples
+///
+/// ```
+/// # #[cfg(feature = "glam")]
+/// # fn main() {
+/// use glam::{Vec2, Vec3};
+/// use v_log::{point, polyline, Position};
+///
+/// assert_eq!(Vec2::new(1.0, 2.0).coords(), [1.0, 2.0]);
+/// assert_eq!(Vec3::new(1.0, 2.0, 0.5).coords(), [1.0, 2.0, 0.5]);
+///
+/// let (pos, vel) = (Vec2::new(1.0, 2.0), Vec2::new(0.5, 0.0));
+/// point!("main_surface", pos, 5.0, Base);
+/// polyline!("main_surface", (pos, pos + vel), 1.0, Base, "->");
+/// point!("main_surface", &Vec3::ZERO, 5.0, Base, "o", "origin");
+/// # }
+/// # #[cfg(not(feature = "glam"))]
+/// # fn main() {}
+/// ```
+#[cfg(feature = "glam")]
+impl Position for glam::Vec2 {
+    type Coords = [f64; 2];
+
+    #[inline]
+    fn coords(&self) -> [f64; 2] {
+        self.as_dvec2().to_array()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl Position for glam::Vec3 {
+    type Coords = [f64; 3];
+
+    #[inline]
+    fn coords(&self) -> [f64; 3] {
+        self.as_dvec3().to_array()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl Position for glam::DVec2 {
+    type Coords = [f64; 2];
+
+    #[inline]
+    fn coords(&self) -> [f64; 2] {
+        self.to_array()
+    }
+}
+
+#[cfg(feature = "glam")]
+impl Position for glam::DVec3 {
+    type Coords = [f64; 3];
+
+    #[inline]
+    fn coords(&self) -> [f64; 3] {
+        self.to_array()
+    }
+}
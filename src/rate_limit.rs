@@ -0,0 +1,209 @@
+//! A vlogger adapter which limits the rate of records.
+
+use crate::{Metadata, Record, VLog};
+use std::collections::{BTreeMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::{Duration, Instant};
+
+// the times of the recently forwarded records, by surface and target.
+type History = BTreeMap<String, BTreeMap<String, VecDeque<Instant>>>;
+
+/// Drops the records of a target and surface once too many of them were sent recently.
+///
+/// A record is forwarded to the inner vlogger only if fewer than `max` records with the
+/// same target and surface were forwarded within the last `window`, so a noisy loop
+/// can't flood a surface. The counts are kept separately for every pair of target and surface.
+///
+/// [`enabled`](VLog::enabled) returns the value of the inner vlogger, as the limit
+/// depends on the time the record is sent. The limit is applied in [`vlog`](VLog::vlog).
+/// A [`clear`](VLog::clear) or [`transaction`](VLog::transaction) of a surface resets
+/// the counts of all targets on it, so a surface which is redrawn every frame gets
+/// the full budget every frame.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use v_log::{clear, point, Metadata, RateLimit, Record, VLog};
+///
+/// #[derive(Default)]
+/// struct CountingVLogger(AtomicUsize);
+///
+/// impl VLog for CountingVLogger {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, _: &Record) {
+///         self.0.fetch_add(1, Ordering::SeqCst);
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// let vlogger = RateLimit::per_second(CountingVLogger::default(), 100);
+/// for i in 0..1000 {
+///     point!(vlogger: vlogger, "main_surface", [i as f64, 0.0], 5.0, Base);
+/// }
+/// assert_eq!(vlogger.inner().0.load(Ordering::SeqCst), 100);
+///
+/// // other targets and surfaces have their own limit.
+/// point!(vlogger: vlogger, target: "physics", "main_surface", [0.0, 0.0], 5.0, Base);
+/// point!(vlogger: vlogger, "debug_surface", [0.0, 0.0], 5.0, Base);
+/// assert_eq!(vlogger.inner().0.load(Ordering::SeqCst), 102);
+///
+/// // a clear resets the limit of the surface.
+/// clear!(vlogger: vlogger, "main_surface");
+/// for i in 0..1000 {
+///     point!(vlogger: vlogger, "main_surface", [i as f64, 0.0], 5.0, Base);
+/// }
+/// assert_eq!(vlogger.inner().0.load(Ordering::SeqCst), 202);
+/// ```
+#[derive(Debug)]
+pub struct RateLimit<V> {
+    inner: V,
+    max: usize,
+    window: Duration,
+    history: Mutex<History>,
+}
+
+impl<V: VLog> RateLimit<V> {
+    /// Forward at most `max` records per target and surface within every `window` to `inner`.
+    pub fn new(inner: V, max: usize, window: Duration) -> Self {
+        RateLimit {
+            inner,
+            max,
+            window,
+            history: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Forward at most `max` records per target and surface and second to `inner`.
+    pub fn per_second(inner: V, max: usize) -> Self {
+        Self::new(inner, max, Duration::from_secs(1))
+    }
+
+    /// The inner vlogger.
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// Returns the inner vlogger.
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+
+    fn lock(&self) -> MutexGuard<'_, History> {
+        // the history is always in a valid state, so poisoning can be ignored.
+        self.history.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Count the record if it is within the limit.
+    fn admit(&self, metadata: &Metadata) -> bool {
+        let now = Instant::now();
+        let mut history = self.lock();
+        let targets = match history.get_mut(metadata.surface()) {
+            Some(targets) => targets,
+            None => history.entry(metadata.surface().into()).or_default(),
+        };
+        let times = match targets.get_mut(metadata.target()) {
+            Some(times) => times,
+            None => targets.entry(metadata.target().into()).or_default(),
+        };
+        while times
+            .front()
+            .map_or(false, |t| now.duration_since(*t) >= self.window)
+        {
+            times.pop_front();
+        }
+        if times.len() < self.max {
+            times.push_back(now);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+/// The vlogger of a transaction of a [`RateLimit`], which applies the same limit.
+struct Limited<'a, V> {
+    inner: &'a dyn VLog,
+    limit: &'a RateLimit<V>,
+}
+
+impl<V: VLog> VLog for Limited<'_, V> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.inner.enabled_record(record)
+    }
+
+    fn vlog(&self, record: &Record) {
+        if self.limit.admit(record.metadata()) {
+            self.inner.vlog(record);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.limit.lock().remove(surface);
+        self.inner.clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        self.limit.lock().remove(surface);
+        self.inner.remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        let limit = self.limit;
+        limit.lock().remove(surface);
+        self.inner.transaction(surface, &|inner| {
+            f(&Limited { inner, limit });
+        });
+    }
+
+    forward_vlog! {
+        inner => begin_frame, end_frame, push_group, pop_group, flush, on_shutdown,
+        set_draw_mode, set_view, snapshot, quantize_color, max_records
+    }
+}
+
+impl<V: VLog> VLog for RateLimit<V> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.inner.enabled(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.inner.enabled_record(record)
+    }
+
+    fn vlog(&self, record: &Record) {
+        if self.admit(record.metadata()) {
+            self.inner.vlog(record);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.lock().remove(surface);
+        self.inner.clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        self.lock().remove(surface);
+        self.inner.remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        // a transaction replaces the content of the surface like a clear.
+        self.lock().remove(surface);
+        self.inner.transaction(surface, &|inner| {
+            f(&Limited { inner, limit: self });
+        });
+    }
+
+    forward_vlog! {
+        inner => begin_frame, end_frame, push_group, pop_group, flush, on_shutdown,
+        set_draw_mode, set_view, snapshot, quantize_color, max_records
+    }
+}
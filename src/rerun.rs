@@ -0,0 +1,332 @@
+//! A vlogger which logs to a recording of the `rerun` crate.
+
+use crate::{Color, LineStyle, Metadata, Point, PointStyle, Record, VLog, Visual};
+use rerun::components::Radius;
+use rerun::datatypes::Vec3D;
+use rerun::{Arrows3D, Clear, EntityPath, LineStrips3D, Points3D, RecordingStream, TextLog};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// Resolves the color to its RGBA bytes, see [`Color::to_rgba8`].
+///
+/// Requires the `rerun` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::Color;
+///
+/// assert_eq!(rerun::Color::from(Color::Error).to_array(), [0xf4, 0x43, 0x36, 0xff]);
+/// assert_eq!(rerun::Color::from(Color::Hex(0x11223380)).to_u32(), 0x11223380);
+/// ```
+impl From<Color> for rerun::Color {
+    fn from(color: Color) -> Self {
+        let [r, g, b, a] = color.to_rgba8();
+        rerun::Color::from_unmultiplied_rgba(r, g, b, a)
+    }
+}
+
+/// Narrows the coordinates to the `f32` of rerun, e.g. for a [`rerun::Position3D`].
+///
+/// The coordinates are kept as they are, rerun's view coordinates decide which axis is up.
+///
+/// Requires the `rerun` feature.
+///
+/// # Examples
+///
+/// ```
+/// use rerun::Position3D;
+/// use v_log::Point;
+///
+/// let p: Position3D = Point::from([1.5, -2.0]).into();
+/// assert_eq!(p, Position3D::new(1.5, -2.0, 0.0));
+/// let p: Position3D = Point::from([0.1, 0.2, 0.3]).into();
+/// assert_eq!(p, Position3D::new(0.1, 0.2, 0.3));
+/// ```
+impl From<Point> for Vec3D {
+    fn from(p: Point) -> Self {
+        Vec3D::new(p.x as f32, p.y as f32, p.z as f32)
+    }
+}
+
+/// A vlogger which logs the records to a [`RecordingStream`] of the [`rerun`] crate,
+/// to inspect them in the rerun viewer.
+///
+/// Every surface is an entity path, so a surface `"robot/arm"` becomes the entity
+/// `robot/arm`. Every record is logged as a new child of it, as rerun replaces the
+/// data of an entity when it is logged again. [`clear`](VLog::clear) clears the entity
+/// of the surface and all its children. The records are logged on the `frame` timeline
+/// with the current [`frame`](crate::frame).
+///
+/// Points and point clouds are logged as [`Points3D`], lines, arcs, ellipses, curves,
+/// splines and polygon outlines as [`LineStrips3D`], except that lines with arrow heads,
+/// vector fields and coordinate axes are logged as [`Arrows3D`]. Labels are logged as
+/// points with the message as label, and messages as [`TextLog`] of the surface, with the
+/// level `ERROR`, `WARN` or `INFO` for the colors [`Color::Error`], [`Color::Warn`] and
+/// [`Color::Info`]. The other visuals are ignored. Sizes of the absolute point styles and
+/// world thicknesses are in scene units, the other sizes are in UI points.
+///
+/// Requires the `rerun` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{clear, point, polyline, RerunVLogger};
+///
+/// let (stream, storage) = rerun::RecordingStreamBuilder::new("v_log_example").memory().unwrap();
+/// let vlogger = RerunVLogger::new(stream);
+/// point!(vlogger: vlogger, "robot/arm", [2.0, 8.0], 5.0, Info);
+/// polyline!(vlogger: vlogger, "robot/arm", ([5.0, 5.0], [9.0, 5.0]), 1.0, Base, "->");
+/// clear!(vlogger: vlogger, "robot/arm");
+/// vlogger.stream().flush_blocking().unwrap();
+/// assert!(!storage.take().is_empty());
+/// ```
+///
+/// Records without points, e.g. deserialized ones, are ignored:
+///
+/// ```
+/// use v_log::{LineStyle, Record, RerunVLogger, VLog, Visual};
+///
+/// let (stream, storage) = rerun::RecordingStreamBuilder::new("v_log_example").memory().unwrap();
+/// let vlogger = RerunVLogger::new(stream);
+/// vlogger.stream().flush_blocking().unwrap();
+/// storage.take();
+/// let polygon = Visual::Polygon {
+///     points: Vec::new(),
+///     filled: false,
+///     style: LineStyle::Simple,
+/// };
+/// let spline = Visual::Spline {
+///     points: Vec::new(),
+///     tension: 0.5,
+///     closed: true,
+///     style: LineStyle::Simple,
+/// };
+/// for visual in [polygon, spline] {
+///     vlogger.vlog(&Record::builder().surface("main_surface").visual(visual).build());
+/// }
+/// vlogger.stream().flush_blocking().unwrap();
+/// assert!(storage.take().is_empty());
+/// ```
+#[derive(Debug)]
+pub struct RerunVLogger {
+    stream: RecordingStream,
+    counts: Mutex<BTreeMap<String, u64>>,
+}
+
+impl RerunVLogger {
+    /// Create a new `RerunVLogger` logging to `stream`.
+    pub fn new(stream: RecordingStream) -> Self {
+        RerunVLogger {
+            stream,
+            counts: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The recording stream the records are logged to.
+    pub fn stream(&self) -> &RecordingStream {
+        &self.stream
+    }
+
+    /// The entity path for the next record of a surface.
+    fn next_entity(&self, surface: &str) -> EntityPath {
+        let mut counts = self.lock();
+        let count = counts.entry(surface.into()).or_default();
+        let mut parts = EntityPath::from(surface).to_vec();
+        parts.push(count.to_string().into());
+        *count += 1;
+        EntityPath::new(parts)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, u64>> {
+        // the counts are always in a valid state, so poisoning can be ignored.
+        self.counts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    fn set_time(&self) {
+        self.stream
+            .set_time_sequence("frame", crate::frame().min(i64::MAX as u64) as i64);
+    }
+}
+
+fn vec3(p: [f64; 3]) -> Vec3D {
+    Point::from(p).into()
+}
+
+/// The radius of a size in UI points, or in scene units if `world` is set.
+fn radius(size: f64, world: bool) -> Radius {
+    let r = (size * 0.5).max(0.0) as f32;
+    if world {
+        Radius::new_scene_units(r)
+    } else {
+        Radius::new_ui_points(r)
+    }
+}
+
+impl VLog for RerunVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        let [r, g, b, a] = record.color().to_rgba8();
+        let a = (a as f32 * record.opacity().clamp(0.0, 1.0)).round() as u8;
+        let color = rerun::Color::from_unmultiplied_rgba(r, g, b, a);
+        let line_radius = |world_thickness: Option<f64>| match world_thickness {
+            Some(thickness) => radius(thickness, true),
+            None => radius(record.size_or_default(1.0), false),
+        };
+        let strip = |points: Vec<[f64; 3]>| {
+            LineStrips3D::new([points.into_iter().map(vec3).collect::<Vec<_>>()])
+                .with_colors([color])
+                .with_radii([line_radius(None)])
+        };
+        self.set_time();
+        let surface = record.surface();
+        // errors can only come from the serialization, and vloggers can't report them.
+        let _ = match *record.visual() {
+            Visual::Message => {
+                let mut text = TextLog::new(record.args().to_string()).with_color(color);
+                match *record.color() {
+                    Color::Error => text = text.with_level("ERROR"),
+                    Color::Warn => text = text.with_level("WARN"),
+                    Color::Info => text = text.with_level("INFO"),
+                    _ => {}
+                }
+                self.stream.log(surface, &text)
+            }
+            Visual::Label { x, y, z, .. } => self.stream.log(
+                self.next_entity(surface),
+                &Points3D::new([vec3([x, y, z])])
+                    .with_colors([color])
+                    .with_radii([Radius::ZERO])
+                    .with_labels([record.args().to_string()]),
+            ),
+            Visual::Point { x, y, z, style } => {
+                let world = matches!(
+                    style,
+                    PointStyle::FilledCircle
+                        | PointStyle::Circle
+                        | PointStyle::DashedCircle
+                        | PointStyle::FilledSquare
+                        | PointStyle::Square
+                        | PointStyle::DashedSquare
+                );
+                self.stream.log(
+                    self.next_entity(surface),
+                    &Points3D::new([vec3([x, y, z])])
+                        .with_colors([color])
+                        .with_radii([radius(record.size_or_default(5.0), world)]),
+                )
+            }
+            Visual::PointCloud { ref points, .. } => self.stream.log(
+                self.next_entity(surface),
+                &Points3D::new(points.iter().copied().map(vec3))
+                    .with_colors([color])
+                    .with_radii([radius(record.size_or_default(5.0), false)]),
+            ),
+            Visual::Line {
+                x1,
+                y1,
+                z1,
+                x2,
+                y2,
+                z2,
+                style,
+                world_thickness,
+            } => {
+                let entity = self.next_entity(surface);
+                if let LineStyle::Arrow = style {
+                    self.stream.log(
+                        entity,
+                        &Arrows3D::from_vectors([vec3([x2 - x1, y2 - y1, z2 - z1])])
+                            .with_origins([vec3([x1, y1, z1])])
+                            .with_colors([color])
+                            .with_radii([line_radius(world_thickness)]),
+                    )
+                } else {
+                    self.stream.log(
+                        entity,
+                        &LineStrips3D::new([[vec3([x1, y1, z1]), vec3([x2, y2, z2])]])
+                            .with_colors([color])
+                            .with_radii([line_radius(world_thickness)]),
+                    )
+                }
+            }
+            Visual::VectorField {
+                ref origins,
+                ref vectors,
+                scale,
+            } => self.stream.log(
+                self.next_entity(surface),
+                &Arrows3D::from_vectors(
+                    vectors
+                        .iter()
+                        .map(|v| vec3([scale * v[0], scale * v[1], scale * v[2]])),
+                )
+                .with_origins(origins.iter().copied().map(vec3))
+                .with_colors([color])
+                .with_radii([line_radius(None)]),
+            ),
+            Visual::Axes { origin, .. } => {
+                let tips = record.visual().axes_tips().unwrap_or_default();
+                let colors = [Color::X, Color::Y, Color::Z].map(|axis| {
+                    let [r, g, b, a] = axis.to_rgba8();
+                    let a = (a as f32 * record.opacity().clamp(0.0, 1.0)).round() as u8;
+                    rerun::Color::from_unmultiplied_rgba(r, g, b, a)
+                });
+                self.stream.log(
+                    self.next_entity(surface),
+                    &Arrows3D::from_vectors(tips.map(|tip| {
+                        vec3([tip[0] - origin[0], tip[1] - origin[1], tip[2] - origin[2]])
+                    }))
+                    .with_origins((0..3).map(|_| vec3(origin)))
+                    .with_colors(colors)
+                    .with_radii([line_radius(None)]),
+                )
+            }
+            Visual::Arc { .. } | Visual::Bezier { .. } | Visual::Ellipse { .. } => {
+                let visual = record.visual();
+                let points = visual
+                    .arc_points(64)
+                    .or_else(|| visual.bezier_points(64))
+                    .or_else(|| visual.ellipse_points(64))
+                    .unwrap_or_default();
+                self.stream.log(self.next_entity(surface), &strip(points))
+            }
+            Visual::Spline {
+                ref points, closed, ..
+            } => {
+                if points.is_empty() {
+                    return;
+                }
+                let mut points = points.clone();
+                if closed {
+                    points.push(points[0]);
+                }
+                self.stream.log(self.next_entity(surface), &strip(points))
+            }
+            Visual::Polygon {
+                ref points, style, ..
+            } => {
+                if points.is_empty() || matches!(style, LineStyle::None) {
+                    return;
+                }
+                let mut outline = points.clone();
+                outline.push(outline[0]);
+                self.stream.log(self.next_entity(surface), &strip(outline))
+            }
+            _ => Ok(()),
+        };
+    }
+
+    fn clear(&self, surface: &str) {
+        self.lock().remove(surface);
+        self.set_time();
+        let _ = self.stream.log(surface, &Clear::recursive());
+    }
+
+    fn flush(&self) {
+        let _ = self.stream.flush_blocking();
+    }
+}
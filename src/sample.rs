@@ -0,0 +1,191 @@
+//! A vlogger adapter which forwards only every nth record.
+
+use crate::{Metadata, Record, VLog};
+use std::collections::BTreeMap;
+use std::sync::{Mutex, MutexGuard};
+
+/// Forwards only one in every `n` records of a surface to the inner vlogger, to thin out dense clouds.
+///
+/// The first record of a surface is forwarded, then the `n+1`th, the `2n+1`th and so on.
+/// The counts are kept per surface, so a surface which is drawn rarely isn't starved by
+/// a busy one. A `n` of 1 forwards all records and a `n` of 0 drops all of them,
+/// in which case [`enabled`](VLog::enabled) returns `false`.
+///
+/// The sampling is deterministic. [`clear`](VLog::clear) is always forwarded and restarts
+/// the count of the surface, so a scene which is redrawn every frame is sampled the same
+/// way every frame.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{clear, message, Metadata, Record, Sample, VLog};
+///
+/// struct Capture(RefCell<Vec<String>>);
+///
+/// impl VLog for Capture {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, record: &Record) {
+///         self.0.borrow_mut().push(format!("{} {}", record.surface(), record.args()));
+///     }
+///     fn clear(&self, surface: &str) {
+///         self.0.borrow_mut().push(format!("clear {}", surface));
+///     }
+///     fn flush(&self) {}
+/// }
+///
+/// let vlogger = Sample::new(Capture(RefCell::new(Vec::new())), 3);
+/// for i in 0..7 {
+///     message!(vlogger: vlogger, "a", "{}", i);
+///     if i % 4 == 0 {
+///         message!(vlogger: vlogger, "b", "{}", i);
+///     }
+/// }
+/// clear!(vlogger: vlogger, "a");
+/// message!(vlogger: vlogger, "a", "7");
+/// assert_eq!(
+///     vlogger.inner().0.take(),
+///     ["a 0", "b 0", "a 3", "a 6", "clear a", "a 7"]
+/// );
+///
+/// let drop_all = Sample::new(Capture(RefCell::new(Vec::new())), 0);
+/// message!(vlogger: drop_all, "a", "dropped");
+/// clear!(vlogger: drop_all, "a");
+/// assert_eq!(drop_all.inner().0.take(), ["clear a"]);
+/// ```
+#[derive(Debug)]
+pub struct Sample<V> {
+    inner: V,
+    n: usize,
+    counts: Mutex<BTreeMap<String, usize>>,
+}
+
+impl<V: VLog> Sample<V> {
+    /// Forward one in every `n` records per surface to `inner`.
+    pub fn new(inner: V, n: usize) -> Self {
+        Sample {
+            inner,
+            n,
+            counts: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// The inner vlogger.
+    pub fn inner(&self) -> &V {
+        &self.inner
+    }
+
+    /// Returns the inner vlogger.
+    pub fn into_inner(self) -> V {
+        self.inner
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, usize>> {
+        // the counts are always in a valid state, so poisoning can be ignored.
+        self.counts.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Count the record and check if it is one of the sampled ones.
+    fn take(&self, surface: &str) -> bool {
+        if self.n <= 1 {
+            return self.n == 1;
+        }
+        let mut counts = self.lock();
+        let count = match counts.get_mut(surface) {
+            Some(count) => count,
+            None => counts.entry(surface.into()).or_default(),
+        };
+        let sampled = *count == 0;
+        *count = (*count + 1) % self.n;
+        sampled
+    }
+}
+
+/// The vlogger of a transaction of a [`Sample`], which applies the same sampling.
+struct Sampled<'a, V> {
+    inner: &'a dyn VLog,
+    sample: &'a Sample<V>,
+}
+
+impl<V: VLog> VLog for Sampled<'_, V> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.sample.n != 0 && self.inner.enabled(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.sample.n != 0 && self.inner.enabled_record(record)
+    }
+
+    fn vlog(&self, record: &Record) {
+        if self.sample.take(record.surface()) {
+            self.inner.vlog(record);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.sample.lock().remove(surface);
+        self.inner.clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        self.sample.lock().remove(surface);
+        self.inner.remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        let sample = self.sample;
+        sample.lock().remove(surface);
+        self.inner.transaction(surface, &|inner| {
+            f(&Sampled { inner, sample });
+        });
+    }
+
+    forward_vlog! {
+        inner => begin_frame, end_frame, push_group, pop_group, flush, on_shutdown,
+        set_draw_mode, set_view, snapshot, quantize_color, max_records
+    }
+}
+
+impl<V: VLog> VLog for Sample<V> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.n != 0 && self.inner.enabled(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        self.n != 0 && self.inner.enabled_record(record)
+    }
+
+    fn vlog(&self, record: &Record) {
+        if self.take(record.surface()) {
+            self.inner.vlog(record);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.lock().remove(surface);
+        self.inner.clear(surface);
+    }
+
+    fn remove_surface(&self, surface: &str) {
+        self.lock().remove(surface);
+        self.inner.remove_surface(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        // a transaction replaces the content of the surface like a clear.
+        self.lock().remove(surface);
+        self.inner.transaction(surface, &|inner| {
+            f(&Sampled {
+                inner,
+                sample: self,
+            });
+        });
+    }
+
+    forward_vlog! {
+        inner => begin_frame, end_frame, push_group, pop_group, flush, on_shutdown,
+        set_draw_mode, set_view, snapshot, quantize_color, max_records
+    }
+}
@@ -0,0 +1,110 @@
+//! Introspection of the global state of the facade.
+
+use crate::{
+    auto_flush, capture_location, default_line_style, default_point_style, surfaces, tap, validate,
+    LineStyle, PointStyle, INITIALIZED, STATE,
+};
+use std::sync::atomic::Ordering;
+use std::time::Duration;
+
+/// A snapshot of the global configuration of the facade, see [`describe`].
+///
+/// Requires the `std` feature.
+#[derive(Clone, Debug)]
+#[non_exhaustive]
+pub struct VLogSetup {
+    /// Whether a global vlogger is installed.
+    pub vlogger_installed: bool,
+    /// The number of taps installed with [`add_tap`](crate::add_tap).
+    pub taps: usize,
+    /// Whether source locations are captured, see [`set_capture_location`](crate::set_capture_location).
+    pub capture_location: bool,
+    /// The idle duration of [`set_auto_flush`](crate::set_auto_flush), if enabled.
+    pub auto_flush: Option<Duration>,
+    /// The surfaces with a record cap from [`set_surface_record_cap`](crate::set_surface_record_cap),
+    /// sorted by name.
+    pub surface_record_caps: Vec<(String, usize)>,
+    /// The surfaces which are cleared once per frame, see [`set_auto_clear`](crate::set_auto_clear),
+    /// sorted by name.
+    pub auto_clear_surfaces: Vec<String>,
+    /// The surfaces with a coordinate scale from [`set_surface_scale`](crate::set_surface_scale),
+    /// sorted by name.
+    pub surface_scales: Vec<(String, f64)>,
+    /// The point style of the macros without one, see [`set_default_point_style`](crate::set_default_point_style).
+    pub default_point_style: PointStyle,
+    /// The line style of the macros without one, see [`set_default_line_style`](crate::set_default_line_style).
+    pub default_line_style: LineStyle,
+    /// Whether the records are validated, see [`enable_validation`](crate::enable_validation).
+    pub validation: bool,
+    /// Whether repeated clears are suppressed, see [`set_coalesce_clears`](crate::set_coalesce_clears).
+    pub coalesce_clears: bool,
+}
+
+/// Takes a snapshot of the global configuration of the facade.
+///
+/// This helps debugging the v-log setup itself, e.g. when draws don't show up.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct MyVLogger;
+/// # impl VLog for MyVLogger {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// # static VLOGGER: MyVLogger = MyVLogger;
+/// use v_log::{LineStyle, PointStyle};
+///
+/// assert!(!v_log::describe().vlogger_installed);
+///
+/// v_log::set_vlogger(&VLOGGER).unwrap();
+/// v_log::add_tap(|record| println!("{}", record.args()));
+/// v_log::set_surface_record_cap("trails", 1000);
+/// v_log::set_auto_clear("current_state", true);
+/// v_log::set_surface_scale("part_mm", 0.001);
+/// v_log::set_default_point_style(PointStyle::PointCross);
+/// v_log::enable_validation(true);
+/// v_log::set_coalesce_clears(true);
+///
+/// let setup = v_log::describe();
+/// assert!(setup.vlogger_installed);
+/// assert_eq!(setup.taps, 1);
+/// assert_eq!(setup.surface_record_caps, [("trails".to_string(), 1000)]);
+/// assert_eq!(setup.auto_clear_surfaces, ["current_state"]);
+/// assert_eq!(setup.surface_scales, [("part_mm".to_string(), 0.001)]);
+/// assert!(matches!(setup.default_point_style, PointStyle::PointCross));
+/// assert!(matches!(setup.default_line_style, LineStyle::Simple));
+/// assert!(setup.validation);
+/// assert!(setup.coalesce_clears);
+/// ```
+pub fn describe() -> VLogSetup {
+    let surfaces = surfaces::snapshot();
+    VLogSetup {
+        vlogger_installed: STATE.load(Ordering::Acquire) == INITIALIZED,
+        taps: tap::count(),
+        capture_location: capture_location(),
+        auto_flush: auto_flush::idle(),
+        surface_record_caps: surfaces
+            .iter()
+            .filter_map(|(surface, config)| config.record_cap.map(|cap| (surface.clone(), cap)))
+            .collect(),
+        auto_clear_surfaces: surfaces
+            .iter()
+            .filter(|(_, config)| config.auto_clear_frame.is_some())
+            .map(|(surface, _)| surface.clone())
+            .collect(),
+        surface_scales: surfaces
+            .iter()
+            .filter_map(|(surface, config)| config.scale.map(|scale| (surface.clone(), scale)))
+            .collect(),
+        default_point_style: default_point_style(),
+        default_line_style: default_line_style(),
+        validation: validate::enabled(),
+        coalesce_clears: surfaces::coalesce_clears(),
+    }
+}
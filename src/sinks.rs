@@ -0,0 +1,10 @@
+//! Vloggers which write the records to files, to inspect or replay them later.
+//!
+//! The vloggers which draw the records to be viewed right away stay at the crate root,
+//! like [`AsciiVLogger`](crate::AsciiVLogger), `PlottersVLogger` and `RerunVLogger`.
+//!
+//! Requires the `std` feature.
+
+#[cfg(feature = "serde")]
+pub use crate::json::{replay_json, JsonlVLogger, ReplayError};
+pub use crate::svg::SvgVLogger;
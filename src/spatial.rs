@@ -0,0 +1,144 @@
+//! A grid based spatial index over the positions of records.
+
+use crate::Visual;
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+
+/// Buckets the [positions](Visual::position) of visuals into a uniform grid
+/// to quickly find the records near a point, e.g. for picking in a GUI.
+///
+/// The index refers to the records by their index in the input. Visuals without
+/// a position (messages, lines, splines) are not indexed. It doesn't update with
+/// the records, so backends rebuild it e.g. once per frame.
+///
+/// Requires the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{PointStyle, Record, SpatialIndex, Visual};
+///
+/// let point = |x, y| Visual::Point { x, y, z: 0.0, style: PointStyle::Point };
+/// let records = [
+///     Record::builder().visual(point(0.0, 0.0)).build(),
+///     Record::builder().visual(Visual::Message).build(),
+///     Record::builder().visual(point(1.5, 0.5)).build(),
+///     Record::builder().visual(point(10.0, 10.0)).build(),
+///     Record::builder().visual(point(-0.5, -0.2)).build(),
+/// ];
+/// let index = SpatialIndex::new(records.iter().map(Record::visual), 1.0);
+/// assert_eq!(index.len(), 4);
+/// assert_eq!(index.query_near([0.0, 0.0], 2.0), [0, 2, 4]);
+/// assert_eq!(index.query_near([0.0, 0.0], 0.6), [0, 4]);
+/// assert_eq!(index.query_near([10.0, 9.0, 0.0], 1.0), [3]);
+/// assert!(index.query_near([5.0, 5.0], 1.0).is_empty());
+/// ```
+#[derive(Clone, Debug)]
+pub struct SpatialIndex {
+    cell_size: f64,
+    positions: Vec<(usize, [f64; 3])>,
+    cells: BTreeMap<[i64; 3], Vec<usize>>,
+}
+
+impl SpatialIndex {
+    /// Index the positions of the given visuals in a grid with the given cell size.
+    ///
+    /// The cell size should be about the typical query radius.
+    /// Non-positive or non-finite cell sizes are replaced by `1.0`.
+    pub fn new<'a, I>(visuals: I, cell_size: f64) -> SpatialIndex
+    where
+        I: IntoIterator<Item = &'a Visual>,
+    {
+        let cell_size = if cell_size > 0.0 && cell_size.is_finite() {
+            cell_size
+        } else {
+            1.0
+        };
+        let mut index = SpatialIndex {
+            cell_size,
+            positions: Vec::new(),
+            cells: BTreeMap::new(),
+        };
+        for (i, visual) in visuals.into_iter().enumerate() {
+            if let Some(pos) = visual.position() {
+                let cell = index.cell(pos);
+                index
+                    .cells
+                    .entry(cell)
+                    .or_default()
+                    .push(index.positions.len());
+                index.positions.push((i, pos));
+            }
+        }
+        index
+    }
+
+    /// The number of indexed records.
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns `true` if no records are indexed.
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// The indices of the records within `radius` of the point, in ascending order.
+    ///
+    /// Missing coordinates of the point are `0.0`, so 2D points can be passed as `[x, y]`.
+    pub fn query_near<P: IntoIterator<Item = f64>>(&self, point: P, radius: f64) -> Vec<usize> {
+        let mut point = point.into_iter();
+        let p = [
+            point.next().unwrap_or(0.0),
+            point.next().unwrap_or(0.0),
+            point.next().unwrap_or(0.0),
+        ];
+        let mut result = Vec::new();
+        if radius.is_nan() || radius < 0.0 {
+            return result;
+        }
+        let min = self.cell([p[0] - radius, p[1] - radius, p[2] - radius]);
+        let max = self.cell([p[0] + radius, p[1] + radius, p[2] + radius]);
+        let cells = (0..3)
+            .map(|i| max[i] as f64 - min[i] as f64 + 1.0)
+            .product::<f64>();
+        let mut check = |entry: usize| {
+            let (i, q) = self.positions[entry];
+            let d = [q[0] - p[0], q[1] - p[1], q[2] - p[2]];
+            if d[0] * d[0] + d[1] * d[1] + d[2] * d[2] <= radius * radius {
+                result.push(i);
+            }
+        };
+        if cells > self.cells.len() as f64 {
+            // the query covers more cells than are occupied.
+            for entries in self.cells.values() {
+                entries.iter().copied().for_each(&mut check);
+            }
+        } else {
+            for x in min[0]..=max[0] {
+                for y in min[1]..=max[1] {
+                    for z in min[2]..=max[2] {
+                        if let Some(entries) = self.cells.get(&[x, y, z]) {
+                            entries.iter().copied().for_each(&mut check);
+                        }
+                    }
+                }
+            }
+        }
+        result.sort_unstable();
+        result
+    }
+
+    fn cell(&self, pos: [f64; 3]) -> [i64; 3] {
+        // `f64::floor` is not available in `core`.
+        pos.map(|x| {
+            let c = x / self.cell_size;
+            let i = c as i64;
+            if (i as f64) > c {
+                i - 1
+            } else {
+                i
+            }
+        })
+    }
+}
@@ -0,0 +1,51 @@
+//! A minimal vlogger printing to stderr.
+
+use crate::{set_vlogger, Metadata, Record, SetVLoggerError, VLog};
+use std::io::Write;
+
+/// Prints every record as a line with its [`Display`](std::fmt::Display) implementation.
+struct StderrVLogger;
+
+impl VLog for StderrVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        eprintln!("{}", record);
+    }
+
+    fn clear(&self, surface: &str) {
+        eprintln!("{}: clear", surface);
+    }
+
+    fn flush(&self) {
+        let _ = std::io::stderr().flush();
+    }
+}
+
+/// Installs a minimal global vlogger, which prints every record as a line of text.
+///
+/// This is the quickest way to see what is drawn, without any visualization.
+/// The records are printed to stderr, so they don't mix with the output of the program.
+///
+/// Requires the `std` feature.
+///
+/// # Errors
+///
+/// An error is returned if a vlogger has already been set.
+///
+/// # Examples
+///
+/// ```
+/// v_log::init_stdout().unwrap();
+///
+/// // prints "main_surface: Point at (1, 2, 0), style Point, size 5, color Base: start"
+/// v_log::point!("main_surface", [1.0, 2.0], 5.0, Base, "o", "start");
+///
+/// assert!(v_log::init_stdout().is_err());
+/// ```
+pub fn init_stdout() -> Result<(), SetVLoggerError> {
+    static VLOGGER: StderrVLogger = StderrVLogger;
+    set_vlogger(&VLOGGER)
+}
@@ -0,0 +1,161 @@
+//! Per-surface configuration managed by the facade.
+
+use crate::DrawMode;
+use std::collections::BTreeMap;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, MutexGuard};
+use std::thread::{self, ThreadId};
+
+/// The facade settings of a single surface.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct SurfaceConfig {
+    pub(crate) record_cap: Option<usize>,
+    /// The frame of the last automatic clear, if auto clearing is enabled.
+    pub(crate) auto_clear_frame: Option<usize>,
+    /// The factor for the coordinates of the records, if it isn't `1.0`.
+    pub(crate) scale: Option<f64>,
+    /// The mode from `set_draw_mode`, which is forwarded again to newly installed vloggers.
+    pub(crate) draw_mode: Option<DrawMode>,
+}
+
+static SURFACES: Mutex<BTreeMap<String, SurfaceConfig>> = Mutex::new(BTreeMap::new());
+// avoids taking the lock on every draw if no surface is auto cleared.
+static ANY_AUTO_CLEAR: AtomicBool = AtomicBool::new(false);
+// avoids taking the lock on every draw if no surface is scaled.
+static ANY_SCALE: AtomicBool = AtomicBool::new(false);
+// the surfaces of the global vlogger which have been cleared by a thread
+// without a draw since, in the current frame.
+static CLEARED: Mutex<Vec<(ThreadId, String)>> = Mutex::new(Vec::new());
+// avoids taking the lock on every draw if no surface has been cleared.
+static ANY_CLEARED: AtomicBool = AtomicBool::new(false);
+static COALESCE_CLEARS: AtomicBool = AtomicBool::new(false);
+
+fn lock() -> MutexGuard<'static, BTreeMap<String, SurfaceConfig>> {
+    // the settings are always in a valid state, so poisoning can be ignored.
+    SURFACES.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Modify the configuration of a surface, creating it if needed.
+pub(crate) fn update<R>(surface: &str, f: impl FnOnce(&mut SurfaceConfig) -> R) -> R {
+    let mut surfaces = lock();
+    if let Some(config) = surfaces.get_mut(surface) {
+        return f(config);
+    }
+    f(surfaces.entry(surface.into()).or_default())
+}
+
+/// Read the configuration of a surface, if it has one.
+pub(crate) fn get<R>(surface: &str, f: impl FnOnce(&SurfaceConfig) -> R) -> Option<R> {
+    lock().get(surface).map(f)
+}
+
+/// Copy the configuration of all surfaces, sorted by name.
+pub(crate) fn snapshot() -> Vec<(String, SurfaceConfig)> {
+    lock()
+        .iter()
+        .map(|(surface, config)| (surface.clone(), config.clone()))
+        .collect()
+}
+
+/// Enable or disable clearing a surface on its first draw of each frame.
+pub(crate) fn set_auto_clear(surface: &str, frame: usize, enabled: bool) {
+    let mut surfaces = lock();
+    let config = match surfaces.get_mut(surface) {
+        Some(config) => config,
+        None => surfaces.entry(surface.into()).or_default(),
+    };
+    // the current frame doesn't get cleared.
+    config.auto_clear_frame = if enabled { Some(frame) } else { None };
+    let any = surfaces.values().any(|c| c.auto_clear_frame.is_some());
+    ANY_AUTO_CLEAR.store(any, Ordering::Relaxed);
+}
+
+/// Check if the surface needs to be cleared before drawing in the given frame.
+pub(crate) fn take_auto_clear(surface: &str, frame: usize) -> bool {
+    if !ANY_AUTO_CLEAR.load(Ordering::Relaxed) {
+        return false;
+    }
+    match lock().get_mut(surface) {
+        Some(SurfaceConfig {
+            auto_clear_frame: Some(last),
+            ..
+        }) if *last != frame => {
+            *last = frame;
+            true
+        }
+        _ => false,
+    }
+}
+
+/// Set the factor for the coordinates of the records of a surface.
+pub(crate) fn set_scale(surface: &str, scale: f64) {
+    let mut surfaces = lock();
+    let config = match surfaces.get_mut(surface) {
+        Some(config) => config,
+        None => surfaces.entry(surface.into()).or_default(),
+    };
+    config.scale = if scale == 1.0 { None } else { Some(scale) };
+    let any = surfaces.values().any(|c| c.scale.is_some());
+    ANY_SCALE.store(any, Ordering::Relaxed);
+}
+
+/// The factor for the coordinates of the records of a surface, if it isn't `1.0`.
+pub(crate) fn scale(surface: &str) -> Option<f64> {
+    if !ANY_SCALE.load(Ordering::Relaxed) {
+        return None;
+    }
+    get(surface, |config| config.scale).flatten()
+}
+
+fn lock_cleared() -> MutexGuard<'static, Vec<(ThreadId, String)>> {
+    // the set is always in a valid state, so poisoning can be ignored.
+    CLEARED.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Enable or disable suppressing repeated clears of a surface.
+pub(crate) fn set_coalesce_clears(enabled: bool) {
+    COALESCE_CLEARS.store(enabled, Ordering::Relaxed);
+    if !enabled {
+        reset_cleared();
+    }
+}
+
+/// Whether repeated clears of a surface are suppressed.
+pub(crate) fn coalesce_clears() -> bool {
+    COALESCE_CLEARS.load(Ordering::Relaxed)
+}
+
+/// Check if a clear of the surface needs to be forwarded to the global vlogger, which is
+/// not the case if the current thread has cleared it already without a draw since.
+pub(crate) fn take_clear(surface: &str) -> bool {
+    if !coalesce_clears() {
+        return true;
+    }
+    let id = thread::current().id();
+    let mut cleared = lock_cleared();
+    if cleared.iter().any(|(t, s)| *t == id && s == surface) {
+        return false;
+    }
+    cleared.push((id, surface.into()));
+    ANY_CLEARED.store(true, Ordering::Relaxed);
+    true
+}
+
+/// Forget the clears of a surface by all threads, as it has been drawn to.
+pub(crate) fn note_draw(surface: &str) {
+    if !ANY_CLEARED.load(Ordering::Relaxed) {
+        return;
+    }
+    let mut cleared = lock_cleared();
+    cleared.retain(|(_, s)| s != surface);
+    ANY_CLEARED.store(!cleared.is_empty(), Ordering::Relaxed);
+}
+
+/// Forget all clears, e.g. at the start of a new frame.
+pub(crate) fn reset_cleared() {
+    if !ANY_CLEARED.load(Ordering::Relaxed) {
+        return;
+    }
+    lock_cleared().clear();
+    ANY_CLEARED.store(false, Ordering::Relaxed);
+}
@@ -0,0 +1,432 @@
+//! A vlogger which writes 2D surfaces as SVG files.
+
+use crate::{
+    Color, LineStyle, Metadata, PointStyle, Record, RecordOwned, TextAlignment, VLog, VLogError,
+    Visual,
+};
+use std::collections::BTreeMap;
+use std::fmt::{self, Write as _};
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, MutexGuard};
+
+/// A vlogger which collects the records of 2D surfaces and writes them as standalone SVG images.
+///
+/// Points are drawn as `<circle>`, `<rect>`, `<polygon>` or `<path>` depending on their style,
+/// lines as `<line>` and labels as `<text>`, honoring their [`TextAlignment`] and rotation.
+/// The connected lines of a path, e.g. from one [`polyline!`](crate::polyline) call, are
+/// drawn as a single `<polyline>` with the color and thickness of its first line.
+/// Colors are resolved with [`Color::to_rgba`], so themed colors use their default shades.
+/// Other visuals, arrow heads and messages are ignored.
+///
+/// The world bounds are mapped onto the image with the y-axis pointing up.
+/// The z-coordinate is ignored. Records with a higher [`z_order`](Record::z_order) are
+/// drawn on top, otherwise the records are drawn in order. Marker sizes, line thicknesses
+/// and text sizes are in pixels, so the screen space point styles
+/// (e.g. [`PointStyle::Point`]) have a fixed pixel radius, while the sizes of the
+/// absolute point styles (e.g. [`PointStyle::Circle`]) are mapped from world units.
+///
+/// The SVG of a surface is written with [`snapshot`](VLog::snapshot) to paths ending
+/// in `.svg`. If an output directory is set with [`with_output_dir`](SvgVLogger::with_output_dir),
+/// [`flush`](VLog::flush) writes all surfaces there. [`clear`](VLog::clear) removes the
+/// collected records of a surface.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::sinks::SvgVLogger;
+/// use v_log::{label, point, polyline};
+///
+/// let vlogger = SvgVLogger::new(100, 50).with_bounds([0.0, 0.0], [10.0, 5.0]);
+///
+/// point!(vlogger: vlogger, "main_surface", [2.0, 1.0], 6.0, Error, z_order: 1);
+/// point!(vlogger: vlogger, "main_surface", [5.0, 2.5], 1.0, 0x00ff0080, "-O");
+/// polyline!(vlogger: vlogger, "main_surface", ([1.0, 1.0], [9.0, 4.0]), 2.0, Base, "--");
+/// polyline!(vlogger: vlogger, "main_surface", [[1.0, 4.0], [3.0, 1.0], [5.0, 3.0]], 1.0, Warn);
+/// label!(vlogger: vlogger, "main_surface", [5.0, 4.0], (10.0, Info, "<"), "a < b");
+///
+/// // the same as the checked-in image, which can be viewed in a browser.
+/// let svg = vlogger.render("main_surface");
+/// assert_eq!(svg, include_str!("../tests/golden/svg_scene.svg"));
+/// ```
+#[derive(Debug)]
+pub struct SvgVLogger {
+    width: u32,
+    height: u32,
+    min: [f64; 2],
+    max: [f64; 2],
+    output_dir: Option<PathBuf>,
+    surfaces: Mutex<BTreeMap<String, Vec<RecordOwned>>>,
+}
+
+impl SvgVLogger {
+    /// Create a new `SvgVLogger` for images of `width` times `height` pixels.
+    ///
+    /// The world bounds default to one unit per pixel, starting at the origin.
+    pub fn new(width: u32, height: u32) -> Self {
+        SvgVLogger {
+            width,
+            height,
+            min: [0.0, 0.0],
+            max: [width as f64, height as f64],
+            output_dir: None,
+            surfaces: Mutex::new(BTreeMap::new()),
+        }
+    }
+
+    /// Set the world bounds, which are mapped onto the image.
+    pub fn with_bounds(mut self, min: [f64; 2], max: [f64; 2]) -> Self {
+        self.min = min;
+        self.max = max;
+        self
+    }
+
+    /// Write every surface to `<dir>/<surface>.svg` on [`flush`](VLog::flush).
+    ///
+    /// Characters of the surface name other than ASCII letters, digits, `-` and `_`
+    /// are replaced by `_` in the file name. Then a hash of the surface name is
+    /// appended, so the files of e.g. `a.b` and `a_b` don't overwrite each other.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::sinks::SvgVLogger;
+    /// use v_log::{point, VLog};
+    ///
+    /// let dir = std::env::temp_dir().join("v_log_svg_output_dir");
+    /// std::fs::create_dir_all(&dir).unwrap();
+    /// let vlogger = SvgVLogger::new(100, 50).with_output_dir(&dir);
+    /// point!(vlogger: vlogger, "main_surface", [2.0, 1.0], 6.0, Error);
+    /// point!(vlogger: vlogger, "robot/arm", [2.0, 1.0], 6.0, Error);
+    /// point!(vlogger: vlogger, "robot_arm", [2.0, 1.0], 6.0, Error);
+    /// vlogger.flush();
+    ///
+    /// assert!(dir.join("main_surface.svg").exists());
+    /// assert!(dir.join("robot_arm.svg").exists());
+    /// let hashed = std::fs::read_dir(&dir).unwrap().filter_map(|entry| {
+    ///     let name = entry.unwrap().file_name().into_string().unwrap();
+    ///     Some(name).filter(|name| name.starts_with("robot_arm-"))
+    /// });
+    /// assert_eq!(hashed.count(), 1);
+    /// # std::fs::remove_dir_all(&dir).unwrap();
+    /// ```
+    pub fn with_output_dir(mut self, dir: impl Into<PathBuf>) -> Self {
+        self.output_dir = Some(dir.into());
+        self
+    }
+
+    /// The number of collected records of a surface.
+    pub fn len(&self, surface: &str) -> usize {
+        self.lock().get(surface).map_or(0, Vec::len)
+    }
+
+    /// Render a surface as an SVG document.
+    ///
+    /// Surfaces which haven't been drawn to render as empty images.
+    pub fn render(&self, surface: &str) -> String {
+        let surfaces = self.lock();
+        let mut records: Vec<&RecordOwned> = surfaces.get(surface).into_iter().flatten().collect();
+        // stable, so records with the same z order keep their order.
+        records.sort_by_key(|record| record.z_order());
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{w}\" height=\"{h}\" viewBox=\"0 0 {w} {h}\">\n",
+            w = self.width,
+            h = self.height
+        );
+        let mut rest = &records[..];
+        while !rest.is_empty() {
+            let (run, next) = rest.split_at(path_run(rest));
+            // writing to a `String` can't fail.
+            let _ = self.write_record(&mut svg, run);
+            rest = next;
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+
+    fn write_file(&self, surface: &str, path: &Path) -> Result<(), VLogError> {
+        std::fs::write(path, self.render(surface)).map_err(|_| VLogError::Failed)
+    }
+
+    fn lock(&self) -> MutexGuard<'_, BTreeMap<String, Vec<RecordOwned>>> {
+        // the records are always in a valid state, so poisoning can be ignored.
+        self.surfaces.lock().unwrap_or_else(|e| e.into_inner())
+    }
+
+    /// Map world coordinates to pixels with the y-axis pointing down.
+    fn to_pixels(&self, x: f64, y: f64) -> (Num, Num) {
+        (
+            Num((x - self.min[0]) / (self.max[0] - self.min[0]) * self.width as f64),
+            Num((self.max[1] - y) / (self.max[1] - self.min[1]) * self.height as f64),
+        )
+    }
+
+    /// Pixels per world unit, assuming a uniform scale.
+    fn scale(&self) -> f64 {
+        self.width as f64 / (self.max[0] - self.min[0])
+    }
+
+    /// Write a record, or a run of connected lines of the same path as one `<polyline>`.
+    fn write_record(&self, svg: &mut String, run: &[&RecordOwned]) -> fmt::Result {
+        let record = run[0];
+        let [r, g, b, a] = record.color().to_rgba();
+        let hex = Color::rgba(r, g, b, 1.0).to_rgba8();
+        let color = format!("#{:02x}{:02x}{:02x}", hex[0], hex[1], hex[2]);
+        let opacity = a as f64 * record.opacity().clamp(0.0, 1.0) as f64;
+        let paint = |kind: &str| {
+            if opacity < 1.0 {
+                format!("{kind}=\"{color}\" {kind}-opacity=\"{}\"", Num(opacity))
+            } else {
+                format!("{kind}=\"{color}\"")
+            }
+        };
+        match *record.visual() {
+            Visual::Point { x, y, style, .. } => {
+                let (cx, cy) = self.to_pixels(x, y);
+                let size = record.size_or_default(5.0);
+                let radius = match style {
+                    PointStyle::FilledCircle
+                    | PointStyle::Circle
+                    | PointStyle::DashedCircle
+                    | PointStyle::FilledSquare
+                    | PointStyle::Square
+                    | PointStyle::DashedSquare => size * 0.5 * self.scale(),
+                    _ => size * 0.5,
+                };
+                let fill = paint("fill");
+                let stroke = format!("fill=\"none\" {}", paint("stroke"));
+                let dashed = format!("{stroke} stroke-dasharray=\"{}\"", Num(radius * 0.5));
+                let (x0, y0, d) = (Num(cx.0 - radius), Num(cy.0 - radius), Num(2.0 * radius));
+                let r = Num(radius);
+                let diamond = format!(
+                    "{},{} {},{} {},{} {},{}",
+                    cx,
+                    Num(cy.0 - radius),
+                    Num(cx.0 + radius),
+                    cy,
+                    cx,
+                    Num(cy.0 + radius),
+                    Num(cx.0 - radius),
+                    cy
+                );
+                let circle = format!("<circle cx=\"{cx}\" cy=\"{cy}\" r=\"{r}\"");
+                let rect = format!("<rect x=\"{x0}\" y=\"{y0}\" width=\"{d}\" height=\"{d}\"");
+                match style {
+                    PointStyle::FilledCircle | PointStyle::Point => {
+                        writeln!(svg, "{circle} {fill}/>")
+                    }
+                    PointStyle::Circle | PointStyle::PointOutline => {
+                        writeln!(svg, "{circle} {stroke}/>")
+                    }
+                    PointStyle::DashedCircle => writeln!(svg, "{circle} {dashed}/>"),
+                    PointStyle::FilledSquare | PointStyle::PointSquare => {
+                        writeln!(svg, "{rect} {fill}/>")
+                    }
+                    PointStyle::Square | PointStyle::PointSquareOutline => {
+                        writeln!(svg, "{rect} {stroke}/>")
+                    }
+                    PointStyle::DashedSquare => writeln!(svg, "{rect} {dashed}/>"),
+                    PointStyle::PointCross => writeln!(
+                        svg,
+                        "<path d=\"M{x0},{y0} l{d},{d} M{x0},{} l{d},-{d}\" {stroke}/>",
+                        Num(cy.0 + radius)
+                    ),
+                    PointStyle::PointDiamond => {
+                        writeln!(svg, "<polygon points=\"{diamond}\" {fill}/>")
+                    }
+                    PointStyle::PointDiamondOutline => {
+                        writeln!(svg, "<polygon points=\"{diamond}\" {stroke}/>")
+                    }
+                    _ => Ok(()),
+                }
+            }
+            Visual::Line {
+                x1,
+                y1,
+                x2,
+                y2,
+                style,
+                world_thickness,
+                ..
+            } => {
+                let width = match world_thickness {
+                    Some(thickness) => thickness * self.scale(),
+                    None => record.size_or_default(1.0),
+                };
+                if run.len() == 1 {
+                    let ((x1, y1), (x2, y2)) = (self.to_pixels(x1, y1), self.to_pixels(x2, y2));
+                    write!(svg, "<line x1=\"{x1}\" y1=\"{y1}\" x2=\"{x2}\" y2=\"{y2}\"")?;
+                } else {
+                    let (x, y) = self.to_pixels(x1, y1);
+                    write!(svg, "<polyline points=\"{x},{y}")?;
+                    for segment in run {
+                        if let Visual::Line { x2, y2, .. } = *segment.visual() {
+                            let (x, y) = self.to_pixels(x2, y2);
+                            write!(svg, " {x},{y}")?;
+                        }
+                    }
+                    write!(svg, "\" fill=\"none\"")?;
+                }
+                write!(svg, " {} stroke-width=\"{}\"", paint("stroke"), Num(width))?;
+                if let LineStyle::Dashed = style {
+                    write!(svg, " stroke-dasharray=\"{}\"", Num(4.0 * width))?;
+                }
+                writeln!(svg, "/>")
+            }
+            Visual::Label {
+                x,
+                y,
+                alignment,
+                rotation,
+                max_width,
+                style,
+                ..
+            } => {
+                let (px, py) = self.to_pixels(x, y);
+                let size = record.size_or_default(12.0);
+                let anchor = match alignment {
+                    TextAlignment::Left => "start",
+                    TextAlignment::Right => "end",
+                    TextAlignment::Center | TextAlignment::Flexible => "middle",
+                };
+                // the width in pixels, assuming an average glyph width of 0.6 times the size.
+                let max_chars =
+                    max_width.map(|w| (w * self.scale() / (0.6 * size)).max(0.0) as usize);
+                let lines = style.wrap(record.args(), max_chars);
+                let line_height = 1.2 * size;
+                let top = py.0 - line_height * (lines.len() - 1) as f64 * 0.5;
+                for (i, line) in lines.iter().enumerate() {
+                    let ly = Num(top + line_height * i as f64);
+                    write!(
+                        svg,
+                        "<text x=\"{px}\" y=\"{ly}\" font-size=\"{}\" text-anchor=\"{anchor}\" dominant-baseline=\"middle\" {}",
+                        Num(size),
+                        paint("fill")
+                    )?;
+                    if rotation != 0.0 {
+                        // the y-axis of the image points down, so counterclockwise is negative.
+                        write!(
+                            svg,
+                            " transform=\"rotate({} {px} {py})\"",
+                            Num(-rotation.to_degrees())
+                        )?;
+                    }
+                    writeln!(svg, ">{}</text>", Escaped(line))?;
+                }
+                Ok(())
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The number of records at the start which are drawn together: the lines of a path
+/// from one [`polyline!`](crate::polyline) call which connect to each other, or one record.
+fn path_run(records: &[&RecordOwned]) -> usize {
+    let path_id = records[0].path_id();
+    let mut len = 1;
+    while let (Some(prev), Some(next)) = (records.get(len - 1), records.get(len)) {
+        match (prev.visual(), next.visual()) {
+            (Visual::Line { x2, y2, .. }, Visual::Line { x1, y1, .. })
+                if path_id != 0 && next.path_id() == path_id && (x1, y1) == (x2, y2) =>
+            {
+                len += 1;
+            }
+            _ => break,
+        }
+    }
+    len
+}
+
+/// The file name of a surface in the output directory, see [`SvgVLogger::with_output_dir`].
+fn file_name(surface: &str) -> String {
+    let name: String = surface
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect();
+    if name == surface {
+        return format!("{name}.svg");
+    }
+    // stable FNV-1a hash, so the files keep their names between runs.
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for b in surface.bytes() {
+        hash ^= b as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    format!("{name}-{:08x}.svg", hash as u32)
+}
+
+/// A number with at most two decimals and without trailing zeros.
+#[derive(Clone, Copy)]
+struct Num(f64);
+
+impl fmt::Display for Num {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let text = format!("{:.2}", self.0);
+        let text = text.trim_end_matches('0').trim_end_matches('.');
+        f.write_str(if text == "-0" { "0" } else { text })
+    }
+}
+
+/// Text with the XML special characters escaped.
+struct Escaped<'a>(&'a str);
+
+impl fmt::Display for Escaped<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for c in self.0.chars() {
+            match c {
+                '&' => f.write_str("&amp;")?,
+                '<' => f.write_str("&lt;")?,
+                '>' => f.write_str("&gt;")?,
+                '"' => f.write_str("&quot;")?,
+                _ => f.write_char(c)?,
+            }
+        }
+        Ok(())
+    }
+}
+
+impl VLog for SvgVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        let mut surfaces = self.lock();
+        let records = surfaces.entry(record.surface().into()).or_default();
+        records.push(record.into());
+        if let Some(cap) = self.max_records(record.surface()) {
+            let excess = records.len().saturating_sub(cap);
+            records.drain(..excess);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.lock().remove(surface);
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        if !path.ends_with(".svg") {
+            return Err(VLogError::UnsupportedFormat);
+        }
+        self.write_file(surface, Path::new(path))
+    }
+
+    fn flush(&self) {
+        let Some(dir) = &self.output_dir else {
+            return;
+        };
+        let surfaces: Vec<String> = self.lock().keys().cloned().collect();
+        for surface in surfaces {
+            // vloggers can't report errors on flush.
+            let _ = self.write_file(&surface, &dir.join(file_name(&surface)));
+        }
+    }
+}
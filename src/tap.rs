@@ -0,0 +1,121 @@
+//! Observers of the record stream, which don't replace the vlogger.
+
+use crate::Record;
+use std::cell::Cell;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+type Tap = Arc<dyn Fn(&Record) + Send + Sync>;
+
+static TAPS: Mutex<Vec<(usize, Tap)>> = Mutex::new(Vec::new());
+static NEXT_ID: AtomicUsize = AtomicUsize::new(0);
+// avoids taking the lock for every record if there are no taps.
+static HAS_TAPS: AtomicBool = AtomicBool::new(false);
+
+thread_local! {
+    // whether this thread is running the taps, so the records they draw aren't tapped again.
+    static IN_TAP: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Resets `IN_TAP` when the taps are done, even if one of them panics.
+struct InTapGuard;
+
+impl Drop for InTapGuard {
+    fn drop(&mut self) {
+        IN_TAP.with(|in_tap| in_tap.set(false));
+    }
+}
+
+fn lock() -> MutexGuard<'static, Vec<(usize, Tap)>> {
+    // the list is always in a valid state, so poisoning can be ignored.
+    TAPS.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Identifies a tap installed with [`add_tap`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TapId(usize);
+
+/// Installs an observer which gets called with every record sent by the macros.
+///
+/// Taps are called in addition to the vlogger, after the record was found to be
+/// [enabled](crate::VLog::enabled_record). They can't change or filter the records
+/// the vlogger receives. This allows inspecting the draws for debugging while
+/// the real vlogger stays installed.
+///
+/// Taps may draw themselves. Those records reach the vlogger, but not the taps,
+/// so a tap which draws doesn't recurse. Taps shouldn't add or remove taps.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use std::sync::atomic::{AtomicUsize, Ordering};
+/// use v_log::{point, Metadata, Record, VLog};
+///
+/// struct CountingVLogger(AtomicUsize);
+///
+/// impl VLog for CountingVLogger {
+///     fn enabled(&self, _: &Metadata) -> bool { true }
+///     fn vlog(&self, _: &Record) {
+///         self.0.fetch_add(1, Ordering::SeqCst);
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// static VLOGGER: CountingVLogger = CountingVLogger(AtomicUsize::new(0));
+/// static TAPPED: AtomicUsize = AtomicUsize::new(0);
+/// v_log::set_vlogger(&VLOGGER).unwrap();
+///
+/// let tap = v_log::add_tap(|record| {
+///     assert_eq!(record.surface(), "main_surface");
+///     TAPPED.fetch_add(1, Ordering::SeqCst);
+///     // only reaches the vlogger.
+///     point!("main_surface", [0.0, 1.0], 5.0, Info);
+/// });
+/// point!("main_surface", [0.0, 0.0], 5.0, Base);
+/// v_log::remove_tap(tap);
+/// point!("main_surface", [1.0, 0.0], 5.0, Base);
+///
+/// assert_eq!(VLOGGER.0.load(Ordering::SeqCst), 3);
+/// assert_eq!(TAPPED.load(Ordering::SeqCst), 1);
+/// ```
+pub fn add_tap(f: impl Fn(&Record) + Send + Sync + 'static) -> TapId {
+    let id = NEXT_ID.fetch_add(1, Ordering::Relaxed);
+    let mut taps = lock();
+    taps.push((id, Arc::new(f)));
+    HAS_TAPS.store(true, Ordering::Release);
+    TapId(id)
+}
+
+/// Removes a tap installed with [`add_tap`].
+///
+/// Returns `false` if the tap was already removed.
+///
+/// Requires the `std` feature.
+pub fn remove_tap(id: TapId) -> bool {
+    let mut taps = lock();
+    let len = taps.len();
+    taps.retain(|(tap_id, _)| *tap_id != id.0);
+    HAS_TAPS.store(!taps.is_empty(), Ordering::Release);
+    taps.len() != len
+}
+
+/// The number of installed taps.
+pub(crate) fn count() -> usize {
+    lock().len()
+}
+
+/// Call all taps with the record.
+pub(crate) fn notify(record: &Record) {
+    if !HAS_TAPS.load(Ordering::Acquire) || IN_TAP.with(|in_tap| in_tap.replace(true)) {
+        return;
+    }
+    let _guard = InTapGuard;
+    // call the taps without holding the lock, so they can draw themselves.
+    let taps: Vec<Tap> = lock().iter().map(|(_, tap)| Arc::clone(tap)).collect();
+    for tap in taps {
+        tap(record);
+    }
+}
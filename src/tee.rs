@@ -0,0 +1,146 @@
+//! A vlogger which forwards to two vloggers at once.
+
+use crate::{Color, Metadata, Record, VLog, VLogError};
+
+/// Forwards everything to both vloggers `A` and `B`, e.g. a live window and a file recorder.
+///
+/// A directive is [enabled](VLog::enabled) if it is enabled for either vlogger. As the
+/// vlogging macros only call [`vlog`](VLog::vlog) if the combined check passes, the
+/// record is then forwarded only to the vloggers which have it
+/// [enabled](VLog::enabled_record), so each vlogger still sees just the records it wants.
+/// Clears, frames, groups and the other surface requests reach both vloggers.
+/// A [`transaction`](VLog::transaction) runs as a transaction of both vloggers,
+/// with the draws going to a `Tee` of their transaction vloggers.
+///
+/// Colors are [quantized](VLog::quantize_color) by `A` and [snapshots](VLog::snapshot)
+/// are taken by `A`, or by `B` if that fails.
+///
+/// Tees nest, so three or more vloggers compose as `tee(a, tee(b, c))`.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::{clear, point, tee, AsciiVLogger};
+///
+/// let vlogger = tee(AsciiVLogger::new(3, 1), AsciiVLogger::new(5, 1));
+///
+/// point!(vlogger: vlogger, "main_surface", [1.5, 0.5], 1.0, Base);
+/// assert_eq!(vlogger.a().render("main_surface"), " o ");
+/// assert_eq!(vlogger.b().render("main_surface"), " o   ");
+///
+/// clear!(vlogger: vlogger, "main_surface");
+/// assert_eq!(vlogger.a().render("main_surface"), "   ");
+/// assert_eq!(vlogger.b().render("main_surface"), "     ");
+/// ```
+///
+/// Records only reach the vloggers which have them enabled:
+///
+/// ```
+/// # use std::cell::RefCell;
+/// use v_log::{point, tee, Metadata, Record, VLog};
+///
+/// struct Capture(&'static str, RefCell<Vec<String>>);
+///
+/// impl VLog for Capture {
+///     fn enabled(&self, metadata: &Metadata) -> bool {
+///         metadata.surface() == self.0
+///     }
+///     fn vlog(&self, record: &Record) {
+///         self.1.borrow_mut().push(record.surface().to_string());
+///     }
+///     fn clear(&self, _: &str) {}
+///     fn flush(&self) {}
+/// }
+///
+/// let a = Capture("a", RefCell::new(Vec::new()));
+/// let b = Capture("b", RefCell::new(Vec::new()));
+/// let c = Capture("c", RefCell::new(Vec::new()));
+/// let vlogger = tee(&a, tee(&b, &c));
+/// for surface in ["a", "b", "c", "d"] {
+///     point!(vlogger: vlogger, surface, [0.0, 0.0], 5.0, Base);
+/// }
+/// assert_eq!(*a.1.borrow(), ["a"]);
+/// assert_eq!(*b.1.borrow(), ["b"]);
+/// assert_eq!(*c.1.borrow(), ["c"]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Tee<A, B> {
+    a: A,
+    b: B,
+}
+
+/// Combine two vloggers into a [`Tee`], which forwards to both.
+///
+/// Requires the `std` feature.
+pub const fn tee<A: VLog, B: VLog>(a: A, b: B) -> Tee<A, B> {
+    Tee { a, b }
+}
+
+impl<A: VLog, B: VLog> Tee<A, B> {
+    /// The first vlogger.
+    pub fn a(&self) -> &A {
+        &self.a
+    }
+
+    /// The second vlogger.
+    pub fn b(&self) -> &B {
+        &self.b
+    }
+
+    /// Returns both vloggers.
+    pub fn into_inner(self) -> (A, B) {
+        (self.a, self.b)
+    }
+}
+
+impl<A: VLog, B: VLog> VLog for Tee<A, B> {
+    fn enabled(&self, metadata: &Metadata) -> bool {
+        self.a.enabled(metadata) || self.b.enabled(metadata)
+    }
+
+    fn enabled_record(&self, record: &Record) -> bool {
+        enabled_record(&self.a, record) || enabled_record(&self.b, record)
+    }
+
+    fn vlog(&self, record: &Record) {
+        if enabled_record(&self.a, record) {
+            self.a.vlog(record);
+        }
+        if enabled_record(&self.b, record) {
+            self.b.vlog(record);
+        }
+    }
+
+    fn clear(&self, surface: &str) {
+        self.a.clear(surface);
+        self.b.clear(surface);
+    }
+
+    fn transaction(&self, surface: &str, f: &dyn Fn(&dyn VLog)) {
+        self.a.transaction(surface, &|a| {
+            self.b.transaction(surface, &|b| f(&Tee { a, b }));
+        });
+    }
+
+    forward_vlog! {
+        a, b => remove_surface, begin_frame, end_frame, push_group, pop_group, flush, on_shutdown,
+        set_draw_mode, set_view
+    }
+
+    fn snapshot(&self, surface: &str, path: &str) -> Result<(), VLogError> {
+        self.a
+            .snapshot(surface, path)
+            .or_else(|_| self.b.snapshot(surface, path))
+    }
+
+    fn quantize_color(&self, color: Color) -> Color {
+        self.a.quantize_color(color)
+    }
+}
+
+/// Check if one of the vloggers wants the record, as the combined check doesn't tell which one.
+fn enabled_record(vlogger: &impl VLog, record: &Record) -> bool {
+    vlogger.enabled(record.metadata()) && vlogger.enabled_record(record)
+}
@@ -0,0 +1,246 @@
+//! Utilities for testing code which vlogs.
+//!
+//! Requires the `test-util` feature.
+
+use crate::{Metadata, Record, RecordOwned, SetVLoggerError, VLog};
+use std::cell::RefCell;
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, Once};
+
+/// A vlogger which records everything it receives, for assertions in tests.
+///
+/// Every [`Record`] is stored as a [`RecordOwned`], which owns its strings
+/// and the formatted message. Clones share the recorded data, so a clone
+/// can be moved into another vlogger, e.g. a [`FlattenVLogger`](crate::FlattenVLogger).
+///
+/// Requires the `test-util` feature.
+///
+/// # Examples
+///
+/// ```
+/// use v_log::test::CaptureVLogger;
+/// use v_log::{clear, point};
+///
+/// let cap = CaptureVLogger::new();
+/// cap.install().unwrap();
+///
+/// point!("main_surface", [1.0, 2.0], 5.0, Base, "o", "p{}", 1);
+/// clear!("debug_surface");
+/// point!("debug_surface", [0.0, 0.0], 5.0, Base);
+///
+/// assert_eq!(cap.records().len(), 2);
+/// assert_eq!(cap.records_for_surface("main_surface")[0].args(), "p1");
+/// assert_eq!(cap.clear_calls(), ["debug_surface"]);
+/// ```
+///
+/// It can also be passed to the macros directly:
+///
+/// ```
+/// use v_log::test::CaptureVLogger;
+/// use v_log::point;
+///
+/// let cap = CaptureVLogger::new();
+/// point!(vlogger: cap, "main_surface", [1.0, 2.0], 5.0, Base);
+/// assert_eq!(cap.records().len(), 1);
+/// ```
+///
+/// It can be [downcast](trait.VLog.html#method.downcast_ref) from a `dyn VLog`,
+/// e.g. when it is passed through code which only knows the trait object:
+///
+/// ```
+/// use v_log::test::CaptureVLogger;
+/// use v_log::{point, VLog};
+///
+/// let vlogger: Box<dyn VLog> = Box::new(CaptureVLogger::new());
+/// point!(vlogger: vlogger, "main_surface", [1.0, 2.0], 5.0, Base);
+/// let cap = vlogger.downcast_ref::<CaptureVLogger>().unwrap();
+/// assert_eq!(cap.records().len(), 1);
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct CaptureVLogger {
+    captured: Arc<Mutex<Captured>>,
+}
+
+// every record and clear gets the next value, so captures can be merged in the
+// order they received their calls, even across threads.
+static TIMESTAMP: AtomicU64 = AtomicU64::new(0);
+
+fn next_timestamp() -> u64 {
+    TIMESTAMP.fetch_add(1, Ordering::Relaxed)
+}
+
+/// The records and clears with the timestamp of their arrival.
+#[derive(Debug, Default)]
+struct Captured {
+    records: Vec<(u64, RecordOwned)>,
+    clears: Vec<(u64, String)>,
+}
+
+impl CaptureVLogger {
+    /// Create a new `CaptureVLogger` without any records.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make this the global vlogger of the current thread.
+    ///
+    /// The global vlogger can only be set once, so the first call installs a
+    /// vlogger which forwards to the `CaptureVLogger` installed on the calling thread.
+    /// This way every test can install its own `CaptureVLogger`, even when tests
+    /// run in parallel. Vlog calls on other threads, where no `CaptureVLogger` is
+    /// installed, are disabled. Installing again replaces the `CaptureVLogger`
+    /// of the current thread.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if a different global vlogger has already been set.
+    pub fn install(&self) -> Result<(), SetVLoggerError> {
+        static INSTALL: Once = Once::new();
+        static INSTALLED: AtomicBool = AtomicBool::new(false);
+        static THREAD_CAPTURE: ThreadCapture = ThreadCapture;
+        INSTALL.call_once(|| {
+            INSTALLED.store(
+                crate::set_vlogger(&THREAD_CAPTURE).is_ok(),
+                Ordering::SeqCst,
+            );
+        });
+        if !INSTALLED.load(Ordering::SeqCst) {
+            return Err(SetVLoggerError(()));
+        }
+        CURRENT.with(|current| *current.borrow_mut() = Some(self.clone()));
+        Ok(())
+    }
+
+    /// All records received so far, in order.
+    pub fn records(&self) -> Vec<RecordOwned> {
+        self.lock()
+            .records
+            .iter()
+            .map(|(_, record)| record.clone())
+            .collect()
+    }
+
+    /// The records received so far for a surface, in order.
+    pub fn records_for_surface(&self, surface: &str) -> Vec<RecordOwned> {
+        self.lock()
+            .records
+            .iter()
+            .filter(|(_, record)| record.surface() == surface)
+            .map(|(_, record)| record.clone())
+            .collect()
+    }
+
+    /// The surfaces of all [`clear`](VLog::clear) calls so far, in order.
+    pub fn clear_calls(&self) -> Vec<String> {
+        self.lock()
+            .clears
+            .iter()
+            .map(|(_, surface)| surface.clone())
+            .collect()
+    }
+
+    /// Combine the transcripts of two captures into a new `CaptureVLogger`.
+    ///
+    /// Each record and clear is timestamped when it is received, with a counter shared
+    /// by all captures of the process. The records of `a` and `b` are interleaved in the
+    /// order of these timestamps, and so are the clears, so the merged capture looks as if
+    /// it had received the calls of both. This is useful to replay the captures of
+    /// simulations which run in parallel, each with its own `CaptureVLogger`.
+    ///
+    /// The records and the clears are ordered separately, as they are stored separately,
+    /// so whether a clear came before or after a record is lost in the merge.
+    ///
+    /// The merged capture doesn't share its data with `a` or `b`, so later calls
+    /// to them aren't added to it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use v_log::test::CaptureVLogger;
+    /// use v_log::{clear, message};
+    ///
+    /// let (a, b) = (CaptureVLogger::new(), CaptureVLogger::new());
+    /// message!(vlogger: a, "main_surface", "a1");
+    /// message!(vlogger: b, "main_surface", "b1");
+    /// clear!(vlogger: b, "main_surface");
+    /// message!(vlogger: a, "main_surface", "a2");
+    /// clear!(vlogger: a, "debug_surface");
+    /// message!(vlogger: b, "main_surface", "b2");
+    ///
+    /// let merged = CaptureVLogger::merge(&a, &b);
+    /// let messages: Vec<_> = merged.records().iter().map(|r| r.args().to_string()).collect();
+    /// assert_eq!(messages, ["a1", "b1", "a2", "b2"]);
+    /// assert_eq!(merged.clear_calls(), ["main_surface", "debug_surface"]);
+    /// ```
+    pub fn merge(a: &CaptureVLogger, b: &CaptureVLogger) -> CaptureVLogger {
+        let (mut records, mut clears) = {
+            let a = a.lock();
+            (a.records.clone(), a.clears.clone())
+        };
+        {
+            // `a` is unlocked first, so merging a capture with a clone of itself doesn't deadlock.
+            let b = b.lock();
+            records.extend(b.records.iter().cloned());
+            clears.extend(b.clears.iter().cloned());
+        }
+        records.sort_by_key(|(timestamp, _)| *timestamp);
+        clears.sort_by_key(|(timestamp, _)| *timestamp);
+        CaptureVLogger {
+            captured: Arc::new(Mutex::new(Captured { records, clears })),
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Captured> {
+        self.captured.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl VLog for CaptureVLogger {
+    fn enabled(&self, _: &Metadata) -> bool {
+        true
+    }
+
+    fn vlog(&self, record: &Record) {
+        self.lock().records.push((next_timestamp(), record.into()));
+    }
+
+    fn clear(&self, surface: &str) {
+        self.lock().clears.push((next_timestamp(), surface.into()));
+    }
+
+    fn flush(&self) {}
+
+    fn as_any(&self) -> Option<&dyn std::any::Any> {
+        Some(self)
+    }
+}
+
+thread_local! {
+    static CURRENT: RefCell<Option<CaptureVLogger>> = const { RefCell::new(None) };
+}
+
+/// The global vlogger installed by [`CaptureVLogger::install`].
+#[derive(Debug)]
+struct ThreadCapture;
+
+impl ThreadCapture {
+    fn with<R>(&self, f: impl FnOnce(&CaptureVLogger) -> R) -> Option<R> {
+        CURRENT.with(|current| current.borrow().as_ref().map(f))
+    }
+}
+
+impl VLog for ThreadCapture {
+    fn enabled(&self, _: &Metadata) -> bool {
+        self.with(|_| ()).is_some()
+    }
+
+    fn vlog(&self, record: &Record) {
+        self.with(|capture| capture.vlog(record));
+    }
+
+    fn clear(&self, surface: &str) {
+        self.with(|capture| capture.clear(surface));
+    }
+
+    fn flush(&self) {}
+}
@@ -0,0 +1,194 @@
+//! Development time checks of the records sent by the macros.
+
+use crate::{Record, Visual};
+use std::collections::BTreeSet;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::Mutex;
+
+static ENABLED: AtomicBool = AtomicBool::new(false);
+// the issues which have been reported, per call site.
+static WARNED: Mutex<BTreeSet<(&'static str, u64)>> = Mutex::new(BTreeSet::new());
+static WARNINGS: AtomicUsize = AtomicUsize::new(0);
+
+/// Enables or disables validating the records sent by the vlogging macros.
+///
+/// If enabled, every record sent to an enabled target is checked for common mistakes, like non-finite
+/// coordinates, an empty surface name, a NaN size or image data of the wrong size.
+/// Each issue is reported as a warning on stderr once per call site. The records
+/// are still sent to the vlogger unchanged. This is meant as a diagnostic during
+/// development.
+///
+/// Requires the `std` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use v_log::{Metadata, Record, VLog};
+/// # struct AllVLogger;
+/// # impl VLog for AllVLogger {
+/// #     fn enabled(&self, _: &Metadata) -> bool { true }
+/// #     fn vlog(&self, _: &Record) {}
+/// #     fn clear(&self, _: &str) {}
+/// #     fn flush(&self) {}
+/// # }
+/// use v_log::point;
+///
+/// let vlogger = AllVLogger;
+/// v_log::enable_validation(true);
+/// for x in [0.0, f64::NAN, f64::NAN] {
+///     point!(vlogger: vlogger, "main_surface", [x, 0.0], 5.0, Base);
+/// }
+/// assert_eq!(v_log::validation_warnings(), 1);
+/// point!(vlogger: vlogger, "main_surface", [f64::INFINITY, 0.0], 5.0, Base);
+/// assert_eq!(v_log::validation_warnings(), 2);
+/// ```
+pub fn enable_validation(enabled: bool) {
+    ENABLED.store(enabled, Ordering::Relaxed);
+}
+
+/// The number of warnings reported by the validation, see [`enable_validation`].
+///
+/// Requires the `std` feature.
+pub fn validation_warnings() -> usize {
+    WARNINGS.load(Ordering::Relaxed)
+}
+
+/// Whether the validation is enabled, see [`enable_validation`].
+pub(crate) fn enabled() -> bool {
+    ENABLED.load(Ordering::Relaxed)
+}
+
+/// Called by the private API for every record.
+#[inline]
+pub(crate) fn check(record: &Record) {
+    if ENABLED.load(Ordering::Relaxed) {
+        for issue in issues(record) {
+            warn(record, issue);
+        }
+    }
+}
+
+fn issues(record: &Record) -> impl Iterator<Item = &'static str> {
+    let finite = match record.visual() {
+        Visual::Message => true,
+        Visual::Image { x, y, z, .. } => [x, y, z].iter().all(|c| c.is_finite()),
+        Visual::Label {
+            x, y, z, max_width, ..
+        } => [x, y, z]
+            .into_iter()
+            .chain(max_width)
+            .all(|c| c.is_finite()),
+        Visual::Point { x, y, z, .. } => [x, y, z].iter().all(|c| c.is_finite()),
+        Visual::Line {
+            x1,
+            y1,
+            z1,
+            x2,
+            y2,
+            z2,
+            ..
+        } => [x1, y1, z1, x2, y2, z2].iter().all(|c| c.is_finite()),
+        Visual::Ray {
+            origin, direction, ..
+        } => origin.iter().chain(direction).all(|c| c.is_finite()),
+        Visual::Arc {
+            cx,
+            cy,
+            cz,
+            radius,
+            start_angle,
+            end_angle,
+            ..
+        } => [cx, cy, cz, radius, start_angle, end_angle]
+            .iter()
+            .all(|c| c.is_finite()),
+        Visual::Aabb { min, max, .. } => min.iter().chain(max).all(|c| c.is_finite()),
+        Visual::Obb {
+            center,
+            half_extents,
+            rotation,
+        } => center
+            .iter()
+            .chain(half_extents)
+            .chain(rotation)
+            .all(|c| c.is_finite()),
+        Visual::Axes {
+            origin,
+            rotation,
+            length,
+        } => origin
+            .iter()
+            .chain(rotation)
+            .chain([length])
+            .all(|c| c.is_finite()),
+        Visual::Ellipse {
+            cx,
+            cy,
+            cz,
+            rx,
+            ry,
+            rotation,
+            ..
+        } => [cx, cy, cz, rx, ry, rotation].iter().all(|c| c.is_finite()),
+        Visual::Grid {
+            spacing, extent, ..
+        } => spacing.is_finite() && extent.is_finite(),
+        Visual::Bezier { p0, p1, p2, p3, .. } => [p0, p1, p2, p3]
+            .into_iter()
+            .flatten()
+            .all(|c| c.is_finite()),
+        Visual::Spline { points, .. }
+        | Visual::Polygon { points, .. }
+        | Visual::PointCloud { points, .. }
+        | Visual::Mesh {
+            vertices: points, ..
+        } => points.iter().flatten().all(|c| c.is_finite()),
+        Visual::VectorField {
+            origins,
+            vectors,
+            scale,
+        } => {
+            scale.is_finite()
+                && origins
+                    .iter()
+                    .chain(vectors)
+                    .flatten()
+                    .all(|c| c.is_finite())
+        }
+    };
+    [
+        (!finite, "non-finite coordinates"),
+        (record.surface().is_empty(), "empty surface name"),
+        (record.size().is_nan(), "NaN size"),
+        (
+            matches!(record.visual(), Visual::Image { width, height, format, data, .. }
+                if *width as usize * *height as usize * format.bytes_per_pixel() != data.len()),
+            "image size mismatch",
+        ),
+    ]
+    .into_iter()
+    .filter_map(|(failed, issue)| failed.then_some(issue))
+}
+
+fn warn(record: &Record, issue: &'static str) {
+    // the set is always in a valid state, so poisoning can be ignored.
+    let mut warned = WARNED.lock().unwrap_or_else(|e| e.into_inner());
+    if !warned.insert((issue, record.call_site_id())) {
+        return;
+    }
+    WARNINGS.fetch_add(1, Ordering::Relaxed);
+    match (record.file(), record.line()) {
+        (Some(file), Some(line)) => eprintln!(
+            "v-log: {} in record to surface {:?} at {}:{}",
+            issue,
+            record.surface(),
+            file,
+            line
+        ),
+        _ => eprintln!(
+            "v-log: {} in record to surface {:?}",
+            issue,
+            record.surface()
+        ),
+    }
+}